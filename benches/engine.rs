@@ -0,0 +1,92 @@
+// Engine benchmarks: dealing, hand evaluation, equity and action legality.
+// Run with `cargo bench`. The full-hand group reports hands/second; the simulator
+// needs roughly 50k full hands/second on a typical laptop to be practical.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use p_kr::game::{BotDifficulty, Game, Round};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const STARTING_CHIPS: u32 = 1000;
+
+// Nine bots, no API key, fixed card seed
+fn bot_table() -> Game {
+    let mut game = Game::new(0, 9, BotDifficulty::Medium, STARTING_CHIPS, 5, 10, None, String::new());
+    game.reseed(42);
+    game
+}
+
+// Deal a hand and let the bots play it out to showdown, the same way the main loop drives them
+fn play_hand(game: &mut Game) -> (usize, u32, String) {
+    for player in &mut game.players {
+        player.chips = STARTING_CHIPS;
+    }
+    game.deal_cards();
+    
+    // Cap the number of actions so a bug can't hang the benchmark
+    for _ in 0..500 {
+        if game.round == Round::Showdown {
+            break;
+        }
+        let bot_player = game.players[game.current_player_idx].clone();
+        if let Ok(action) = game.get_bot_action(&bot_player) {
+            game.perform_action(action);
+        }
+        if !game.next_player() {
+            break;
+        }
+    }
+    
+    game.determine_winner()
+}
+
+// A table with hole cards dealt and the board run out to the given number of cards
+fn table_with_board(board_cards: usize) -> Game {
+    let mut game = bot_table();
+    game.deal_cards();
+    for _ in 0..board_cards {
+        let card = game.deck.pop().unwrap();
+        game.community_cards.push(card);
+    }
+    game
+}
+
+fn bench_full_hand(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full_hand");
+    group.throughput(Throughput::Elements(1));
+    let mut game = bot_table();
+    group.bench_function("9_players_to_showdown", |b| {
+        b.iter(|| black_box(play_hand(&mut game)))
+    });
+    group.finish();
+}
+
+fn bench_evaluation(c: &mut Criterion) {
+    // rs_poker picks the best five of the seven cards (all 21 combinations) when ranking
+    let game = table_with_board(5);
+    c.bench_function("best_five_of_seven", |b| {
+        b.iter(|| black_box(game.evaluate_hand(black_box(0))))
+    });
+}
+
+fn bench_equity(c: &mut Criterion) {
+    let game = table_with_board(3);
+    let mut group = c.benchmark_group("equity");
+    for iterations in [100, 1000] {
+        group.bench_with_input(BenchmarkId::from_parameter(iterations), &iterations, |b, &iterations| {
+            let mut rng = StdRng::seed_from_u64(7);
+            b.iter(|| black_box(game.estimate_equity(0, iterations, &mut rng)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_legal_actions(c: &mut Criterion) {
+    let game = table_with_board(0);
+    c.bench_function("legal_actions", |b| {
+        b.iter(|| black_box(game.legal_actions()))
+    });
+}
+
+criterion_group!(benches, bench_full_hand, bench_evaluation, bench_equity, bench_legal_actions);
+criterion_main!(benches);
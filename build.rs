@@ -0,0 +1,47 @@
+// Build info for `p_kr --version --verbose`: the git commit, the enabled cargo features and the
+// rs_poker version from Cargo.lock, passed to the crate as compile-time environment variables.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let root = Path::new(&manifest_dir);
+
+    // Short commit hash, with "-dirty" for uncommitted changes; "unknown" outside a git checkout
+    let git = |args: &[&str]| {
+        Command::new("git").args(args).current_dir(root).output().ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let hash = match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if !hash.is_empty() => {
+            let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+            if dirty { format!("{}-dirty", hash) } else { hash }
+        },
+        _ => "unknown".to_string(),
+    };
+    println!("cargo:rustc-env=GIT_HASH={}", hash);
+
+    // Cargo sets CARGO_FEATURE_<NAME> for each enabled feature
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|name| name.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=P_KR_FEATURES={}", features.join(","));
+
+    // The rs_poker version actually locked, not just the range asked for in Cargo.toml
+    let lock = fs::read_to_string(root.join("Cargo.lock")).unwrap_or_default();
+    let rs_poker = lock.split("[[package]]")
+        .find(|package| package.lines().any(|line| line.trim() == "name = \"rs_poker\""))
+        .and_then(|package| package.lines().find_map(|line| line.trim().strip_prefix("version = \"")))
+        .map(|version| version.trim_end_matches('"').to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=RS_POKER_VERSION={}", rs_poker);
+
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+}
@@ -0,0 +1,42 @@
+// Seeded round-robin between the library's strategies.
+// Run with `cargo run --release --example bot_tournament`.
+
+use std::time::Instant;
+
+use p_kr::game::BotDifficulty;
+use p_kr::simulator::Simulator;
+use p_kr::strategy::{CallingStation, EquityBot, HeuristicBot, Strategy};
+
+const HANDS: u32 = 10_000;
+const SEED: u64 = 2024;
+const STARTING_CHIPS: u32 = 1000;
+const SMALL_BLIND: u32 = 5;
+const BIG_BLIND: u32 = 10;
+
+fn main() {
+    let strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(HeuristicBot { difficulty: BotDifficulty::Easy }),
+        Box::new(HeuristicBot { difficulty: BotDifficulty::Hard }),
+        Box::new(EquityBot::new(200, SEED)),
+        Box::new(CallingStation),
+    ];
+
+    let mut simulator = Simulator::new(strategies, STARTING_CHIPS, SMALL_BLIND, BIG_BLIND, SEED);
+    let started = Instant::now();
+    simulator.run(HANDS);
+    let elapsed = started.elapsed();
+
+    println!("{} hands, blinds {}/{}, seed {} ({:.1}s)\n",
+             simulator.hands_played, SMALL_BLIND, BIG_BLIND, SEED, elapsed.as_secs_f64());
+    println!("{:<20} {:>12} {:>10} {:>8}", "Strategy", "Net chips", "bb/100", "Wins");
+
+    // Best result first
+    let names = simulator.strategy_names();
+    let mut seats: Vec<usize> = (0..names.len()).collect();
+    seats.sort_by_key(|&seat| std::cmp::Reverse(simulator.net[seat]));
+
+    for seat in seats {
+        let bb_per_100 = simulator.net[seat] as f64 / BIG_BLIND as f64 / simulator.hands_played as f64 * 100.0;
+        println!("{:<20} {:>12} {:>10.1} {:>8}", names[seat], simulator.net[seat], bb_per_100, simulator.wins[seat]);
+    }
+}
@@ -0,0 +1,28 @@
+// Deal fairness audit: plays many hands and checks that every card reaches every seat and
+// the board about equally often, and that no card turns up twice in a hand.
+// Run with `cargo run --release --example deal_audit -- [HANDS] [SEED]`.
+
+use std::time::Instant;
+
+use p_kr::audit::{self, AUDIT_SEATS};
+
+const DEFAULT_HANDS: u64 = 100_000;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let hands = args.next().and_then(|a| a.parse().ok()).unwrap_or(DEFAULT_HANDS);
+    let seed = args.next().and_then(|a| a.parse().ok()).unwrap_or_else(rand::random::<u64>);
+
+    let started = Instant::now();
+    let audit = audit::audit_deals(hands, seed);
+
+    println!("{} hands, {} seats, seed {} ({:.1}s)\n", audit.hands, AUDIT_SEATS, seed, started.elapsed().as_secs_f64());
+    let (lines, passed) = audit.report();
+    for line in lines {
+        println!("{}", line);
+    }
+    println!("\nOverall: {}", if passed { "PASS" } else { "FAIL" });
+    if !passed {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,38 @@
+// Play one hand with the cards set in advance and check who wins it.
+// Run with `cargo run --example scripted_hand`.
+
+use p_kr::game::{Card, Rank, Suit};
+use p_kr::simulator::Simulator;
+use p_kr::strategy::{CallingStation, Strategy};
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card { rank, suit }
+}
+
+fn main() {
+    // Everyone calls down, so the cards alone decide the hand
+    let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(CallingStation), Box::new(CallingStation), Box::new(CallingStation)];
+    let mut simulator = Simulator::new(strategies, 1000, 5, 10, 7);
+
+    let hands = [
+        [card(Rank::Ace, Suit::Spades), card(Rank::King, Suit::Spades)],
+        [card(Rank::Queen, Suit::Hearts), card(Rank::Queen, Suit::Diamonds)],
+        [card(Rank::Seven, Suit::Clubs), card(Rank::Two, Suit::Diamonds)],
+    ];
+    let board = [
+        card(Rank::Ace, Suit::Hearts), card(Rank::King, Suit::Diamonds), card(Rank::Nine, Suit::Clubs),
+        card(Rank::Four, Suit::Spades), card(Rank::Two, Suit::Hearts),
+    ];
+    simulator.game.stack_deck(&hands, &board);
+
+    let winner = simulator.play_hand();
+    let board_text: Vec<String> = simulator.game.community_cards.iter().map(|c| c.to_string()).collect();
+    println!("Board: {}", board_text.join(" "));
+    for (seat, player) in simulator.game.players.iter().enumerate() {
+        println!("Seat {}: {}{} -> ${}", seat, player.hand[0], player.hand[1], player.chips);
+    }
+
+    // Aces and Kings beats Queens and the Twos
+    assert_eq!(winner, 0);
+    println!("Seat {} wins, as scripted.", winner);
+}
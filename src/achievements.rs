@@ -0,0 +1,159 @@
+// Fun milestones for the human player. Each badge is earned once and kept in the profile.
+// Adding a badge is one entry in ACHIEVEMENTS: a predicate over how the last hand went.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How a settled hand went from the human's seat
+#[derive(Clone, Debug, Default)]
+pub struct HandSummary {
+    pub won: bool,                      // Came out of the hand ahead
+    pub showdown: bool,                 // The pot was contested to the end
+    pub made_hand: Option<&'static str>, // Best hand at the end (e.g. "Flush"), if they saw a board without folding
+    pub win_streak: usize,              // Hands won in a row, including this one
+    pub chips: u32,                     // Stack after the hand
+    pub starting_chips: u32,            // Stack the session started with
+    pub net_gain: u32,                  // Chips gained this hand (0 if they lost chips)
+    pub big_blind: u32,
+    pub raised_river: bool,             // Bet or raised on the river
+}
+
+pub struct Achievement {
+    pub id: &'static str, // Stable key stored in the profile
+    pub name: &'static str,
+    pub description: &'static str,
+    earned: fn(&HandSummary) -> bool,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_flush",
+        name: "Suited Up",
+        description: "Make a flush",
+        earned: |h| h.made_hand == Some("Flush"),
+    },
+    Achievement {
+        id: "first_full_house",
+        name: "Full House",
+        description: "Make a full house",
+        earned: |h| h.made_hand == Some("Full House"),
+    },
+    Achievement {
+        id: "five_in_a_row",
+        name: "On a Heater",
+        description: "Win 5 hands in a row",
+        earned: |h| h.win_streak >= 5,
+    },
+    Achievement {
+        id: "double_up",
+        name: "Double Up",
+        description: "Double your starting stack",
+        earned: |h| h.starting_chips > 0 && h.chips >= h.starting_chips * 2,
+    },
+    Achievement {
+        id: "big_pot",
+        name: "Monster Pot",
+        description: "Win 100 big blinds or more in one hand",
+        earned: |h| h.won && h.net_gain >= h.big_blind * 100,
+    },
+    Achievement {
+        id: "river_bluff",
+        name: "Nobody Called",
+        description: "Bet or raise the river and win without a showdown",
+        earned: |h| h.won && !h.showdown && h.raised_river,
+    },
+];
+
+// A badge the player has, with when it was earned (seconds since the Unix epoch)
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarnedAchievement {
+    pub id: String,
+    pub earned_at: u64,
+}
+
+impl EarnedAchievement {
+    pub fn achievement(&self) -> Option<&'static Achievement> {
+        ACHIEVEMENTS.iter().find(|a| a.id == self.id)
+    }
+
+    // "2024-06-01" (UTC)
+    pub fn date(&self) -> String {
+        civil_date(self.earned_at)
+    }
+}
+
+// Badges this hand earns that aren't in `earned` yet. They're added to `earned` (stamped now)
+// and returned so the caller can announce them.
+pub fn award(summary: &HandSummary, earned: &mut Vec<EarnedAchievement>) -> Vec<&'static Achievement> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut new = Vec::new();
+    for achievement in ACHIEVEMENTS {
+        if earned.iter().any(|e| e.id == achievement.id) || !(achievement.earned)(summary) {
+            continue;
+        }
+        earned.push(EarnedAchievement { id: achievement.id.to_string(), earned_at: now });
+        new.push(achievement);
+    }
+    new
+}
+
+// Unix seconds to a YYYY-MM-DD date (days-from-civil, inverted)
+fn civil_date(secs: u64) -> String {
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(new: &[&Achievement]) -> Vec<&'static str> {
+        new.iter().map(|a| a.id).collect()
+    }
+
+    fn earns(summary: HandSummary) -> Vec<&'static str> {
+        ids(&award(&summary, &mut Vec::new()))
+    }
+
+    #[test]
+    fn each_badge_has_its_own_trigger() {
+        let base = HandSummary { starting_chips: 1000, chips: 1000, big_blind: 10, ..Default::default() };
+        assert!(earns(base.clone()).is_empty());
+        assert_eq!(earns(HandSummary { made_hand: Some("Flush"), ..base.clone() }), vec!["first_flush"]);
+        assert_eq!(earns(HandSummary { made_hand: Some("Full House"), ..base.clone() }), vec!["first_full_house"]);
+        assert_eq!(earns(HandSummary { win_streak: 5, ..base.clone() }), vec!["five_in_a_row"]);
+        assert_eq!(earns(HandSummary { chips: 2000, ..base.clone() }), vec!["double_up"]);
+        assert_eq!(earns(HandSummary { won: true, showdown: true, net_gain: 1000, ..base.clone() }), vec!["big_pot"]);
+        assert_eq!(earns(HandSummary { won: true, raised_river: true, ..base.clone() }), vec!["river_bluff"]);
+        assert!(earns(HandSummary { won: true, showdown: true, raised_river: true, ..base }).is_empty(),
+                "a called river bet is no bluff");
+    }
+
+    #[test]
+    fn badge_is_awarded_only_once() {
+        let summary = HandSummary { made_hand: Some("Flush"), ..Default::default() };
+        let mut earned = Vec::new();
+        assert_eq!(ids(&award(&summary, &mut earned)), vec!["first_flush"]);
+        assert!(award(&summary, &mut earned).is_empty());
+        assert_eq!(earned.len(), 1);
+        assert_eq!(earned[0].achievement().map(|a| a.name), Some("Suited Up"));
+    }
+
+    #[test]
+    fn civil_date_handles_epoch_and_leap_days() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(951_782_400), "2000-02-29");
+        assert_eq!(civil_date(1_709_251_199), "2024-02-29");
+        assert_eq!(civil_date(1_709_251_200), "2024-03-01");
+        assert_eq!(civil_date(4_102_444_800), "2100-01-01");
+        assert_eq!(EarnedAchievement { id: "big_pot".to_string(), earned_at: 1_717_200_000 }.date(), "2024-06-01");
+    }
+}
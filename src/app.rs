@@ -1,14 +1,193 @@
-use std::time::Instant;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::{Duration, Instant};
 use crossterm::event::KeyCode;
 use rand::Rng;
-use crate::game::{Game, GameAction, BotDifficulty, Round, Player};
+use crate::game::{self, BlindKind, Card, Game, GameAction, GameEvent, BotDifficulty, PendingBotAction, PotAward, PreflopClass, Round, Player};
+use crate::version::BuildInfo;
 use crate::util;
 use crate::util::get_player_position;
+use crate::config::{self, Config, Verbosity};
+use crate::flavor::{self, FlavorEvent};
+use crate::session::{self, LedgerKind, SessionSnapshot, SessionStats};
+use crate::profile::{self, OpponentRecord, Profile};
+use crate::achievements::{self, EarnedAchievement, HandSummary};
+use crate::drill::{self, DrillSession};
+use crate::keys::{KeyAction, KeyBindings};
+use crate::whatif;
+use crate::range::HandRange;
+
+// Largest starting stack accepted at runtime - keeps the sum of all stacks well inside u32
+const MAX_STARTING_CHIPS: u32 = 100_000_000;
+
+// How long a bot's turn may sit idle (with no think timer pending) before the watchdog steps in
+const BOT_WATCHDOG_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How long to wait on an API decision before playing the built-in bot's move instead
+// (inside the watchdog's limit, so a slow reply never trips it)
+const API_DECISION_TIMEOUT: Duration = Duration::from_secs(8);
+
+// Delay between each flop card when the flop is revealed one card at a time
+const FLOP_REVEAL_STEP: Duration = Duration::from_millis(350);
+
+// Upper bound on bot actions when a stopped hand is played out, so a stuck round can't hang the UI
+const STOP_FAST_FORWARD_ACTIONS: usize = 200;
+
+// Simulated bot thinking times, in milliseconds
+const BOT_THINK_FIRST_ACTION_MS: Range<u64> = 3000..4000; // First action after the deal
+const BOT_THINK_HAND_START_MS: Range<u64> = 2000..3000;   // Other early actions in a hand
+const BOT_THINK_MS: Range<u64> = 1500..2500;              // Bot acting after another bot
+const BOT_THINK_AFTER_HUMAN_MS: Range<u64> = 1500..3000;  // Bot acting after the human
+
+// How long a chip movement note stays beside the pot (it dims for the second half)
+pub const TRANSIENT_LIFETIME: Duration = Duration::from_millis(1800);
+
+// Message log share of the main column's free height: '+'/'-' move it a step at a time.
+// Whatever it's set to, the log and the game info panel keep their minimum heights.
+const LOG_SHARE_STEP: u16 = 10;
+const LOG_SHARE_MIN: u16 = 20;
+
+// Monte Carlo deals behind the equity estimate in the pot-odds advice
+const POT_ODDS_EQUITY_ITERATIONS: usize = 2000;
+// Range put on an opponent by V with no percentage typed
+const DEFAULT_RANGE_PERCENT: u8 = 20;
+
+// What's running, for the top of the log so screenshots and bug reports describe themselves
+pub fn startup_summary(config: &Config, game: &Game, seed: u64) -> Vec<String> {
+    let build = BuildInfo::current();
+    let bots = game.players.iter().filter(|p| p.is_bot).count();
+    let mut structure = vec![if config.pot_limit { "Pot-limit" } else { "No-limit" }.to_string()];
+    if config.short_deck {
+        structure.push("short deck".to_string());
+    }
+    if let Some(cap) = config.max_raises_per_street {
+        structure.push(format!("{} raises per street", cap));
+    }
+    structure.push(if config.button_ante { "button ante" } else { "antes" }.to_string());
+    if config.allow_straddle {
+        structure.push("straddles".to_string());
+    }
+    if config.rake_percent > 0 {
+        let cap = config.rake_cap.map(|c| format!(", cap {}", util::format_chips(c))).unwrap_or_default();
+        structure.push(format!("{}% rake{}", config.rake_percent, cap));
+    }
+    
+    let mut lines = vec![
+        format!("{} - rs_poker {} - seed {}", build.short(), build.rs_poker, seed),
+        format!("Table: you + {} bots, {} stacks, blinds {}/{} ({})",
+                bots, util::format_chips(config.starting_chips),
+                util::format_chips(config.small_blind), util::format_chips(config.big_blind), structure.join(", ")),
+        match game.api_key {
+            Some(_) => format!("Bots: OpenAI API (heuristic fallback, at most {} request{} at once)",
+                               game.api_limiter.max(), if game.api_limiter.max() == 1 { "" } else { "s" }),
+            None if !cfg!(feature = "llm") => "Bots: built-in heuristics (built without the llm feature)".to_string(),
+            None => "Bots: built-in heuristics (no OPENAI_API_KEY)".to_string(),
+        },
+        "Config: built-in defaults plus command-line flags (no config file)".to_string(),
+    ];
+    if !cfg!(feature = "history") {
+        lines.push("History: off (built without the history feature) - no autosave or profile, this session stays in memory".to_string());
+    }
+    lines
+}
+
+// Describe a think-time range, e.g. 1500..2500 -> "1.5-2.5s"
+fn think_range_display(range: Range<u64>) -> String {
+    format!("{}-{}s", range.start as f64 / 1000.0, range.end as f64 / 1000.0)
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum InputMode {
     Normal,   // Regular game input
     PlayerName, // Entering player name
+    ConfirmNewSession, // Waiting for y/n before resetting chips and stats
+    DealMaking, // Heads-up all-in: waiting for y/n on an equity chop
+    RecoverSession, // Startup: waiting for y/n on restoring an autosaved session
+    PostBlind, // Before a deal: waiting for y/n on posting the human's blind
+    TableSetup, // Between hands: adding/removing bots and changing their difficulty
+    SessionLimit, // After a hand: the stop-loss or stop-win was hit, waiting for y (quit) / n (play on)
+    Settings, // Settings screen: arrows pick an option, Enter changes it
+    TableWon, // Victory screen after the human takes every chip: n (new session) / r (bots rebuy) / q (quit)
+    Busted, // The human has no chips: b (buy back in) / q (quit); no dealing until then
+    WhatIf, // Replaying the last hand with a changed decision: pick the decision, then the action
+}
+
+// Runtime options on the settings screen, in display order
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Setting {
+    Verbosity,
+    ShowdownStyle,
+    StagedFlop,
+    AsciiSuits,
+    TableTalk,
+    ShowdownRanking,
+    PotOdds,
+    Coach,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 8] = [
+        Setting::Verbosity, Setting::ShowdownStyle, Setting::StagedFlop, Setting::AsciiSuits,
+        Setting::TableTalk, Setting::ShowdownRanking, Setting::PotOdds, Setting::Coach,
+    ];
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            Setting::Verbosity => "Log verbosity",
+            Setting::ShowdownStyle => "Showdown style",
+            Setting::StagedFlop => "Flop reveal",
+            Setting::AsciiSuits => "Suits",
+            Setting::TableTalk => "Bot table talk",
+            Setting::ShowdownRanking => "Showdown ranking",
+            Setting::PotOdds => "Pot odds advice",
+            Setting::Coach => "Coach (outs)",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum ShowdownStyle {
+    Cinematic, // Reveal hands one by one with pauses
+    Fast,      // Collapse the showdown into a single result line
+}
+
+// Pot odds for the human's current decision against their estimated equity (--pot-odds)
+pub struct PotOddsAdvice {
+    pub spot: (u32, Round, u32, u32), // Hand number, street, pot and call it was worked out for
+    pub ratio: String,   // "3:1"
+    pub need: f64,       // Break-even equity for the call, 0.0-1.0
+    pub equity: f64,     // Estimated share of the pot against random hands, 0.0-1.0
+}
+
+// The human's equity against one opponent's assumed hand range (V)
+pub struct RangeEquity {
+    pub spot: (u32, Round, usize, u8), // Hand number, street, opponent and range percent it was worked out for
+    pub opponent: String,
+    pub range: String,   // "top 10%"
+    pub equity: f64,     // Estimated share of the pot, 0.0-1.0
+}
+
+// How settle_hand paid out the hand, for the result lines each ending prints
+struct SettledHand {
+    winner_idx: usize,
+    winnings: u32,
+    hand_type: String,
+    human_took_pot: bool, // The human is the main pot's winner, for naming who won it
+    profit: i32,       // The human's net for this hand
+    total_profit: i32, // The human's session net, this hand included
+}
+
+impl PotOddsAdvice {
+    pub fn positive(&self) -> bool {
+        self.equity > self.need
+    }
+    
+    // "equity 41% vs 25% needed, call is +EV"
+    pub fn verdict(&self) -> String {
+        format!("equity {:.0}% vs {:.0}% needed, call is {}",
+                self.equity * 100.0, self.need * 100.0, if self.positive() { "+EV" } else { "-EV" })
+    }
 }
 
 pub struct App {
@@ -16,325 +195,2312 @@ pub struct App {
     pub input: String,
     pub messages: Vec<String>,
     pub should_quit: bool,
-    pub player_starting_chips: u32, // To track wins/losses
+    pub player_starting_chips: u32, // Stack at the deal, to cross-check the profit ledger
     pub round_results: Option<(String, i32)>, // (Winner name, player profit/loss)
     pub game_stats: Vec<i32>, // Track player profits across multiple rounds
+    pub hand_results: Vec<bool>, // Whether the human won each hand (parallel to game_stats)
     pub bot_thinking: bool,         // To simulate bot thinking time
     pub bot_think_until: Instant, // When bot should finish "thinking"
+    pub pending_bot_action: Option<PendingBotAction>, // API decision being fetched on a worker thread
     pub game_active: bool,          // Whether a game is currently in progress
     pub message_scroll_pos: usize,  // Position in message history for scrolling
     pub input_mode: InputMode,      // Current input mode (raise amount or player name)
+    pub staged_flop: bool,          // Reveal the flop one card at a time like a dealer spreading it
+    pub flop_reveal: Option<(usize, Instant)>, // (Flop cards shown, when the next one appears)
+    pub config: Config,             // Startup configuration (blinds, starting chips)
+    pub next_session_chips: u32,    // Starting stack to use the next time a session is reset
+    pub leaderboard: Vec<(usize, u32)>, // Chip standings as of the end of the last hand
+    pub last_progress_at: Instant,  // Last time the hand moved forward (deal or any action)
+    pub showdown_style: ShowdownStyle, // How hand results are presented
+    pub chop_offer: Vec<(usize, u32, f64)>, // Proposed chop while in DealMaking: (player, chips, equity)
+    pub pending_recovery: Option<SessionSnapshot>, // Autosave found at startup, until accepted or declined
+    pub autosaved_hands: usize,     // Settled hands (game_stats entries) covered by the last autosave
+    pub auto_call: Option<(u32, Round)>, // Standing "call up to $N" for the rest of the given street
+    pub human_decision_ms: Vec<u64>, // How long the human took over each (manual) decision this session
+    pub session_stats: SessionStats, // Pace of play since the session started
+    pub winning_cards: Vec<Card>,   // Best five of the last showdown's winner, highlighted until the next deal
+    pub winning_reveal_msg: Option<usize>, // Message index of the winner's "shows" line
+    pub rabbit_hunts_left: u32,     // Looks at the undealt board still allowed this session
+    pub rabbit_hunted: bool,        // Already looked at this hand's undealt board
+    pub table_changes: Vec<String>, // Seats added/removed and difficulty changes this session, in order
+    pub modal_opened_at: Option<Instant>, // When the current non-Normal input mode was entered
+    pub opponent_records: HashMap<String, OpponentRecord>, // Per-bot showdown tracking, keyed by Player::record_key (kept in the profile)
+    pub hand_start_msg: Option<usize>,  // Message index where the current (or last) hand's log begins
+    pub showdown_msg: Option<usize>,    // Message index where the last contested showdown's result begins
+    pub seed: u64,                  // Card seed for this session's deals (pass --seed to replay it)
+    pub auto_fold_below: Option<PreflopClass>, // Preflop, fold hands weaker than this when facing a raise
+    pub table_talk: Option<String>, // A bot's reaction to the hand just settled, shown once the result is out
+    pub talked_hand: Option<u32>,   // Hand number table talk was last considered for (one chance per hand)
+    pub verbosity: Verbosity,       // How much goes into the game log ('v' cycles it)
+    pub achievements: Vec<EarnedAchievement>, // Badges earned so far (kept in the profile)
+    pub achievement_lines: Vec<String>, // Badges the hand just settled earned, announced once the result is out
+    pub drill: Option<DrillSession>, // Training drill in progress ('d' sets up the next scenario instead of dealing)
+    pub keys: KeyBindings,          // Single-key commands, as bound by --keys or the defaults
+    pub pot_odds: Option<PotOddsAdvice>, // Advice for the human's current call, with --pot-odds
+    pub limits_checked_hands: usize, // Settled hands the stop-loss/stop-win check has looked at
+    pub settings_cursor: usize,     // Highlighted row on the settings screen (index into Setting::ALL)
+    pub input_cursor: usize,        // Where typed digits go in the amount being entered (clamped to its length)
+    pub input_spot: Option<(u32, Round)>, // Hand and street the amount was typed on (None between hands)
+    pub shown_on_fold: Vec<Card>,   // Hole cards the human showed while folding this hand (until the next deal)
+    pub knockouts: Vec<(String, Option<String>)>, // (Eliminated bot, the bot that busted them; None when it was you)
+    pub outcomes_checked_hands: usize, // Settled hands check_eliminations has looked at
+    pub transients: Vec<(String, Instant)>, // Chip movement notes beside the pot ("+$30 from B2"), until they expire
+    pub log_share: u16,             // Percent of the main column's free height given to the message log (the rest to game info)
+    pub hand_snapshot: Option<Game>, // The table as the current (or last) hand was dealt, for what-if replays
+    pub what_if_decision: Option<usize>, // The decision being replayed (index into the hand's action history)
+    pub range_focus: Option<(usize, HandRange)>, // Opponent seat and the range the human puts them on (V)
+    pub range_equity: Option<RangeEquity>, // Equity against that range on the human's turn
 }
 
 impl App {
-    pub fn new(api_key: Option<String>, player_name: String) -> Self {
+    pub fn new(api_key: Option<String>, player_name: String, config: Config) -> Self {
         // Starting chips amount
-        let starting_chips = 100;
+        let starting_chips = config.starting_chips;
         
         // Set up a game with 1 human player and 8 bots (total 9 players)
-        let game = Game::new(1, 8, BotDifficulty::Medium, starting_chips, api_key, player_name);
+        let mut game = Game::new(1, 8, BotDifficulty::Medium, starting_chips, config.small_blind, config.big_blind, api_key, player_name);
+        config.apply_to(&mut game);
+        
+        // Bring back the bots (and what we know about them) from the profile, unless asked not to
+        let saved_profile = if config.fresh_table { None } else { profile::read_profile() };
+        let mut opponent_records = HashMap::new();
+        let mut earned_achievements = Vec::new();
+        let mut profile_notice = None;
+        if let Some(saved) = saved_profile {
+            match saved.seat_roster(&mut game, starting_chips) {
+                Ok(()) => profile_notice = Some(format!("Your table is back: {} bots from {} (--fresh-table for new ones).",
+                                                        saved.roster.len(), profile::profile_path().display())),
+                Err(e) => {
+                    // New bots, but the saved records still hold on to the ids already given out
+                    game.renumber_bots(saved.next_bot_id);
+                    profile_notice = Some(format!("Could not seat the saved table: {}", e));
+                },
+            }
+            opponent_records = saved.opponents;
+            earned_achievements = saved.achievements;
+        }
+        
+        // A broken key map falls back to the default keys rather than stopping the game
+        let (keys, keys_notice) = match &config.keys {
+            Some(path) => match KeyBindings::load(path) {
+                Ok(keys) => (keys, Some(format!("Key bindings loaded from {}.", path.display()))),
+                Err(e) => (KeyBindings::default(), Some(format!("Key map not used ({}). Using the default keys.", e))),
+            },
+            None => (KeyBindings::default(), None),
+        };
+        
+        // Seed the deck so a session can be replayed from the seed shown at startup
+        let seed = config.seed.unwrap_or_else(rand::random);
+        game.reseed(seed);
+        
+        // Create initial instructions, after a summary of what's running
+        let mut initial_messages = startup_summary(&config, &game, seed);
+        initial_messages.extend(profile_notice);
+        initial_messages.extend(keys_notice);
+        initial_messages.push(format!("Press '{}' to deal a new hand, '{}' to quit.",
+                                      keys.key(KeyAction::Deal), keys.key(KeyAction::Quit)));
+        if let Some(notice) = config.short_stack_notice() {
+            initial_messages.push(notice);
+        }
+        
+        let leaderboard = game.standings();
+        
+        App {
+            game,
+            input: String::new(),
+            messages: initial_messages,
+            should_quit: false,
+            player_starting_chips: starting_chips,
+            round_results: None,
+            game_stats: Vec::new(),
+            hand_results: Vec::new(),
+            bot_thinking: false,
+            bot_think_until: Instant::now(),
+            pending_bot_action: None,
+            game_active: false,
+            message_scroll_pos: 4, // Start at bottom of instructions
+            input_mode: InputMode::Normal,
+            staged_flop: true,
+            flop_reveal: None,
+            next_session_chips: config.starting_chips,
+            leaderboard,
+            last_progress_at: Instant::now(),
+            chop_offer: Vec::new(),
+            pending_recovery: None,
+            autosaved_hands: 0,
+            auto_call: None,
+            human_decision_ms: Vec::new(),
+            session_stats: SessionStats::new(),
+            winning_cards: Vec::new(),
+            winning_reveal_msg: None,
+            rabbit_hunts_left: config.rabbit_hunts,
+            rabbit_hunted: false,
+            table_changes: Vec::new(),
+            modal_opened_at: None,
+            opponent_records,
+            hand_start_msg: None,
+            showdown_msg: None,
+            seed,
+            auto_fold_below: config.auto_fold_below,
+            table_talk: None,
+            talked_hand: None,
+            verbosity: config.verbosity,
+            achievements: earned_achievements,
+            achievement_lines: Vec::new(),
+            drill: None,
+            keys,
+            pot_odds: None,
+            limits_checked_hands: 0,
+            settings_cursor: 0,
+            input_cursor: 0,
+            input_spot: None,
+            shown_on_fold: Vec::new(),
+            knockouts: Vec::new(),
+            outcomes_checked_hands: 0,
+            transients: Vec::new(),
+            log_share: 100,
+            hand_snapshot: None,
+            what_if_decision: None,
+            range_focus: None,
+            range_equity: None,
+            showdown_style: if config.fast_showdown { ShowdownStyle::Fast } else { ShowdownStyle::Cinematic },
+            config,
+        }
+    }
+    
+    pub fn on_key(&mut self, key: KeyCode) {
+        // Don't process input when bot is thinking or it's not the player's turn
+        let is_player_turn = !self.game.players[self.game.current_player_idx].is_bot;
+        let can_take_action = is_player_turn && !self.bot_thinking;
+        
+        // Handle input based on current input mode
+        match self.input_mode {
+            InputMode::PlayerName => {
+                // Special handling for player name input
+                match key {
+                    KeyCode::Char('n') => {
+                        // Set the player name once it's been cleaned up for display
+                        match util::sanitize_name(&self.input, self.config.max_name_width) {
+                            Ok(new_name) => {
+                                let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+                                self.game.players[human_idx].name = new_name.clone();
+                                self.messages.push(format!("Your name has been set to '{}'.", new_name));
+                                self.input.clear();
+                                self.input_mode = InputMode::Normal;
+                            },
+                            Err(e) => {
+                                self.input.clear();
+                                self.messages.push(e);
+                            }
+                        }
+                    },
+                    // Allow any printable character for name
+                    KeyCode::Char(c) if !c.is_control() => {
+                        self.input.push(c);
+                    },
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::RecoverSession => {
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        self.input_mode = InputMode::Normal;
+                        if let Some(snapshot) = self.pending_recovery.take() {
+                            self.restore_session(&snapshot);
+                        }
+                    },
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.pending_recovery = None;
+                        session::clear_autosave();
+                        self.messages.push("Previous session discarded.".to_string());
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::DealMaking => {
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        self.input_mode = InputMode::Normal;
+                        self.accept_chop();
+                    },
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.chop_offer.clear();
+                        self.messages.push("No deal - running out the board.".to_string());
+                        self.game.decline_chop();
+                        self.determine_winner_and_end_round();
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::PostBlind => {
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        self.input_mode = InputMode::Normal;
+                        self.deal_new_hand();
+                    },
+                    KeyCode::Char('n') | KeyCode::Char('N') => {
+                        self.input_mode = InputMode::Normal;
+                        self.game.human_sits_out_next = true;
+                        self.deal_new_hand();
+                    },
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push("Deal cancelled.".to_string());
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::TableSetup => {
+                match key {
+                    KeyCode::Char(c) if c.is_ascii_digit() && self.input.len() < 2 => {
+                        self.input.push(c);
+                    },
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    },
+                    KeyCode::Char('a') => {
+                        self.input.clear();
+                        match self.game.add_bot(self.config.starting_chips, BotDifficulty::Medium) {
+                            Ok(idx) => {
+                                let name = self.game.players[idx].name.clone();
+                                self.record_table_change(format!("{} sits down with ${} (Medium)", name, self.config.starting_chips));
+                            },
+                            Err(e) => self.messages.push(e),
+                        }
+                    },
+                    KeyCode::Char('r') => {
+                        // A typed bot number removes that bot; on its own, the first busted bot goes
+                        let seat = if self.input.is_empty() {
+                            self.game.players.iter().position(|p| p.is_bot && p.chips == 0)
+                        } else {
+                            self.input.parse::<usize>().ok().and_then(|n| self.game.seat_of_bot(n))
+                        };
+                        self.input.clear();
+                        match seat {
+                            Some(idx) => match self.game.remove_bot(idx) {
+                                Ok(removed) => self.record_table_change(format!("{} leaves the table with ${}", removed.name, removed.chips)),
+                                Err(e) => self.messages.push(e),
+                            },
+                            None => self.messages.push("No such bot. Type a bot number (3 for B3) before 'r', or 'r' alone for a busted bot.".to_string()),
+                        }
+                    },
+                    KeyCode::Char('e') | KeyCode::Char('m') | KeyCode::Char('h') => {
+                        let difficulty = match key {
+                            KeyCode::Char('e') => BotDifficulty::Easy,
+                            KeyCode::Char('m') => BotDifficulty::Medium,
+                            _ => BotDifficulty::Hard,
+                        };
+                        let seat = self.input.parse::<usize>().ok().and_then(|n| self.game.seat_of_bot(n));
+                        self.input.clear();
+                        match seat {
+                            Some(idx) => {
+                                self.game.players[idx].bot_difficulty = difficulty.clone();
+                                let name = self.game.players[idx].name.clone();
+                                self.record_table_change(format!("{} is now {:?}", name, difficulty));
+                            },
+                            None => self.messages.push("Type a bot number (3 for B3) before choosing e/m/h.".to_string()),
+                        }
+                    },
+                    KeyCode::Char('g') | KeyCode::Esc => {
+                        self.input.clear();
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push(format!("Table set: {} players. Press '{}' to deal.", self.game.players.len(), self.keys.key(KeyAction::Deal)));
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::Settings => {
+                match key {
+                    KeyCode::Up => {
+                        self.settings_cursor = self.settings_cursor.checked_sub(1).unwrap_or(Setting::ALL.len() - 1);
+                    },
+                    KeyCode::Down => {
+                        self.settings_cursor = (self.settings_cursor + 1) % Setting::ALL.len();
+                    },
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        self.change_setting(Setting::ALL[self.settings_cursor]);
+                    },
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                    },
+                    KeyCode::Char(c) if c == self.keys.key(KeyAction::Settings) => {
+                        self.input_mode = InputMode::Normal;
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::WhatIf => {
+                match key {
+                    KeyCode::Esc => {
+                        self.input.clear();
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push("What-if closed.".to_string());
+                    },
+                    KeyCode::Char(c) if c.is_ascii_digit() => self.input.push(c),
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    },
+                    KeyCode::Enter if self.what_if_decision.is_none() => self.pick_what_if_decision(),
+                    KeyCode::Char('f') => self.run_what_if(GameAction::Fold),
+                    KeyCode::Char('k') => self.run_what_if(GameAction::Check),
+                    KeyCode::Char('c') => self.run_what_if(GameAction::Call),
+                    KeyCode::Char('r') => match util::parse_amount(&self.input) {
+                        Some(amount) => self.run_what_if(GameAction::Raise(amount)),
+                        None => self.messages.push("Type the raise amount first, then 'r'.".to_string()),
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::Busted => {
+                match key {
+                    KeyCode::Char('b') | KeyCode::Char('B') => {
+                        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+                        let stack = self.game.rebuy_stack;
+                        self.game.players[human_idx].chips = stack;
+                        self.session_stats.record(self.game.hand_number, LedgerKind::Rebuy, stack as i64);
+                        self.leaderboard = self.game.standings();
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push(format!("You buy back in for ${}. {}", stack, self.deal_prompt()));
+                    },
+                    KeyCode::Char('q') | KeyCode::Char('Q') => {
+                        self.input_mode = InputMode::Normal;
+                        self.should_quit = true;
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::TableWon => {
+                match key {
+                    KeyCode::Char('n') | KeyCode::Char('N') => {
+                        self.input_mode = InputMode::Normal;
+                        self.start_new_session();
+                    },
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        // Cash game: the bots buy back in and play goes on
+                        let stack = self.game.rebuy_stack;
+                        for player in self.game.players.iter_mut().filter(|p| p.is_bot) {
+                            player.chips = stack;
+                        }
+                        self.leaderboard = self.game.standings();
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push(format!("The bots buy back in for ${} each. {}", stack, self.deal_prompt()));
+                    },
+                    KeyCode::Char('q') | KeyCode::Char('Q') => {
+                        self.input_mode = InputMode::Normal;
+                        self.should_quit = true;
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::SessionLimit => {
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        // The profile is saved on the way out, as with any quit
+                        self.input_mode = InputMode::Normal;
+                        self.should_quit = true;
+                    },
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push("Playing on.".to_string());
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::ConfirmNewSession => {
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        self.input_mode = InputMode::Normal;
+                        self.start_new_session();
+                    },
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.messages.push("New session cancelled.".to_string());
+                    },
+                    _ => {}
+                }
+            },
+            InputMode::Normal => {
+                // Regular game input handling. Letter commands go through the key map
+                // so they can be rebound (--keys)
+                if let KeyCode::Char(c) = key {
+                    if let Some(action) = self.keys.action(c) {
+                        self.on_action_key(action, can_take_action);
+                        return;
+                    }
+                }
+                match key {
+                    // Digits are raise amounts on our turn, or a starting stack between hands
+                    KeyCode::Char(c) if c.is_ascii_digit() && (is_player_turn || !self.game_active) => {
+                        // Ignore digits past what could ever be used, so the amount always parses
+                        let max_len = if self.game_active {
+                            self.game.players[self.game.current_player_idx].chips.max(1).to_string().len()
+                        } else {
+                            MAX_STARTING_CHIPS.to_string().len()
+                        };
+                        let cursor = self.input_cursor.min(self.input.len());
+                        let mut edited = self.input.clone();
+                        edited.insert(cursor, c);
+                        if edited.trim_start_matches('0').len() <= max_len {
+                            self.set_amount_input(edited, cursor + 1);
+                        }
+                    },
+                    KeyCode::Backspace => {
+                        let cursor = self.input_cursor.min(self.input.len());
+                        if (is_player_turn || !self.game_active) && cursor > 0 {
+                            let mut edited = self.input.clone();
+                            edited.remove(cursor - 1);
+                            self.set_amount_input(edited, cursor - 1);
+                        }
+                    },
+                    KeyCode::Delete => {
+                        let cursor = self.input_cursor.min(self.input.len());
+                        if (is_player_turn || !self.game_active) && cursor < self.input.len() {
+                            let mut edited = self.input.clone();
+                            edited.remove(cursor);
+                            self.set_amount_input(edited, cursor);
+                        }
+                    },
+                    KeyCode::Left => {
+                        self.input_cursor = self.input_cursor.min(self.input.len()).saturating_sub(1);
+                    },
+                    KeyCode::Right => {
+                        self.input_cursor = (self.input_cursor + 1).min(self.input.len());
+                    },
+                    // Add scrolling support for message history
+                    KeyCode::Up => {
+                        self.message_scroll_pos = self.message_scroll_pos.saturating_sub(1);
+                    },
+                    KeyCode::Down if self.message_scroll_pos < self.messages.len().saturating_sub(1) => {
+                        self.message_scroll_pos += 1;
+                    },
+                    KeyCode::PageUp => {
+                        // Scroll up 10 lines at a time
+                        self.message_scroll_pos = self.message_scroll_pos.saturating_sub(10);
+                    },
+                    KeyCode::PageDown => {
+                        // Scroll down 10 lines at a time
+                        self.message_scroll_pos = (self.message_scroll_pos + 10).min(self.messages.len().saturating_sub(1));
+                    },
+                    KeyCode::Home => {
+                        // Scroll to the top
+                        self.message_scroll_pos = 0;
+                    },
+                    KeyCode::End => {
+                        // Scroll to the bottom
+                        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+    
+    // Alt+key at the table: Alt+<fold key> folds showing both hole cards, Alt+1 or Alt+2 folds
+    // showing just that one
+    pub fn on_alt_key(&mut self, c: char) {
+        if self.input_mode != InputMode::Normal {
+            return;
+        }
+        let shown: &[usize] = match c {
+            '1' => &[0],
+            '2' => &[1],
+            c if c == self.keys.key(KeyAction::Fold) => &[0, 1],
+            _ => return,
+        };
+        self.fold_and_show(shown);
+    }
+    
+    // Fold and turn over some hole cards for the table, for the image of it
+    fn fold_and_show(&mut self, shown: &[usize]) {
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let your_turn = self.game_active && !self.bot_thinking && self.game.current_player_idx == human_idx;
+        if !your_turn || self.game.round == Round::Showdown {
+            return;
+        }
+        // A drill just grades the fold
+        if self.drill.as_ref().is_some_and(|drill| drill.awaiting_answer) {
+            self.handle_player_action(GameAction::Fold);
+            return;
+        }
+        let cards: Vec<Card> = shown.iter().filter_map(|&i| self.game.players[human_idx].hand.get(i).cloned()).collect();
+        let faces = cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("");
+        self.messages.push(format!("You fold showing {}.", faces));
+        self.handle_player_action(GameAction::Fold);
+        if self.game.players[human_idx].folded {
+            self.shown_on_fold = cards;
+        }
+    }
+    
+    // A bound command key pressed at the table (Normal input mode)
+    fn on_action_key(&mut self, action: KeyAction, can_take_action: bool) {
+        match action {
+            KeyAction::Quit => {
+                self.should_quit = true;
+            },
+            KeyAction::Deal if self.drill.is_some() => {
+                self.deal_drill_scenario();
+            },
+            KeyAction::Deal => {
+                // Ask first if the human is about to be in the blinds and wants the choice
+                if self.game.players.iter().any(|p| !p.is_bot && p.chips == 0) {
+                    self.offer_buy_in();
+                } else if let Some((blind_name, amount)) = self.human_blind_next_hand() {
+                    self.input_mode = InputMode::PostBlind;
+                    self.messages.push(format!("You're in the {} this hand. Post ${}? (y = post, n = sit this hand out)",
+                                              blind_name, amount));
+                } else {
+                    self.deal_new_hand();
+                }
+            },
+            KeyAction::Name => {
+                // Switch to player name input mode
+                self.input.clear();
+                self.input_mode = InputMode::PlayerName;
+                self.messages.push(format!("Enter your name and press '{}' to confirm:", self.keys.key(KeyAction::Name)));
+            },
+            KeyAction::ShowdownStyle => {
+                // Switch between cinematic and fast showdowns
+                self.change_setting(Setting::ShowdownStyle);
+            },
+            KeyAction::NewSession => {
+                // Ask before wiping chips and stats
+                self.input_mode = InputMode::ConfirmNewSession;
+                self.messages.push("Start a new session? All chips and stats will be reset. (y/n)".to_string());
+            },
+            KeyAction::SetChips if self.game_active => {
+                // Digits typed mid-hand are a raise amount, so leave them be
+                self.messages.push("Starting chips are set between hands.".to_string());
+            },
+            KeyAction::SetChips => {
+                // Use the typed number as the starting stack for the next session
+                match util::parse_amount(&self.input) {
+                    Some(chips) if chips >= self.game.big_blind && chips <= MAX_STARTING_CHIPS => {
+                        self.next_session_chips = chips;
+                        self.messages.push(format!("Next session will start with ${} per player. Press '{}' to start it.", chips, self.keys.key(KeyAction::NewSession)));
+                        if let Some(notice) = config::short_stack_notice(chips, self.game.big_blind) {
+                            self.messages.push(notice);
+                        }
+                    },
+                    Some(_) => {
+                        self.messages.push(format!("Starting chips must be between ${} (one big blind) and ${}.",
+                                                  self.game.big_blind, MAX_STARTING_CHIPS));
+                    },
+                    None => {
+                        self.messages.push(format!("Type a starting chip amount first, then press '{}'.", self.keys.key(KeyAction::SetChips)));
+                    }
+                }
+                self.input.clear();
+            },
+            KeyAction::Stop => {
+                // Stop current game: fold and let the bots settle the pot
+                if self.game_active {
+                    self.stop_hand();
+                }
+            },
+            KeyAction::Call => {
+                // Allow player action regardless of round
+                if can_take_action && self.game_active {
+                    // Double-check it's actually the player's turn
+                    if !self.game.players[self.game.current_player_idx].is_bot {
+                        // Check if there's a bet to call
+                        let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
+                        let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
+                        
+                        if highest_bet <= player_current_bet {
+                            self.messages.push("No bet to call - action changed to check.".to_string());
+                        }
+                        self.handle_player_action(GameAction::Call);
+                    } else {
+                        self.messages.push("It's not your turn yet. Please wait.".to_string());
+                    }
+                }
+            },
+            KeyAction::RabbitHunt => {
+                self.rabbit_hunt();
+            },
+            KeyAction::TableSetup => {
+                // Table setup between hands; stacks and stats carry on
+                if self.game_active {
+                    self.messages.push("The table can only be changed between hands.".to_string());
+                } else {
+                    self.input.clear();
+                    self.input_mode = InputMode::TableSetup;
+                    self.messages.push("Table setup: 'a' add a bot, [N] 'r' remove bot N (alone: a busted bot), N then e/m/h to set bot N's difficulty, 'g' done.".to_string());
+                }
+            },
+            KeyAction::Straddle => {
+                // Ask to straddle the next time we're Under The Gun
+                if !self.game.allow_straddle {
+                    self.messages.push("Straddling is off (start with --straddle to allow it).".to_string());
+                } else if self.game_active {
+                    self.messages.push("Straddles are chosen between hands.".to_string());
+                } else {
+                    self.game.human_straddle_next = !self.game.human_straddle_next;
+                    if self.game.human_straddle_next {
+                        self.messages.push("You'll straddle if you're Under The Gun next hand.".to_string());
+                    } else {
+                        self.messages.push("Straddle cancelled.".to_string());
+                    }
+                }
+            },
+            KeyAction::AutoCall => {
+                // Standing instruction: type N then 'a' to call up to $N this street,
+                // 'a' alone to call anything (or to cancel an instruction already set)
+                if self.game_active {
+                    if self.input.is_empty() && self.auto_call.is_some() {
+                        self.auto_call = None;
+                        self.messages.push("Auto-call cancelled.".to_string());
+                    } else if self.input.is_empty() {
+                        self.auto_call = Some((u32::MAX, self.game.round));
+                        self.messages.push("Calling any bet for the rest of this street.".to_string());
+                    } else if let Some(limit) = util::parse_amount(&self.input) {
+                        self.auto_call = Some((limit, self.game.round));
+                        self.messages.push(format!("Calling bets up to ${} for the rest of this street.", limit));
+                    }
+                    self.input.clear();
+                }
+            },
+            KeyAction::Check => {
+                // Allow player action regardless of round
+                if can_take_action && self.game_active {
+                    // Double-check it's actually the player's turn
+                    if !self.game.players[self.game.current_player_idx].is_bot {
+                        // Can't check facing a bet - don't quietly commit chips with a call,
+                        // leave it to the player to call or fold
+                        let legal = self.game.legal_actions();
+                        if legal.can_check {
+                            self.handle_player_action(GameAction::Check);
+                        } else {
+                            self.messages.push(format!("Can't check — ${} to call. Use {} or {}.", legal.to_call,
+                                                      self.keys.hint(KeyAction::Call, "call"), self.keys.hint(KeyAction::Fold, "fold")));
+                        }
+                    } else {
+                        self.messages.push("It's not your turn yet. Please wait.".to_string());
+                    }
+                }
+            },
+            KeyAction::Fold => {
+                // Allow player action regardless of round
+                if can_take_action && self.game_active {
+                    // Double-check it's actually the player's turn
+                    if !self.game.players[self.game.current_player_idx].is_bot {
+                        self.handle_player_action(GameAction::Fold);
+                    } else {
+                        self.messages.push("It's not your turn yet. Please wait.".to_string());
+                    }
+                }
+            },
+            KeyAction::Raise => {
+                // Allow player action regardless of round
+                if can_take_action && self.game_active {
+                    // Double-check it's actually the player's turn
+                    if !self.game.players[self.game.current_player_idx].is_bot {
+                        // Use the current input as raise amount
+                        if self.input.is_empty() {
+                            self.messages.push(format!("Please enter a raise amount first, then press '{}'.", self.keys.key(KeyAction::Raise)));
+                        } else if let Some(amount) = util::parse_amount(&self.input) {
+                            self.handle_player_action(GameAction::Raise(amount));
+                            self.input.clear();
+                        } else {
+                            self.messages.push("Invalid raise amount. Please enter a number.".to_string());
+                        }
+                    } else {
+                        self.messages.push("It's not your turn yet. Please wait.".to_string());
+                    }
+                }
+            },
+            KeyAction::Verbosity => {
+                // Cycle how chatty the log is
+                self.change_setting(Setting::Verbosity);
+            },
+            KeyAction::Settings => {
+                self.input_mode = InputMode::Settings;
+            },
+            KeyAction::HandStart => {
+                // Jump back to where this hand started
+                match self.hand_start_msg {
+                    Some(pos) => self.message_scroll_pos = pos,
+                    None => self.messages.push("No hand dealt yet.".to_string()),
+                }
+            },
+            KeyAction::LastShowdown => {
+                // Jump to the most recent showdown result
+                match self.showdown_msg {
+                    Some(pos) => self.message_scroll_pos = pos,
+                    None => self.messages.push("No showdown yet this session.".to_string()),
+                }
+            },
+            KeyAction::WhatIf => self.open_what_if(),
+            KeyAction::LogGrow => self.resize_log(true),
+            KeyAction::LogShrink => self.resize_log(false),
+            KeyAction::VsRange => self.set_range_focus(),
+        }
+    }
+    
+    // Put an opponent on a hand range for the equity readout: a typed percentage sets "top N%"
+    // for the focused opponent (0 turns the readout off), V alone moves the focus to the next
+    // bot. The first focus goes to the bot that last bet or raised, if any.
+    fn set_range_focus(&mut self) {
+        let typed = self.input.trim().to_string();
+        self.input.clear();
+        let percent = if typed.is_empty() {
+            None
+        } else {
+            match typed.parse::<u8>() {
+                Ok(0) => {
+                    self.range_focus = None;
+                    self.range_equity = None;
+                    self.messages.push("Range equity readout off.".to_string());
+                    return;
+                },
+                Ok(percent) if percent <= 100 => Some(percent),
+                _ => {
+                    self.messages.push(format!("'{}' isn't a percentage - type 1 to 100, then '{}'.", typed, self.keys.key(KeyAction::VsRange)));
+                    return;
+                }
+            }
+        };
+        
+        let bots: Vec<usize> = self.game.players.iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_bot)
+            .map(|(idx, _)| idx)
+            .collect();
+        if bots.is_empty() {
+            self.messages.push("There's no opponent to put on a range.".to_string());
+            return;
+        }
+        let seat = match (&self.range_focus, percent) {
+            (Some((seat, _)), Some(_)) if bots.contains(seat) => *seat,
+            (Some((seat, _)), None) => bots.iter().copied().find(|&idx| idx > *seat).unwrap_or(bots[0]),
+            _ => self.game.last_aggressor.filter(|idx| bots.contains(idx))
+                .or_else(|| bots.iter().copied().find(|&idx| !self.game.players[idx].folded))
+                .unwrap_or(bots[0]),
+        };
+        let percent = percent
+            .or_else(|| self.range_focus.as_ref().map(|(_, range)| range.percent))
+            .unwrap_or(DEFAULT_RANGE_PERCENT);
+        let range = HandRange::top(percent, &self.game.fresh_deck());
+        self.messages.push(format!("Showing your equity against {} on a {} range on your turns.", self.game.players[seat].name, range.label()));
+        self.range_focus = Some((seat, range));
+        self.range_equity = None;
+    }
+    
+    // List the human's decisions in the hand just played, to pick one to replay differently
+    fn open_what_if(&mut self) {
+        if self.game_active {
+            self.messages.push("Finish this hand first - what-if replays the hand just played.".to_string());
+            return;
+        }
+        let Some(start) = self.hand_snapshot.as_ref().filter(|start| start.hand_number == self.game.hand_number) else {
+            self.messages.push("No hand to replay yet.".to_string());
+            return;
+        };
+        let decisions = whatif::human_decisions(start, &self.game.action_history);
+        if decisions.is_empty() {
+            self.messages.push("You made no decisions last hand.".to_string());
+            return;
+        }
+        self.log(Verbosity::Normal, "".to_string());
+        self.messages.push(format!("What if... (hand #{}, hypothetical - nothing here counts)", self.game.hand_number));
+        for (number, (_, label)) in decisions.iter().enumerate() {
+            self.messages.push(format!("  {}. {}", number + 1, label));
+        }
+        self.messages.push("Type a decision's number and press Enter (Esc cancels).".to_string());
+        self.input.clear();
+        self.what_if_decision = None;
+        self.input_mode = InputMode::WhatIf;
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    fn pick_what_if_decision(&mut self) {
+        let decisions = self.hand_snapshot.as_ref()
+            .map(|start| whatif::human_decisions(start, &self.game.action_history))
+            .unwrap_or_default();
+        let picked = self.input.parse::<usize>().ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(|number| decisions.get(number));
+        self.input.clear();
+        match picked {
+            Some((idx, label)) => {
+                self.what_if_decision = Some(*idx);
+                self.messages.push(format!("{} - instead: f = fold, k = check, c = call, or type an amount and r = raise.", label));
+            },
+            None => self.messages.push(format!("Pick a decision from 1 to {}.", decisions.len())),
+        }
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Replay the last hand with the picked decision changed and report how it would have gone.
+    // The replay runs on a copy of the table as it was dealt; the real stacks and stats stay as they are.
+    fn run_what_if(&mut self, alternative: GameAction) {
+        let (Some(idx), Some(start)) = (self.what_if_decision, self.hand_snapshot.as_ref()) else {
+            self.messages.push("Pick a decision first: type its number and press Enter.".to_string());
+            return;
+        };
+        let played = &self.game.action_history[idx].action;
+        if std::mem::discriminant(played) == std::mem::discriminant(&alternative) && !matches!(alternative, GameAction::Raise(_)) {
+            self.messages.push("That's what you did - pick a different action.".to_string());
+            return;
+        }
+        self.input.clear();
+        match whatif::replay(start, &self.game.action_history, idx, alternative) {
+            Ok(outcome) => {
+                let real = self.session_stats.hand_net(self.game.hand_number);
+                let signed = |net: i64| format!("{}${}", if net < 0 { "-" } else { "+" }, net.abs());
+                self.messages.push("--- WHAT IF (hypothetical) ---".to_string());
+                for line in &outcome.lines {
+                    self.messages.push(format!("  {}", line));
+                }
+                let winner = if outcome.winner == "You" { "you'd win".to_string() } else { format!("{} wins", outcome.winner) };
+                self.messages.push(format!("Result: {} - {}. You'd be {} on the hand (really {}).",
+                                           winner, outcome.hand_type, signed(outcome.human_net), signed(real as i64)));
+                if outcome.guessed {
+                    self.messages.push("Actions marked (guessed) are the built-in bots' play once the real hand no longer fitted.".to_string());
+                }
+                self.messages.push("--- END OF WHAT IF (not counted in your stats) ---".to_string());
+                self.input_mode = InputMode::Normal;
+            },
+            Err(e) => self.messages.push(format!("Can't replay that: {}.", e)),
+        }
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Auto-scroll the log to the newest message unless the user has scrolled back up
+    pub fn follow_log(&mut self) {
+        let len = self.messages.len();
+        if len == 0 {
+            self.message_scroll_pos = 0;
+        } else if self.message_scroll_pos == 0 || self.message_scroll_pos >= len.saturating_sub(2) {
+            self.message_scroll_pos = len - 1;
+        }
+    }
+    
+    // Give the message log more or less of the main column; what it gives up goes to the
+    // game info panel above it
+    fn resize_log(&mut self, grow: bool) {
+        let share = if grow { self.log_share + LOG_SHARE_STEP } else { self.log_share.saturating_sub(LOG_SHARE_STEP) };
+        let share = share.clamp(LOG_SHARE_MIN, 100);
+        if share == self.log_share {
+            self.messages.push(format!("The log panel is already as {} as it goes.", if grow { "tall" } else { "short" }));
+        } else {
+            self.log_share = share;
+            self.messages.push(format!("Log panel: {}% of the free height.", share));
+        }
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Current value of a runtime option, as shown on the settings screen
+    pub fn setting_value(&self, setting: Setting) -> String {
+        let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+        match setting {
+            Setting::Verbosity => self.verbosity.name().to_string(),
+            Setting::ShowdownStyle => match self.showdown_style {
+                ShowdownStyle::Cinematic => "cinematic".to_string(),
+                ShowdownStyle::Fast => "fast".to_string(),
+            },
+            Setting::StagedFlop => if self.staged_flop { "one card at a time" } else { "all at once" }.to_string(),
+            Setting::AsciiSuits => if self.config.ascii_suits { "letters (h/d/c/s)" } else { "symbols" }.to_string(),
+            Setting::TableTalk => on_off(self.config.table_talk),
+            Setting::ShowdownRanking => on_off(self.config.showdown_ranking),
+            Setting::PotOdds => on_off(self.config.pot_odds),
+            Setting::Coach => on_off(self.config.coach),
+        }
+    }
+    
+    // Flip a runtime option (or step to its next value) and note the change in the log
+    pub fn change_setting(&mut self, setting: Setting) {
+        match setting {
+            Setting::Verbosity => self.verbosity = self.verbosity.next(),
+            Setting::ShowdownStyle => {
+                self.showdown_style = match self.showdown_style {
+                    ShowdownStyle::Cinematic => ShowdownStyle::Fast,
+                    ShowdownStyle::Fast => ShowdownStyle::Cinematic,
+                };
+            },
+            Setting::StagedFlop => self.staged_flop = !self.staged_flop,
+            Setting::AsciiSuits => {
+                self.config.ascii_suits = !self.config.ascii_suits;
+                game::set_ascii_suits(self.config.ascii_suits);
+            },
+            Setting::TableTalk => self.config.table_talk = !self.config.table_talk,
+            Setting::ShowdownRanking => self.config.showdown_ranking = !self.config.showdown_ranking,
+            Setting::PotOdds => self.config.pot_odds = !self.config.pot_odds,
+            Setting::Coach => self.config.coach = !self.config.coach,
+        }
+        self.messages.push(format!("{}: {}.", setting.label(), self.setting_value(setting)));
+    }
+    
+    // "Press 'd' to deal a new hand." with the bound deal key
+    fn deal_prompt(&self) -> String {
+        format!("Press '{}' to deal a new hand.", self.keys.key(KeyAction::Deal))
+    }
+    
+    // "[c]all, [f]old, or [r]aise" (or check instead of call) with the bound keys
+    fn action_options(&self, can_check: bool) -> String {
+        let first = if can_check { self.keys.hint(KeyAction::Check, "check") } else { self.keys.hint(KeyAction::Call, "call") };
+        format!("{}, {}, or {}", first, self.keys.hint(KeyAction::Fold, "fold"), self.keys.hint(KeyAction::Raise, "raise"))
+    }
+    
+    // Reinitialize the game with fresh stacks and clear all session stats,
+    // keeping the human's name and the terminal session alive
+    pub fn start_new_session(&mut self) {
+        let human_name = self.game.players.iter()
+            .find(|p| !p.is_bot)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "Player 1".to_string());
+        let api_key = self.game.api_key.clone();
+        let starting_chips = self.next_session_chips;
+        
+        let table = Profile::capture(&self.game, &self.opponent_records, &self.achievements);
+        self.pending_bot_action = None;
+        self.game = Game::new(1, 8, BotDifficulty::Medium, starting_chips,
+                              self.config.small_blind, self.config.big_blind, api_key, human_name);
+        // Same opponents as before, just with fresh stacks; or new ones that don't pick up the
+        // old ones' records
+        if self.config.fresh_table || table.seat_roster(&mut self.game, starting_chips).is_err() {
+            self.game.renumber_bots(table.next_bot_id);
+        }
+        self.config.apply_to(&mut self.game);
+        self.seed = self.config.seed.unwrap_or_else(rand::random);
+        self.game.reseed(self.seed);
+        self.game.shuffle_deck();
+        
+        // Reset hand and session tracking
+        self.player_starting_chips = starting_chips;
+        self.round_results = None;
+        self.game_stats.clear();
+        self.hand_results.clear();
+        self.hand_snapshot = None;
+        self.human_decision_ms.clear();
+        self.session_stats = SessionStats::new();
+        self.limits_checked_hands = 0;
+        self.transients.clear();
+        self.knockouts.clear();
+        self.outcomes_checked_hands = 0;
+        self.rabbit_hunts_left = self.config.rabbit_hunts;
+        self.table_changes.clear();
+        self.bot_thinking = false;
+        self.game_active = false;
+        self.flop_reveal = None;
+        self.input.clear();
+        self.leaderboard = self.game.standings();
+        
+        self.log(Verbosity::Normal, "".to_string());
+        self.messages.push(format!("New session started. Everyone is back to ${}. Press '{}' to deal. (Seed {})",
+                                   starting_chips, self.keys.key(KeyAction::Deal), self.seed));
+    }
+    
+    // An autosave was left behind by a session that didn't exit cleanly: ask before restoring it
+    pub fn offer_recovery(&mut self, snapshot: SessionSnapshot) {
+        let human_chips = snapshot.players.iter()
+            .find(|p| !p.is_bot)
+            .map(|p| p.chips)
+            .unwrap_or(0);
+        self.messages.push(format!("Found an unfinished session ({} hands played, you had ${}).",
+                                   snapshot.game_stats.len(), human_chips));
+        self.messages.push("Recover previous session? (y/n)".to_string());
+        self.pending_recovery = Some(snapshot);
+        self.input_mode = InputMode::RecoverSession;
+    }
+    
+    // Restore stacks, seats and stats from a snapshot
+    pub fn restore_session(&mut self, snapshot: &SessionSnapshot) {
+        if let Err(e) = snapshot.restore_into(&mut self.game) {
+            self.messages.push(format!("Could not recover session: {}", e));
+            return;
+        }
+        
+        self.player_starting_chips = snapshot.player_starting_chips;
+        self.game_stats = snapshot.game_stats.clone();
+        self.hand_results = snapshot.hand_results.clone();
+        self.table_changes = snapshot.table_changes.clone();
+        if let Some(stats) = snapshot.restored_stats() {
+            self.session_stats = stats;
+        }
+        self.round_results = None;
+        self.autosaved_hands = self.game_stats.len();
+        self.leaderboard = self.game.standings();
+        self.messages.push(format!("Session recovered after {} hands. Press '{}' to deal.", self.game_stats.len(), self.keys.key(KeyAction::Deal)));
+    }
+    
+    // Save the session after every settled hand so a crash loses at most the hand in progress
+    pub fn autosave_if_settled(&mut self) {
+        if self.game_active || self.input_mode == InputMode::RecoverSession || self.game_stats.len() == self.autosaved_hands {
+            return;
+        }
+        self.autosaved_hands = self.game_stats.len();
+        
+        let snapshot = SessionSnapshot::capture(&self.game, self.player_starting_chips, &self.game_stats, &self.hand_results, &self.table_changes,
+                                                &self.session_stats);
+        if let Err(e) = session::write_autosave(&snapshot) {
+            self.messages.push(format!("Autosave failed: {}", e));
+        }
+        if let Err(e) = self.save_profile() {
+            self.messages.push(format!("Profile save failed: {}", e));
+        }
+    }
+    
+    // Once a hand is settled, offer to quit when the session net has crossed the stop-loss
+    // or stop-win. The net is the sum of the per-hand results, so chips added between hands
+    // don't count. Each alert fires at most once per session.
+    pub fn check_session_limits(&mut self) {
+        if self.game_active || self.input_mode != InputMode::Normal || self.drill.is_some()
+            || self.game_stats.len() == self.limits_checked_hands {
+            return;
+        }
+        self.limits_checked_hands = self.game_stats.len();
+        
+        let net: i32 = self.game_stats.iter().sum();
+        let big_blind = self.game.big_blind;
+        let loss = self.config.stop_loss.map(|limit| limit.chips(big_blind));
+        let win = self.config.stop_win.map(|limit| limit.chips(big_blind));
+        let hit = match (loss, win) {
+            (Some(chips), _) if !self.session_stats.stop_loss_alerted && net <= -(chips as i32) => {
+                self.session_stats.stop_loss_alerted = true;
+                Some(("stop-loss", chips))
+            },
+            (_, Some(chips)) if !self.session_stats.stop_win_alerted && net >= chips as i32 => {
+                self.session_stats.stop_win_alerted = true;
+                Some(("stop-win", chips))
+            },
+            _ => None,
+        };
+        
+        if let Some((name, chips)) = hit {
+            self.messages.push(format!("Session {} reached: net {} after {} hands (limit {}).",
+                                       name, util::format_profit(net), self.game_stats.len(), util::format_chips(chips)));
+            self.messages.push(format!("You've hit your {} ({}). Quit now? (y = quit and save, n = continue)",
+                                       name, util::format_profit(net)));
+            self.input_mode = InputMode::SessionLimit;
+            self.message_scroll_pos = self.messages.len().saturating_sub(1);
+        }
+    }
+    
+    // After each settled hand: note the biggest pot, credit a knockout for each bot that busted
+    // with no rebuys left, and stop for the victory screen once the human holds every chip
+    pub fn check_eliminations(&mut self) {
+        if self.game_active || self.drill.is_some() || self.game_stats.len() == self.outcomes_checked_hands {
+            return;
+        }
+        self.outcomes_checked_hands = self.game_stats.len();
+        self.session_stats.biggest_pot = self.session_stats.biggest_pot.max(self.game.last_pot);
+        
+        // The knockout goes to whoever won the last pot the busted player had chips in
+        let players = &self.game.players;
+        let start = |idx: usize| self.game.hand_start_chips.get(idx).copied().unwrap_or(0);
+        let mut eliminated = Vec::new();
+        for (idx, player) in players.iter().enumerate() {
+            if !player.is_bot || player.chips > 0 || start(idx) == 0 || self.game.can_rebuy(idx) {
+                continue;
+            }
+            let by = self.game.pot_awards.iter().rev()
+                .find(|pot| pot.contenders.contains(&idx))
+                .and_then(|pot| pot.winners.iter().find(|&&(seat, _)| seat != idx))
+                .map(|&(seat, _)| seat);
+            if let Some(by) = by {
+                eliminated.push((player.name.clone(), players[by].is_bot.then(|| players[by].name.clone())));
+            }
+        }
+        for (name, by) in eliminated {
+            self.messages.push(format!("{} is out of chips - knocked out by {}.", name, by.as_deref().unwrap_or("you")));
+            self.knockouts.push((name, by));
+        }
+        
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let table_won = self.game.players.len() > 1 && self.game.players[human_idx].chips > 0
+            && self.game.players.iter().enumerate().all(|(idx, p)| !p.is_bot || (p.chips == 0 && !self.game.can_rebuy(idx)));
+        if table_won && self.input_mode == InputMode::Normal {
+            self.messages.push("You've won every chip at the table!".to_string());
+            self.input_mode = InputMode::TableWon;
+        } else if self.game.players[human_idx].chips == 0 && self.input_mode == InputMode::Normal {
+            self.offer_buy_in();
+        }
+    }
+    
+    // Out of chips: nothing more is dealt until the human buys back in (or quits)
+    fn offer_buy_in(&mut self) {
+        self.input_mode = InputMode::Busted;
+        self.messages.push(format!("You're out of chips - [b]uy in for ${} or [q]uit.", self.game.rebuy_stack));
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // The victory screen's lines, once the human has won the whole table
+    pub fn victory_summary(&self) -> Vec<String> {
+        let minutes = self.session_stats.started_at.elapsed().as_secs() / 60;
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let yours = self.knockouts.iter().filter(|(_, by)| by.is_none()).count();
+        let mut lines = vec![
+            format!("Hands played: {}", self.game_stats.len()),
+            format!("Time: {}h {:02}m", minutes / 60, minutes % 60),
+            format!("Biggest pot: {}", util::format_chips(self.session_stats.biggest_pot)),
+            format!("Final stack: {}", util::format_chips(self.game.players[human_idx].chips)),
+            format!("Knockouts: {} by you, {} by other bots", yours, self.knockouts.len() - yours),
+        ];
+        for (name, by) in &self.knockouts {
+            lines.push(format!("  {} - by {}", name, by.as_deref().unwrap_or("you")));
+        }
+        lines
+    }
+    
+    // Keep the roster and opponent records for next time (not with --fresh-table)
+    pub fn save_profile(&self) -> Result<(), String> {
+        // A drill's heads-up table isn't the player's table
+        if self.config.fresh_table || self.drill.is_some() {
+            return Ok(());
+        }
+        profile::write_profile(&Profile::capture(&self.game, &self.opponent_records, &self.achievements))
+    }
+    
+    pub fn print_game_stats(&mut self) {
+        if self.verbosity < Verbosity::Normal {
+            return;
+        }
+        if !self.game_stats.is_empty() {
+            let total_profit = self.game_stats.iter().sum::<i32>();
+            
+            // Get the human's current chip stack
+            let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+            let current_chips = self.game.players[human_idx].chips;
+            
+            // Calculate profit for the current round
+            let current_round_profit = if !self.game_stats.is_empty() {
+                self.game_stats.last().unwrap()
+            } else {
+                &0
+            };
+            
+            // Format the round profits
+            let round_profits = self.game_stats.iter()
+                .map(|profit| format!("${}{}", if *profit >= 0 {""} else {"-"}, profit.abs()))
+                .collect::<Vec<_>>()
+                .join(". ");
+            
+            // Show detailed stats
+            self.messages.push(format!(
+                "Overall stats: {} rounds played. Current round profit: ${}{}. Total profit: ${}. Current chips: ${}. {}", 
+                self.game_stats.len(), 
+                if *current_round_profit >= 0 { "" } else { "-" },
+                current_round_profit.abs(),
+                total_profit,
+                current_chips,
+                self.streak_display()
+            ));
+            
+            // Show round-by-round profits
+            self.messages.push(format!("Round profits: {}", round_profits));
+            
+            if let Some(avg) = self.avg_decision_display() {
+                self.messages.push(avg);
+            }
+            self.messages.push(format!("{}.", self.session_stats.summary(self.game_stats.len())));
+            self.messages.push(self.speed_settings_display());
+            
+            // Opponent tendencies, in seat order
+            for player in self.game.players.iter().filter(|p| p.is_bot) {
+                if let Some(record) = self.opponent_records.get(&player.record_key()) {
+                    self.messages.push(format!("{}: {} hands, mucked {:.0}%, {} showdowns, won {} ({:.0}%).",
+                                               player.name, record.hands, record.muck_rate(),
+                                               record.showdowns, record.showdown_wins, record.showdown_win_rate()));
+                }
+            }
+            
+            if !self.achievements.is_empty() {
+                let badges = self.achievements.iter()
+                    .filter_map(|earned| earned.achievement().map(|a| format!("{} ({})", a.name, earned.date())))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.messages.push(format!("Achievements: {}.", badges));
+            }
+            
+            // API bot reliability: frequent clamping means the prompt isn't getting through
+            let api_stats = &self.game.api_stats;
+            if api_stats.calls > 0 {
+                self.messages.push(format!("API bots: {} calls, {} failed, {} of {} raises clamped to a legal size.",
+                                           api_stats.calls, api_stats.failures, api_stats.clamped, api_stats.raises));
+            }
+            self.log(Verbosity::Normal, "".to_string()); // Add empty line for better readability
+            self.log(Verbosity::Normal, "".to_string()); // Add empty line for better readability
+        } else {
+            self.messages.push("STATS: No rounds played yet.".to_string());
+        }
+    }
+    
+    // Begin revealing the flop card by card (no-op when staged reveal is off)
+    fn start_flop_reveal(&mut self) {
+        if self.staged_flop {
+            self.flop_reveal = Some((1, Instant::now() + FLOP_REVEAL_STEP));
+        }
+    }
+    
+    // Advance the staged flop reveal - called every main loop iteration so it never blocks
+    pub fn update_flop_reveal(&mut self) {
+        if let Some((shown, next_at)) = self.flop_reveal {
+            if Instant::now() >= next_at {
+                if shown + 1 >= 3 {
+                    self.flop_reveal = None;
+                } else {
+                    self.flop_reveal = Some((shown + 1, next_at + FLOP_REVEAL_STEP));
+                }
+            }
+        }
+    }
+    
+    // Number of community cards the board panel should currently show
+    pub fn visible_community_cards(&self) -> usize {
+        let dealt = self.game.community_cards.len();
+        match self.flop_reveal {
+            Some((shown, _)) if self.game.round == Round::Flop => shown.min(dealt),
+            _ => dealt,
+        }
+    }
+    
+    // A menu (name entry, new-session confirm) left open on the human's turn would hold the
+    // table up forever. With --modal-timeout, close it once the turn has run that long (timed
+    // from the turn's start, like an action timer, not from when the menu opened) and play
+    // the timeout action: check if it's free, otherwise fold.
+    pub fn check_modal_timeout(&mut self) {
+        if self.input_mode == InputMode::Normal {
+            self.modal_opened_at = None;
+            return;
+        }
+        let opened_at = *self.modal_opened_at.get_or_insert_with(Instant::now);
+        
+        let Some(timeout_secs) = self.config.modal_timeout_secs else {
+            return;
+        };
+        // A chop offer is its own decision, not something to time out into a fold
+        let human_turn = self.game_active
+            && !self.bot_thinking
+            && !self.game.chop_pending
+            && !self.game.players[self.game.current_player_idx].is_bot;
+        if !human_turn || self.input_mode == InputMode::DealMaking || self.game.last_action_at.elapsed() < Duration::from_secs(timeout_secs) {
+            return;
+        }
+        
+        self.input.clear();
+        self.input_mode = InputMode::Normal;
+        self.modal_opened_at = None;
+        let action = if self.game.legal_actions().can_check { GameAction::Check } else { GameAction::Fold };
+        self.messages.push(format!("Your {}s turn ran out with a menu open (for {}s) - {} for you.", timeout_secs,
+                                   opened_at.elapsed().as_secs(),
+                                   if matches!(action, GameAction::Check) { "checking" } else { "folding" }));
+        self.handle_player_action(action);
+    }
+    
+    // Recover from a bot turn that never gets processed: if it's been a bot's turn with no
+    // think timer pending and no progress for too long, force a heuristic action for that seat
+    pub fn check_bot_watchdog(&mut self) {
+        if !self.game_active || self.game.chop_pending || !self.game.players[self.game.current_player_idx].is_bot {
+            return;
+        }
+        
+        let now = Instant::now();
+        let think_pending = self.bot_thinking && now < self.bot_think_until;
+        if think_pending || now.duration_since(self.last_progress_at) < BOT_WATCHDOG_TIMEOUT {
+            return;
+        }
+        
+        let bot_player = self.game.players[self.game.current_player_idx].clone();
+        self.messages.push(format!("System warning: {} was stuck without acting for {}s - forcing an action.",
+                                   bot_player.name, BOT_WATCHDOG_TIMEOUT.as_secs()));
+        self.bot_thinking = false;
+        
+        // A request still out to the API is what got stuck, so don't wait on it (or start another)
+        self.pending_bot_action = None;
+        let action = self.game.heuristic_bot_action(&bot_player);
+        self.process_bot_action(action, bot_player);
+    }
+    
+    // The acting bot's decision, once it's ready. With an API key the model decides on a worker
+    // thread that's polled here on each pass of the main loop; a reply slower than
+    // API_DECISION_TIMEOUT is given up on for the built-in play.
+    pub fn poll_bot_decision(&mut self, bot_player: &Player) -> Option<GameAction> {
+        let pending = match self.pending_bot_action.take() {
+            Some(pending) if pending.seat == self.game.current_player_idx && pending.hand_number == self.game.hand_number => pending,
+            _ => match self.game.start_api_bot_action() {
+                Some(pending) => pending,
+                None => return Some(self.game.heuristic_bot_action(bot_player)),
+            },
+        };
+        if let Some(decision) = pending.poll() {
+            return Some(self.game.finish_api_bot_action(decision, bot_player));
+        }
+        if pending.started_at.elapsed() >= API_DECISION_TIMEOUT {
+            self.game.api_stats.failures += 1;
+            self.game.debug_log.push(format!("{}: no reply from the API in {}s - using built-in play",
+                                             bot_player.name, API_DECISION_TIMEOUT.as_secs()));
+            return Some(self.game.heuristic_bot_action(bot_player));
+        }
+        self.pending_bot_action = Some(pending);
+        None
+    }
+    
+    // Act on a standing "call up to $N" instruction when it's the human's turn.
+    // The instruction ends with the street, or as soon as a bet is bigger than the limit.
+    pub fn apply_auto_call(&mut self) {
+        let (limit, street) = match self.auto_call {
+            Some(instruction) => instruction,
+            None => return,
+        };
+        if !self.game_active || self.game.round != street {
+            self.auto_call = None;
+            return;
+        }
+        
+        let is_player_turn = !self.game.players[self.game.current_player_idx].is_bot;
+        if !is_player_turn || self.bot_thinking || self.input_mode != InputMode::Normal || self.game.chop_pending {
+            return;
+        }
+        
+        let legal = self.game.legal_actions();
+        if legal.can_check {
+            self.messages.push("Auto-check.".to_string());
+            self.handle_player_action(GameAction::Check);
+        } else if legal.to_call <= limit {
+            self.messages.push(format!("Auto-calling ${}.", legal.to_call));
+            self.handle_player_action(GameAction::Call);
+        } else {
+            self.auto_call = None;
+            self.messages.push(format!("${} to call is more than your auto-call limit of ${} - your decision.", legal.to_call, limit));
+        }
+    }
+    
+    // Work out the pot-odds advice (--pot-odds) when the human faces a bet. The equity
+    // simulation runs once per decision, not every frame.
+    pub fn update_pot_odds(&mut self) {
+        let current = self.game.current_player_idx;
+        let to_call = self.game.legal_actions().to_call;
+        if !self.config.pot_odds || !self.game_active || self.bot_thinking
+            || self.game.players[current].is_bot || to_call == 0 {
+            self.pot_odds = None;
+            return;
+        }
+        let spot = (self.game.hand_number, self.game.round, self.game.pot, to_call);
+        if self.pot_odds.as_ref().is_some_and(|advice| advice.spot == spot) {
+            return;
+        }
+        
+        let equity = self.game.estimate_equity(current, POT_ODDS_EQUITY_ITERATIONS, &mut rand::thread_rng());
+        self.pot_odds = Some(PotOddsAdvice {
+            spot,
+            ratio: util::pot_odds_ratio(self.game.pot, to_call),
+            need: util::break_even_equity(self.game.pot, to_call),
+            equity,
+        });
+    }
+    
+    // Equity against the focused opponent's range, worked out once per street on the human's turn
+    pub fn update_range_equity(&mut self) {
+        let current = self.game.current_player_idx;
+        let Some((seat, range)) = &self.range_focus else {
+            self.range_equity = None;
+            return;
+        };
+        if !self.game_active || self.bot_thinking || self.game.players[current].is_bot {
+            self.range_equity = None;
+            return;
+        }
+        let spot = (self.game.hand_number, self.game.round, *seat, range.percent);
+        if self.range_equity.as_ref().is_some_and(|readout| readout.spot == spot) {
+            return;
+        }
+        
+        self.range_equity = self.game.equity_vs_range(current, *seat, range, POT_ODDS_EQUITY_ITERATIONS, &mut rand::thread_rng())
+            .map(|equity| RangeEquity {
+                spot,
+                opponent: self.game.players[*seat].name.clone(),
+                range: range.label(),
+                equity,
+            });
+    }
+    
+    // Fast-fold the human's weak hands when there's a raise preflop. Never folds a free check.
+    pub fn apply_auto_fold(&mut self) {
+        let Some(threshold) = self.auto_fold_below else {
+            return;
+        };
+        if !self.game_active || self.game.round != Round::PreFlop || self.game.raises_this_street == 0 || self.drill.is_some() {
+            return;
+        }
+        let player = &self.game.players[self.game.current_player_idx];
+        if player.is_bot || player.folded || self.bot_thinking || self.input_mode != InputMode::Normal || self.game.chop_pending {
+            return;
+        }
+        if self.game.legal_actions().can_check {
+            return;
+        }
+        
+        let Some(class) = game::classify_preflop_hand(&player.hand) else {
+            return;
+        };
+        if class < threshold {
+            let cards = player.hand.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+            self.messages.push(format!("Auto-fold: {} is {} (below {}) facing a raise.", cards, class.name(), threshold.name()));
+            self.auto_call = None;
+            self.handle_player_action(GameAction::Fold);
+        }
+    }
+    
+    // Log a between-hands table change and make sure the next autosave picks it up
+    fn record_table_change(&mut self, change: String) {
+        self.messages.push(format!("{}.", change));
+        self.table_changes.push(format!("Before hand {}: {}", self.game_stats.len() + 1, change));
+        self.leaderboard = self.game.standings();
+        self.autosaved_hands = usize::MAX;
+    }
+    
+    // Show what the rest of the board would have been after the human folded,
+    // a limited number of times per session
+    fn rabbit_hunt(&mut self) {
+        let human_folded = self.game.players.iter().any(|p| !p.is_bot && p.folded);
+        let board_left = 5usize.saturating_sub(self.game.community_cards.len());
+        if self.game_active || !human_folded {
+            self.messages.push("Rabbit hunting is for after you've folded, once the hand is over.".to_string());
+        } else if self.rabbit_hunted {
+            self.messages.push("You've already seen this hand's runout.".to_string());
+        } else if board_left == 0 {
+            self.messages.push("The whole board was dealt - nothing to hunt.".to_string());
+        } else if self.rabbit_hunts_left == 0 {
+            self.messages.push("No rabbit hunts left this session.".to_string());
+        } else {
+            let cards = self.game.rabbit_hunt_cards().iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.rabbit_hunts_left -= 1;
+            self.rabbit_hunted = true;
+            self.messages.push(format!("Rabbit hunt: {} would have come. Rabbit hunts left: {}", cards, self.rabbit_hunts_left));
+        }
+    }
+    
+    // Stop the hand without losing chips from the table: the human folds, the bots
+    // play the rest out with no think delays, and the pot is settled as usual
+    fn stop_hand(&mut self) {
+        self.bot_thinking = false;
+        self.bot_think_until = Instant::now();
+        // Drop any API request still out (and its receiver) so a late reply can't be applied
+        // to the next hand
+        self.pending_bot_action = None;
+        self.flop_reveal = None;
+        self.auto_call = None;
+        
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let mut game_continues = true;
+        if !self.game.players[human_idx].folded {
+            if self.game.current_player_idx == human_idx {
+                self.game.perform_action(GameAction::Fold);
+                game_continues = self.game.next_player();
+            } else {
+                self.game.players[human_idx].folded = true;
+            }
+            self.messages.push("You fold and stop the game.".to_string());
+        }
+        
+        // Fast-forward with the offline heuristics so nothing waits on the API
+        for _ in 0..STOP_FAST_FORWARD_ACTIONS {
+            let still_in = self.game.players.iter().filter(|p| !p.folded).count();
+            if !game_continues || still_in <= 1 || self.game.round == Round::Showdown {
+                break;
+            }
+            let bot = self.game.players[self.game.current_player_idx].clone();
+            let action = self.game.heuristic_bot_action(&bot);
+            self.game.perform_action(action);
+            game_continues = self.game.next_player();
+        }
+        self.game.debug_log.clear();
+        
+        let settled = self.settle_hand(None);
+        let summary = self.fast_showdown_summary(settled.winner_idx, settled.winnings, &settled.hand_type);
+        self.messages.push(summary);
+        self.messages.push(format!("Game stopped. {}", self.deal_prompt()));
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Start a training drill: scenarios replace normal hands until the program exits
+    pub fn start_drill(&mut self, drill: DrillSession) {
+        self.messages.push(format!("Drill mode: {} scenario{}. Press '{}' for the first one.",
+                                   drill.scenarios.len(), if drill.scenarios.len() == 1 { "" } else { "s" }, self.keys.key(KeyAction::Deal)));
+        self.drill = Some(drill);
+    }
+    
+    // Set the table up at the next drill scenario's decision point
+    fn deal_drill_scenario(&mut self) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        let scenario = drill.scenario().clone();
+        let number = drill.current + 1;
+        let total = drill.scenarios.len();
+        let human_name = self.game.players.iter()
+            .find(|p| !p.is_bot)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "Player 1".to_string());
+        match scenario.build_game(human_name) {
+            Ok(game) => self.game = game,
+            Err(e) => {
+                self.messages.push(format!("Could not set up drill: {}", e));
+                return;
+            }
+        }
+        drill.awaiting_answer = true;
+        self.hand_snapshot = None;
+        
+        self.game_active = true;
+        self.bot_thinking = false;
+        self.auto_call = None;
+        self.winning_cards.clear();
+        self.winning_reveal_msg = None;
+        self.hand_start_msg = Some(self.messages.len());
+        self.log(Verbosity::Normal, "".to_string());
+        self.log(Verbosity::Normal, format!("--- DRILL {}/{}: {} ---", number, total, scenario.name));
+        if !scenario.description.is_empty() {
+            self.messages.push(scenario.description.clone());
+        }
+        let to_call = self.game.players[1].current_bet.saturating_sub(self.game.players[0].current_bet);
+        let villain = if to_call > 0 {
+            format!("Villain makes it ${} (${} to call)", self.game.players[1].current_bet, to_call)
+        } else {
+            "Villain checks".to_string()
+        };
+        self.messages.push(format!("Pot ${}. {}. Your move.", self.game.pot, villain));
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Check the human's decision against the scenario and show how the drill is going
+    fn grade_drill_answer(&mut self, action: GameAction) {
+        let Some(drill) = &mut self.drill else {
+            return;
+        };
+        let scenario = drill.scenario().clone();
+        let correct = drill.answer(&action);
+        let (right, answered, accuracy, wrapped) = (drill.correct, drill.answered, drill.accuracy(), drill.current == 0);
+        
+        self.messages.push(format!("You {}.", drill::action_name(&action)));
+        if correct {
+            self.messages.push("Correct!".to_string());
+        } else {
+            self.messages.push(format!("Not quite - the scenario calls for {}.", scenario.correct.join(" or ")));
+        }
+        self.messages.push(format!("Why: {}", scenario.explanation));
+        let villain_cards = self.game.players[1].hand.iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.log(Verbosity::Normal, format!("Villain held {} (dealt at random).", villain_cards));
+        self.messages.push(format!("Drill score: {} of {} ({:.0}%).", right, answered, accuracy));
+        self.game_active = false;
+        if wrapped {
+            self.messages.push(format!("That was the last scenario - press '{}' to go round again.", self.keys.key(KeyAction::Deal)));
+        } else {
+            self.messages.push(format!("Press '{}' for the next scenario.", self.keys.key(KeyAction::Deal)));
+        }
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Start the next hand (blinds, cards, bot timing) and report it
+    fn deal_new_hand(&mut self) {
+        // Busted bots buy back in first, so the leaderboard doesn't count the rebuy as winnings
+        let rebought = self.game.rebuy_busted_bots();
+        
+        // Freeze the leaderboard at the stacks the previous hand ended with
+        self.leaderboard = self.game.standings();
+        
+        // Allow starting new hand even if there's a game in progress
+        self.game.deal_cards();
+        self.hand_snapshot = Some(self.game.clone());
+        self.table_talk = None;
+        self.hand_start_msg = Some(self.messages.len());
+        self.messages.push(format!("\nNew hand dealt (hand #{}).", self.game.hand_number));
+        for idx in rebought {
+            let name = self.game.players[idx].name.clone();
+            let used = self.game.bot_rebuys.get(&name).copied().unwrap_or(0);
+            self.messages.push(format!("{} rebuys for ${} ({} of {} rebuys).", name, self.game.rebuy_stack, used, self.game.max_bot_rebuys));
+        }
+        
+        // Force a larger delay to allow the UI to update and the player to see the new hand
+        // This makes the game feel more natural and gives time to look at the cards
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        
+        // Blinds, antes and straddles, as the deal posted them
+        self.narrate_game_events();
+        
+        // Verify deck is properly set up - the board still needs five cards and three burns after
+        // the deal (a 36-card short deck leaves well under 35 at a full table, so don't key off that)
+        if self.game.deck.len() < 8 {
+            // Silently replace the deck without printing warnings
+            self.game.deck = self.game.fresh_deck();
+            self.game.shuffle_deck();
+        }
+        
+        // Reset tracking for new hand
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        self.winning_cards.clear();
+        self.winning_reveal_msg = None;
+        self.rabbit_hunted = false;
+        self.shown_on_fold.clear();
+        self.transients.clear();
+        self.player_starting_chips = self.game.players[human_idx].chips;
+        self.round_results = None;
+        self.flop_reveal = None;
+        self.game_active = true;
+        self.last_progress_at = Instant::now();
+        self.game.last_action_count = 0;
+        
+        // Add a deliberate delay when a new hand is dealt to make the game flow more naturally
+        // First, check if it's a bot's turn 
+        if self.game.players[self.game.current_player_idx].is_bot {
+            // Always set thinking to true and force a much longer delay (3-4 seconds) for the first action
+            self.bot_thinking = true;
+            let delay = rand::thread_rng().gen_range(BOT_THINK_FIRST_ACTION_MS); // Much longer thinking time (3-4 seconds)
+            self.bot_think_until = std::time::Instant::now() + std::time::Duration::from_millis(delay);
+            
+            // Placeholder for bot thinking
+            let _bot_name = &self.game.players[self.game.current_player_idx].name;
+            let _position = util::get_player_position(&self.game, self.game.current_player_idx);
+            
+            // Force UI update to show this message
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        } else {
+            self.bot_thinking = false;
+        }
+        
+        // Show total stats
+        if !self.game_stats.is_empty() {
+            let total_profit = self.game_stats.iter().sum::<i32>();
+            let profit_list = self.game_stats.iter()
+                .enumerate()
+                .map(|(i, profit)| format!("R{}: ${}{}", i+1, if *profit >= 0 {""} else {"-"}, profit.abs()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.messages.push(format!("Stats: {} rounds played. Profits: {}. Total: ${}", 
+                                      self.game_stats.len(), profit_list, total_profit));
+        }
+    }
+    
+    // Replace the amount being typed after an edit. Leading zeros are dropped as they go (so they
+    // never count against the length cap), and the cursor moves back with them.
+    fn set_amount_input(&mut self, text: String, cursor: usize) {
+        let zeros = text.len() - text.trim_start_matches('0').len();
+        self.input = text[zeros..].to_string();
+        self.input_cursor = cursor.saturating_sub(zeros).min(self.input.len());
+        self.input_spot = self.game_active.then_some((self.game.hand_number, self.game.round));
+    }
+    
+    // Drop an amount typed for a decision that's gone: the turn passed to someone else, or
+    // the street or hand moved on. A starting stack typed between hands keeps until the deal.
+    pub fn clear_stale_input(&mut self) {
+        if self.input_mode != InputMode::Normal || self.input.is_empty() {
+            return;
+        }
+        let spot = self.game_active.then_some((self.game.hand_number, self.game.round));
+        let human_turn = !self.bot_thinking && !self.game.players[self.game.current_player_idx].is_bot;
+        if spot != self.input_spot || (self.game_active && !human_turn) {
+            self.log(Verbosity::Verbose, format!("Cleared the unused amount {}.", self.input));
+            self.input.clear();
+            self.input_cursor = 0;
+        }
+    }
+    
+    // Turn the engine's deal events into log lines. Called right after a deal, and from the
+    // main loop in case a hand was dealt some other way.
+    pub fn narrate_game_events(&mut self) {
+        for event in std::mem::take(&mut self.game.events) {
+            self.record_ledger(&event);
+            let name = |seat: usize| {
+                let player = &self.game.players[seat];
+                if player.is_bot { player.name.clone() } else { "You".to_string() }
+            };
+            let line = match event {
+                GameEvent::HandStarted { hand_no, dealer } => {
+                    self.log(Verbosity::Verbose, format!("Hand #{}: {} has the button.", hand_no, name(dealer)));
+                    continue;
+                },
+                GameEvent::SatOut { seat } if !self.game.players[seat].is_bot => {
+                    "You sit this hand out instead of posting your blind.".to_string()
+                },
+                GameEvent::SatOut { seat } => format!("{} sits this hand out.", name(seat)),
+                GameEvent::AntesPosted { posted } if self.game.button_ante && posted.len() == 1 => {
+                    format!("{} on the Button posts the ${} ante for the table.", name(self.game.dealer_idx), posted[0].1)
+                },
+                GameEvent::AntesPosted { posted } => {
+                    let amount: u32 = posted.iter().map(|&(_, amount)| amount).sum();
+                    self.log(Verbosity::Verbose, format!("Antes: {} players post ${} in all.", posted.len(), amount));
+                    continue;
+                },
+                GameEvent::BlindPosted { seat, kind: BlindKind::Small, amount } => {
+                    format!("{} in Small Blind (SB) position posts ${}.", self.game.players[seat].name, amount)
+                },
+                GameEvent::BlindPosted { seat, kind: BlindKind::Big, amount } => {
+                    format!("{} in Big Blind (BB) position posts ${}.", self.game.players[seat].name, amount)
+                },
+                GameEvent::BlindPosted { seat, kind: BlindKind::Straddle, amount } => {
+                    let who = if self.game.players[seat].is_bot { format!("{} straddles", name(seat)) } else { "You straddle".to_string() };
+                    format!("{} ${} from Under The Gun.", who, amount)
+                },
+                // Bets and payouts are already in the log; these just animate the pot
+                GameEvent::ChipsIn { seat, amount } => {
+                    let who = if self.game.players[seat].is_bot { util::short_player_name(&self.game, seat) } else { "You".to_string() };
+                    self.add_transient(format!("+${} from {}", amount, who));
+                    continue;
+                },
+                GameEvent::PotAwarded { seat, amount } => {
+                    let who = if self.game.players[seat].is_bot { util::short_player_name(&self.game, seat) } else { "You".to_string() };
+                    self.add_transient(format!("→ ${} to {}", amount, who));
+                    continue;
+                },
+                // announce_side_pots says so at settlement
+                GameEvent::BetReturned { .. } => continue,
+                GameEvent::HumanToAct { to_call } => {
+                    if to_call == 0 {
+                        format!("Your turn now. Options: {}.", self.action_options(true))
+                    } else {
+                        format!("Your turn now - ${} to call. Options: {}.", to_call, self.action_options(false))
+                    }
+                },
+            };
+            self.messages.push(line);
+        }
+    }
+    
+    // Put the human's side of a chip movement in the session ledger
+    fn record_ledger(&mut self, event: &GameEvent) {
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let (kind, amount) = match *event {
+            GameEvent::AntesPosted { ref posted } => {
+                let Some(&(_, amount)) = posted.iter().find(|&&(seat, _)| seat == human_idx) else { return };
+                (LedgerKind::Ante, -(amount as i64))
+            },
+            GameEvent::BlindPosted { seat, amount, .. } if seat == human_idx => (LedgerKind::Blind, -(amount as i64)),
+            GameEvent::ChipsIn { seat, amount } if seat == human_idx => (LedgerKind::Bet, -(amount as i64)),
+            GameEvent::BetReturned { seat, amount } if seat == human_idx => (LedgerKind::Refund, amount as i64),
+            GameEvent::PotAwarded { seat, amount } if seat == human_idx => (LedgerKind::PotShare, amount as i64),
+            _ => return,
+        };
+        self.session_stats.record(self.game.hand_number, kind, amount);
+    }
+    
+    // The human's profit on the hand being settled, from the ledger. The stack change since
+    // the deal should always agree; if it doesn't, a chip movement went unrecorded.
+    fn hand_profit(&mut self) -> i32 {
+        self.narrate_game_events();
+        let profit = self.session_stats.hand_net(self.game.hand_number);
+        let breakdown = self.session_stats.hand_breakdown(self.game.hand_number);
+        if !breakdown.is_empty() {
+            self.log(Verbosity::Verbose, format!("Your chips this hand: {}.", breakdown));
+        }
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let stack_change = self.game.players[human_idx].chips as i64 - self.player_starting_chips as i64;
+        if profit as i64 != stack_change {
+            self.game.debug_log.push(format!("System warning: the ledger puts this hand at {:+} but your stack moved {:+}",
+                                             profit, stack_change));
+        }
+        profit
+    }
+    
+    // Show a chip movement beside the pot for a moment (not with fast showdowns)
+    fn add_transient(&mut self, text: String) {
+        if self.showdown_style == ShowdownStyle::Fast {
+            return;
+        }
+        self.transients.push((text, Instant::now()));
+    }
+    
+    // Drop the chip movement notes that have had their time
+    pub fn expire_transients(&mut self) {
+        self.transients.retain(|(_, shown_at)| shown_at.elapsed() < TRANSIENT_LIFETIME);
+    }
+    
+    // The blind the human would post next hand, when they should be asked about it
+    fn human_blind_next_hand(&self) -> Option<(&'static str, u32)> {
+        let num_players = self.game.players.len();
+        // Heads-up there would be no hand left to play without them
+        if !self.config.prompt_blind_post || num_players < 3 {
+            return None;
+        }
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot)?;
+        let small_blind_idx = (self.game.dealer_idx + 2) % num_players;
+        if human_idx == small_blind_idx {
+            Some(("small blind", self.game.small_blind))
+        } else if human_idx == (small_blind_idx + 1) % num_players {
+            Some(("big blind", self.game.big_blind))
+        } else {
+            None
+        }
+    }
+    
+    // Current pacing settings, so "why is this slow" can be answered in-game
+    pub fn speed_settings_display(&self) -> String {
+        format!("Speed: bots think {} ({} after you, {} to open a hand), flop reveal {}, {} showdown.",
+                think_range_display(BOT_THINK_MS),
+                think_range_display(BOT_THINK_AFTER_HUMAN_MS),
+                think_range_display(BOT_THINK_FIRST_ACTION_MS),
+                if self.staged_flop { format!("{}ms per card", FLOP_REVEAL_STEP.as_millis()) } else { "off".to_string() },
+                match self.showdown_style {
+                    ShowdownStyle::Cinematic => "cinematic",
+                    ShowdownStyle::Fast => "fast",
+                })
+    }
+    
+    // Lines printed to the terminal after quitting
+    pub fn exit_summary(&self) -> Vec<String> {
+        let mut lines = vec![self.session_stats.summary(self.game_stats.len())];
+        lines.push(format!("Hands dealt: {}", self.game.hand_number));
+        if !self.game_stats.is_empty() {
+            let total_profit = self.game_stats.iter().sum::<i32>();
+            lines.push(format!("Total profit: {}", util::format_profit(total_profit)));
+        }
+        if let Some(avg) = self.avg_decision_display() {
+            lines.push(avg);
+        }
+        lines.push(self.speed_settings_display());
+        if !self.table_changes.is_empty() {
+            lines.push("Table changes:".to_string());
+            lines.extend(self.table_changes.iter().map(|change| format!("  {}", change)));
+        }
+        if let Some(drill) = &self.drill {
+            if drill.answered > 0 {
+                lines.push(format!("Drill: {} of {} correct ({:.0}%)", drill.correct, drill.answered, drill.accuracy()));
+            }
+        }
+        lines
+    }
+    
+    // "Avg decision: 3.4s" over the human's decisions this session - creeping up can be a sign of tilt
+    pub fn avg_decision_display(&self) -> Option<String> {
+        if self.human_decision_ms.is_empty() {
+            return None;
+        }
+        let total: u64 = self.human_decision_ms.iter().sum();
+        let avg_secs = total as f64 / self.human_decision_ms.len() as f64 / 1000.0;
+        Some(format!("Avg decision: {:.1}s", avg_secs))
+    }
+    
+    // Add a line to the game log if the current verbosity includes its level.
+    // Actions and results go straight to `messages`; headers, spacing and detail come through here.
+    fn log(&mut self, level: Verbosity, message: String) {
+        if level <= self.verbosity {
+            self.messages.push(message);
+        }
+    }
+    
+    // Move engine notes (API clamping, integrity warnings) into the message log
+    pub fn flush_debug_log(&mut self) {
+        for note in std::mem::take(&mut self.game.debug_log) {
+            // System warnings point at a real inconsistency, so they show at the normal level
+            let level = if note.starts_with("System warning") { Verbosity::Normal } else { Verbosity::Verbose };
+            self.log(level, format!("Debug (hand #{}): {}", self.game.hand_number, note));
+        }
+    }
+    
+    // Both remaining players are all-in: propose an equity-based split of the pot
+    fn offer_chop(&mut self) {
+        self.bot_thinking = false;
+        let offer = self.game.propose_chop(1000, &mut *self.game.table_rng.borrow_mut());
+        if offer.len() != 2 {
+            self.game.decline_chop();
+            self.determine_winner_and_end_round();
+            return;
+        }
+        
+        let terms = offer.iter()
+            .map(|&(idx, amount, equity)| {
+                let name = if self.game.players[idx].is_bot { self.game.players[idx].name.clone() } else { "You".to_string() };
+                format!("{} {:.0}% equity, ${}", name, equity * 100.0, amount)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        
+        self.messages.push("Both players are all-in.".to_string());
+        self.messages.push(format!("Chop offer: {}", terms));
+        self.messages.push("Chop the pot instead of dealing the rest of the board? (y/n)".to_string());
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+        
+        self.chop_offer = offer;
+        self.input_mode = InputMode::DealMaking;
+    }
+    
+    // Settle the hand with the agreed chop
+    fn accept_chop(&mut self) {
+        let offer = std::mem::take(&mut self.chop_offer);
+        self.settle_hand(Some(&offer));
         
-        // Create initial instructions
-        let initial_messages = vec![
-            "Press 'd' to deal a new hand, 'q' to quit.".to_string(),
-        ];
+        for &(idx, amount, _) in &offer {
+            let player = &self.game.players[idx];
+            if player.is_bot {
+                self.messages.push(format!("{} takes ${}.", player.name, amount));
+            } else {
+                self.messages.push(format!("You take ${}.", amount));
+            }
+        }
         
-        App {
-            game,
-            input: String::new(),
-            messages: initial_messages,
-            should_quit: false,
-            player_starting_chips: starting_chips,
-            round_results: None,
-            game_stats: Vec::new(),
-            bot_thinking: false,
-            bot_think_until: Instant::now(),
-            game_active: false,
-            message_scroll_pos: 4, // Start at bottom of instructions
-            input_mode: InputMode::Normal
+        self.messages.push("Pot chopped.".to_string());
+        self.messages.push(self.deal_prompt());
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+    }
+    
+    // Remember the winner's best five (and their reveal line) so the UI can highlight them.
+    // Only for real showdowns: nothing to show when everyone else folded.
+    fn mark_winning_cards(&mut self, winner_idx: usize, reveal_lines: &[(usize, usize)]) {
+        let contested = self.game.players.iter().filter(|p| !p.folded).count() > 1;
+        if !contested || self.game.community_cards.len() < 3 {
+            return;
         }
+        self.winning_cards = self.game.best_five_cards(winner_idx);
+        self.winning_reveal_msg = reveal_lines.iter()
+            .find(|&&(idx, _)| idx == winner_idx)
+            .map(|&(_, line)| line);
     }
     
-    pub fn on_key(&mut self, key: KeyCode) {
-        // Don't process input when bot is thinking or it's not the player's turn
-        let is_player_turn = !self.game.players[self.game.current_player_idx].is_bot;
-        let can_take_action = is_player_turn && !self.bot_thinking;
+    // When the human loses a real showdown, say what beat them (or which kicker did)
+    fn explain_showdown_loss(&mut self, winner_idx: usize) {
+        let Some(human_idx) = self.game.players.iter().position(|p| !p.is_bot) else {
+            return;
+        };
+        if human_idx == winner_idx || self.game.players[human_idx].folded {
+            return;
+        }
+        // Nothing beat a hand that tied for the main pot
+        if self.game.pot_awards.first().is_some_and(|pot| pot.winners.iter().any(|&(seat, _)| seat == human_idx)) {
+            return;
+        }
+        if let (Some(winner), Some(human)) = (self.game.best_hand(winner_idx), self.game.best_hand(human_idx)) {
+            self.messages.push(format!("{}.", game::describe_comparison(&winner, &human)));
+        }
+    }
+    
+    // With --rank-showdown, list the live hands best to worst under the result
+    fn print_showdown_ranking(&mut self) {
+        if !self.config.showdown_ranking {
+            return;
+        }
+        let lines = self.game.showdown_ranking();
+        if lines.is_empty() {
+            return;
+        }
+        self.messages.push("Showdown ranking:".to_string());
+        for line in lines {
+            self.messages.push(format!("  {}", line));
+        }
+    }
+    
+    // Every way a hand ends comes through here: pay the pot (or the agreed chop shares), then
+    // do the bookkeeping a finished hand needs. The stop-loss and elimination checks run from
+    // the main loop once the hand is over and game_stats has grown.
+    fn settle_hand(&mut self, chop: Option<&[(usize, u32, f64)]>) -> SettledHand {
+        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+        let (winner_idx, winnings, hand_type) = match chop {
+            Some(offer) => {
+                self.game.apply_chop(offer);
+                // The bigger share counts as the winner for results and streaks
+                let (winner_idx, amount) = offer.iter().max_by_key(|&&(_, amount, _)| amount)
+                    .map(|&(idx, amount, _)| (idx, amount)).unwrap_or((human_idx, 0));
+                (winner_idx, amount, "a chop".to_string())
+            },
+            None => {
+                let result = self.game.determine_winner();
+                self.announce_side_pots();
+                result
+            },
+        };
+        self.announce_rake();
         
-        // Handle input based on current input mode
-        match self.input_mode {
-            InputMode::PlayerName => {
-                // Special handling for player name input
-                match key {
-                    KeyCode::Char('n') => {
-                        // Set the player name if input is not empty
-                        if !self.input.is_empty() {
-                            let new_name = self.input.clone();
-                            let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-                            self.game.players[human_idx].name = new_name.clone();
-                            self.messages.push(format!("Your name has been set to '{}'.", new_name));
-                            self.input.clear();
-                            self.input_mode = InputMode::Normal;
-                        } else {
-                            self.messages.push("Name cannot be empty. Please enter a name.".to_string());
-                        }
-                    },
-                    KeyCode::Char(c) => {
-                        // Allow any character for name
-                        self.input.push(c);
-                    },
-                    KeyCode::Backspace => {
-                        self.input.pop();
-                    },
-                    _ => {}
+        let profit = self.hand_profit();
+        self.round_results = Some((self.game.players[winner_idx].name.clone(), profit));
+        self.game_stats.push(profit);
+        // A split or a side pot can leave the human ahead without taking the main pot, so the
+        // hand counts as won by what it did to their stack
+        self.hand_results.push(profit > 0);
+        self.record_opponent_results(winner_idx);
+        self.queue_table_talk(winner_idx);
+        self.check_achievements(profit > 0);
+        self.game_active = false;
+        
+        SettledHand {
+            winner_idx,
+            winnings,
+            hand_type,
+            human_took_pot: winner_idx == human_idx,
+            profit,
+            total_profit: self.game_stats.iter().sum(),
+        }
+    }
+    
+    // "You won this hand! Your profit: $40. Total: $115", going by the human's net for the hand
+    fn push_hand_result(&mut self, profit: i32, total_profit: i32) {
+        match profit.cmp(&0) {
+            Ordering::Greater => self.messages.push(format!("You won this hand! Your profit: ${}. Total: ${}", profit, total_profit)),
+            Ordering::Less => self.messages.push(format!("You lost this hand. Your loss: ${}. Total: ${}", -profit, total_profit)),
+            Ordering::Equal => self.messages.push(format!("You broke even this hand. Total: ${}", total_profit)),
+        }
+    }
+    
+    // Show the house's cut of the pot just settled and add it to the session total
+    fn announce_rake(&mut self) {
+        let rake = self.game.last_rake;
+        if rake > 0 {
+            self.session_stats.rake_paid += rake;
+            self.messages.push(format!("Rake: ${}", rake));
+        }
+    }
+    
+    // Tally how the settled hand ended for each bot: folded, or shown down and won or lost
+    fn record_opponent_results(&mut self, winner_idx: usize) {
+        let contested = self.game.players.iter().filter(|p| !p.folded).count() > 1;
+        for (idx, player) in self.game.players.iter().enumerate() {
+            if !player.is_bot || player.hand.is_empty() {
+                continue;
+            }
+            let record = self.opponent_records.entry(player.record_key()).or_default();
+            record.hands += 1;
+            if player.folded {
+                record.folded += 1;
+            } else if contested {
+                record.showdowns += 1;
+                if idx == winner_idx || self.game.side_pot_awards.iter().any(|&(i, _)| i == idx) {
+                    record.showdown_wins += 1;
                 }
+            }
+        }
+    }
+    
+    // Let at most one bot react to the hand just settled: a strong hand beaten at showdown,
+    // a big pot won, or a pot taken uncontested with nothing. A reaction never gives away
+    // cards the table didn't see: a beaten hand has to have been shown, and a bot boasting
+    // about a bluff shows it.
+    fn queue_table_talk(&mut self, winner_idx: usize) {
+        if !self.config.table_talk || self.talked_hand == Some(self.game.hand_number) {
+            return;
+        }
+        self.talked_hand = Some(self.game.hand_number);
+        let contested = self.game.players.iter().filter(|p| !p.folded).count() > 1;
+        let revealed = self.revealed_seats(winner_idx);
+        let mut event = None;
+        if contested {
+            event = self.game.players.iter().enumerate()
+                .filter(|&(idx, p)| p.is_bot && revealed.contains(&idx) && idx != winner_idx
+                    && !self.game.side_pot_awards.iter().any(|&(i, _)| i == idx))
+                .find(|&(idx, _)| self.game.evaluate_hand(idx)
+                    .is_some_and(|rank| flavor::is_strong(Game::hand_type_name(&rank))))
+                .map(|(idx, _)| (idx, FlavorEvent::BadBeat));
+        }
+        let winner = &self.game.players[winner_idx];
+        if event.is_none() && winner.is_bot {
+            // Chips gained this hand, measured from the standings frozen at the deal
+            let started_with = self.leaderboard.iter()
+                .find(|&&(idx, _)| idx == winner_idx)
+                .map_or(winner.chips, |&(_, chips)| chips);
+            let nothing = match self.game.evaluate_hand(winner_idx) {
+                Some(rank) => Game::hand_type_name(&rank) == "High Card",
+                None => game::classify_preflop_hand(&winner.hand) == Some(PreflopClass::Trash),
+            };
+            if winner.chips.saturating_sub(started_with) >= flavor::BIG_POT_BB * self.game.big_blind {
+                event = Some((winner_idx, FlavorEvent::BigPot));
+            } else if !contested && nothing {
+                event = Some((winner_idx, FlavorEvent::Bluff));
+            }
+        }
+        
+        if let Some((idx, event)) = event {
+            let bot = &self.game.players[idx];
+            if let Some(line) = flavor::reaction(&bot.bot_difficulty, event, &mut rand::thread_rng()) {
+                self.table_talk = Some(if event == FlavorEvent::Bluff {
+                    let cards = bot.hand.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+                    format!("{} shows {}: {}", bot.name, cards, line)
+                } else {
+                    format!("{}: {}", bot.name, line)
+                });
+            }
+        }
+    }
+    
+    // Seats whose hands the table saw at settlement: every live hand at a full showdown, the
+    // winner and the best losing hand in a fast one (as fast_showdown_summary lists them), and
+    // nobody when everyone else folded
+    fn revealed_seats(&self, winner_idx: usize) -> Vec<usize> {
+        let live: Vec<usize> = (0..self.game.players.len())
+            .filter(|&idx| !self.game.players[idx].folded && self.game.players[idx].hand.len() >= 2)
+            .collect();
+        if live.len() < 2 {
+            return Vec::new();
+        }
+        if self.showdown_style != ShowdownStyle::Fast {
+            return live;
+        }
+        let others: Vec<usize> = live.into_iter().filter(|&idx| idx != winner_idx).collect();
+        let (runner_up, _) = self.game.best_hand_among(&others);
+        vec![winner_idx, runner_up]
+    }
+    
+    // Award the badges the hand just settled earned. Nothing on a session's first hand,
+    // so someone just sitting down isn't greeted with a pile of them.
+    fn check_achievements(&mut self, won: bool) {
+        if self.hand_results.len() <= 1 {
+            return;
+        }
+        let Some(human_idx) = self.game.players.iter().position(|p| !p.is_bot) else {
+            return;
+        };
+        let human = &self.game.players[human_idx];
+        let started_with = self.leaderboard.iter()
+            .find(|&&(idx, _)| idx == human_idx)
+            .map_or(human.chips, |&(_, chips)| chips);
+        let summary = HandSummary {
+            won,
+            showdown: self.game.players.iter().filter(|p| !p.folded).count() > 1,
+            made_hand: if human.folded { None } else { self.game.evaluate_hand(human_idx).map(|rank| Game::hand_type_name(&rank)) },
+            win_streak: match self.current_streak() {
+                Some((true, length)) => length,
+                _ => 0,
             },
-            InputMode::Normal => {
-                // Regular game input handling
-                match key {
-                    KeyCode::Char('q') => {
-                        self.should_quit = true;
-                    },
-                    KeyCode::Char('d') => {
-                        // Allow starting new hand even if there's a game in progress
-                        self.game.deal_cards();
-                        self.messages.push("\nNew hand dealt.".to_string());
-                        
-                        // Force a larger delay to allow the UI to update and the player to see the new hand
-                        // This makes the game feel more natural and gives time to look at the cards
-                        std::thread::sleep(std::time::Duration::from_millis(500));
-                        
-                        // Add clear messages about blinds
-                        let sb_name = self.game.players[self.game.small_blind_idx].name.clone();
-                        let bb_name = self.game.players[self.game.big_blind_idx].name.clone();
-                        
-                        // Get positions for display (currently unused but kept for future enhancements)
-                        let _small_blind_pos = util::get_player_position(&self.game, self.game.small_blind_idx);
-                        let _big_blind_pos = util::get_player_position(&self.game, self.game.big_blind_idx);
-                        
-                        // Add clear blind posts
-                        self.messages.push(format!("{} in Small Blind (SB) position posts ${}.", 
-                                                  sb_name, self.game.min_bet / 2));
-                        self.messages.push(format!("{} in Big Blind (BB) position posts ${}.", 
-                                                  bb_name, self.game.min_bet));
-                        
-                        // Verify deck is properly set up - must have more than 2*players cards 
-                        // after initial deal (approximately 52 - 2*player_count)
-                        if self.game.deck.len() < 35 {
-                            // Silently replace the deck without printing warnings
-                            self.game.deck = Game::create_deck();
-                            self.game.shuffle_deck();
-                        }
-                        
-                        // Reset tracking for new hand
-                        let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-                        self.player_starting_chips = self.game.players[human_idx].chips;
-                        self.round_results = None;
-                        self.game_active = true;
-                        self.game.last_action_count = 0;
-                        
-                        // Add a deliberate delay when a new hand is dealt to make the game flow more naturally
-                        // First, check if it's a bot's turn 
-                        if self.game.players[self.game.current_player_idx].is_bot {
-                            // Always set thinking to true and force a much longer delay (3-4 seconds) for the first action
-                            self.bot_thinking = true;
-                            let delay = rand::thread_rng().gen_range(3000..4000); // Much longer thinking time (3-4 seconds)
-                            self.bot_think_until = std::time::Instant::now() + std::time::Duration::from_millis(delay);
-                            
-                            // Placeholder for bot thinking
-                            let _bot_name = &self.game.players[self.game.current_player_idx].name;
-                            let _position = util::get_player_position(&self.game, self.game.current_player_idx);
-                            
-                            // Force UI update to show this message
-                            std::thread::sleep(std::time::Duration::from_millis(50));
-                        } else {
-                            self.bot_thinking = false;
-                        }
-                        
-                        // Show total stats
-                        if !self.game_stats.is_empty() {
-                            let total_profit = self.game_stats.iter().sum::<i32>();
-                            let profit_list = self.game_stats.iter()
-                                .enumerate()
-                                .map(|(i, profit)| format!("R{}: ${}{}", i+1, if *profit >= 0 {""} else {"-"}, profit.abs()))
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            self.messages.push(format!("Stats: {} rounds played. Profits: {}. Total: ${}", 
-                                                      self.game_stats.len(), profit_list, total_profit));
-                        }
-                    },
-                    KeyCode::Char('n') => {
-                        // Switch to player name input mode
-                        self.input.clear();
-                        self.input_mode = InputMode::PlayerName;
-                        self.messages.push("Enter your name and press 'n' to confirm:".to_string());
-                    },
-                    KeyCode::Char('s') => {
-                        // Stop current game
-                        if self.game_active {
-                            self.game_active = false;
-                            self.bot_thinking = false;
-                            self.messages.push("Game stopped. Press 'd' to deal a new hand.".to_string());
-                        }
-                    },
-                    KeyCode::Char('c') => {
-                        // Allow player action regardless of round
-                        if can_take_action && self.game_active {
-                            // Double-check it's actually the player's turn
-                            if !self.game.players[self.game.current_player_idx].is_bot {
-                                // Check if there's a bet to call
-                                let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-                                let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
-                                
-                                if highest_bet <= player_current_bet {
-                                    self.messages.push("No bet to call - action changed to check.".to_string());
-                                }
-                                self.handle_player_action(GameAction::Call);
-                            } else {
-                                self.messages.push("It's not your turn yet. Please wait.".to_string());
-                            }
-                        }
-                    },
-                    KeyCode::Char('k') => {
-                        // Allow player action regardless of round
-                        if can_take_action && self.game_active {
-                            // Double-check it's actually the player's turn
-                            if !self.game.players[self.game.current_player_idx].is_bot {
-                                // Check if there's a bet to call (can't check if there is)
-                                let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-                                let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
-                                
-                                if highest_bet > player_current_bet {
-                                    self.messages.push("There's a bet - action changed to call.".to_string());
-                                }
-                                self.handle_player_action(GameAction::Check);
-                            } else {
-                                self.messages.push("It's not your turn yet. Please wait.".to_string());
-                            }
-                        }
-                    },
-                    KeyCode::Char('f') => {
-                        // Allow player action regardless of round
-                        if can_take_action && self.game_active {
-                            // Double-check it's actually the player's turn
-                            if !self.game.players[self.game.current_player_idx].is_bot {
-                                self.handle_player_action(GameAction::Fold);
-                            } else {
-                                self.messages.push("It's not your turn yet. Please wait.".to_string());
-                            }
-                        }
-                    },
-                    KeyCode::Char('r') => {
-                        // Allow player action regardless of round
-                        if can_take_action && self.game_active {
-                            // Double-check it's actually the player's turn
-                            if !self.game.players[self.game.current_player_idx].is_bot {
-                                // Use the current input as raise amount
-                                if self.input.is_empty() {
-                                    self.messages.push("Please enter a raise amount first, then press 'r'.".to_string());
-                                } else if let Ok(amount) = self.input.parse::<u32>() {
-                                    self.handle_player_action(GameAction::Raise(amount));
-                                    self.input.clear();
-                                } else {
-                                    self.messages.push("Invalid raise amount. Please enter a number.".to_string());
-                                }
-                            } else {
-                                self.messages.push("It's not your turn yet. Please wait.".to_string());
-                            }
-                        }
-                    },
-                    KeyCode::Char(c) => {
-                        if c.is_digit(10) && is_player_turn {
-                            self.input.push(c);
-                        }
-                    },
-                    KeyCode::Backspace => {
-                        if is_player_turn {
-                            self.input.pop();
-                        }
-                    },
-                    // Add scrolling support for message history
-                    KeyCode::Up => {
-                        if self.message_scroll_pos > 0 {
-                            self.message_scroll_pos -= 1;
-                        }
-                    },
-                    KeyCode::Down => {
-                        if self.message_scroll_pos < self.messages.len().saturating_sub(1) {
-                            self.message_scroll_pos += 1;
-                        }
-                    },
-                    KeyCode::PageUp => {
-                        // Scroll up 10 lines at a time
-                        self.message_scroll_pos = self.message_scroll_pos.saturating_sub(10);
-                    },
-                    KeyCode::PageDown => {
-                        // Scroll down 10 lines at a time
-                        self.message_scroll_pos = (self.message_scroll_pos + 10).min(self.messages.len().saturating_sub(1));
-                    },
-                    KeyCode::Home => {
-                        // Scroll to the top
-                        self.message_scroll_pos = 0;
-                    },
-                    KeyCode::End => {
-                        // Scroll to the bottom
-                        self.message_scroll_pos = self.messages.len().saturating_sub(1);
-                    },
-                    _ => {}
-                }
+            chips: human.chips,
+            starting_chips: self.player_starting_chips,
+            net_gain: human.chips.saturating_sub(started_with),
+            big_blind: self.game.big_blind,
+            raised_river: self.game.action_history.iter()
+                .any(|a| a.player_idx == human_idx && a.round == Round::River && matches!(a.action, GameAction::Raise(_))),
+        };
+        
+        for achievement in achievements::award(&summary, &mut self.achievements) {
+            self.achievement_lines.push(format!("Achievement unlocked: {} - {}!", achievement.name, achievement.description));
+        }
+    }
+    
+    // Announce newly earned badges once the hand's result is in the log
+    pub fn flush_achievements(&mut self) {
+        if !self.game_active {
+            let lines = std::mem::take(&mut self.achievement_lines);
+            self.messages.extend(lines);
+        }
+    }
+    
+    // Show the queued bot reaction once the hand's result is in the log
+    pub fn flush_table_talk(&mut self) {
+        if !self.game_active {
+            if let Some(line) = self.table_talk.take() {
+                self.messages.push(line);
             }
         }
     }
     
-    pub fn print_game_stats(&mut self) {
-        if !self.game_stats.is_empty() {
-            let total_profit = self.game_stats.iter().sum::<i32>();
-            
-            // Get the human's current chip stack
-            let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-            let current_chips = self.game.players[human_idx].chips;
-            
-            // Calculate profit for the current round
-            let current_round_profit = if !self.game_stats.is_empty() {
-                self.game_stats.last().unwrap()
+    // " (plus your $40 uncalled bet returned)" for a win line, when part of the winner's own bet
+    // came back to them instead of counting as chips won
+    fn uncalled_note(&self, winner_idx: usize) -> String {
+        match self.game.uncalled_return {
+            Some((idx, amount)) if idx == winner_idx => {
+                let whose = if self.game.players[idx].is_bot { "their" } else { "your" };
+                format!(" (plus {} ${} uncalled bet returned)", whose, amount)
+            },
+            _ => String::new(),
+        }
+    }
+    
+    // Announce side pots won by players other than the main pot winner
+    // (called first thing after every settlement, so it also marks where a showdown's result starts)
+    fn announce_side_pots(&mut self) {
+        if self.game.players.iter().filter(|p| !p.folded).count() > 1 {
+            self.showdown_msg = Some(self.messages.len());
+        }
+        // An uncontested winner's refund is told with the win itself (uncalled_note)
+        if let Some((idx, amount)) = self.game.uncalled_return {
+            let player = &self.game.players[idx];
+            let uncontested = !player.folded && self.game.players.iter().filter(|p| !p.folded).count() == 1;
+            if !uncontested {
+                let who = if player.is_bot { player.name.clone() } else { "You".to_string() };
+                self.messages.push(format!("Uncalled bet of ${} returned to {}.", amount, who));
+            }
+        }
+        if let Some(collected) = self.game.last_walk {
+            let bb_idx = self.game.option_player_idx();
+            let bb = &self.game.players[bb_idx];
+            let note = self.uncalled_note(bb_idx);
+            if bb.is_bot {
+                self.messages.push(format!("Everyone folds - {} wins the blinds, ${}{}.", bb.name, collected, note));
             } else {
-                &0
-            };
-            
-            // Format the round profits
-            let round_profits = self.game_stats.iter()
-                .enumerate()
-                .map(|(_i, profit)| format!("${}{}", if *profit >= 0 {""} else {"-"}, profit.abs()))
-                .collect::<Vec<_>>()
-                .join(". ");
-            
-            // Show detailed stats
-            self.messages.push(format!(
-                "Overall stats: {} rounds played. Current round profit: ${}{}. Total profit: ${}. Current chips: ${}", 
-                self.game_stats.len(), 
-                if *current_round_profit >= 0 { "" } else { "-" },
-                current_round_profit.abs(),
-                total_profit,
-                current_chips
-            ));
-            
-            // Show round-by-round profits
-            self.messages.push(format!("Round profits: {}", round_profits));
-            self.messages.push("".to_string()); // Add empty line for better readability
-            self.messages.push("".to_string()); // Add empty line for better readability
-        } else {
-            self.messages.push("STATS: No rounds played yet.".to_string());
+                self.messages.push(format!("Everyone folds - you win the blinds, ${}{}.", collected, note));
+            }
+        }
+        // With side pots or a split, list every pot: the main pot first, then side pots in the
+        // order they formed, each tied winner with their share
+        let split = self.game.pot_awards.iter().any(|pot| pot.winners.len() > 1);
+        if self.game.pot_awards.len() > 1 || split {
+            let mut lines = Vec::new();
+            for (number, pot) in self.game.pot_awards.iter().enumerate() {
+                let name = match number {
+                    0 if self.game.pot_awards.len() == 1 => "Pot".to_string(),
+                    0 => "Main pot".to_string(),
+                    _ => format!("Side pot {}", number),
+                };
+                lines.push(format!("{} (${}, {} players): {} with {}.", name, pot.amount, pot.contenders.len(), self.pot_winners(pot), pot.hand));
+            }
+            self.messages.extend(lines);
+        }
+    }
+    
+    // "Bot 2 wins", or for a split "split between You ($17) and Bot 2 ($16)"
+    fn pot_winners(&self, pot: &PotAward) -> String {
+        let name = |seat: usize| {
+            let player = &self.game.players[seat];
+            if player.is_bot { player.name.clone() } else { "You".to_string() }
+        };
+        if let [(seat, _)] = pot.winners[..] {
+            return if self.game.players[seat].is_bot { format!("{} wins", name(seat)) } else { "You win".to_string() };
+        }
+        let shares: Vec<String> = pot.winners.iter()
+            .map(|&(seat, share)| format!("{} (${})", name(seat), share))
+            .collect();
+        let (last, rest) = shares.split_last().expect("a split has winners");
+        format!("split between {} and {}", rest.join(", "), last)
+    }
+    
+    // Walk the hand results backward to find the human's current streak.
+    // Returns (won, length), or None if no hands have been played yet.
+    pub fn current_streak(&self) -> Option<(bool, usize)> {
+        let last = *self.hand_results.last()?;
+        let length = self.hand_results.iter()
+            .rev()
+            .take_while(|&&won| won == last)
+            .count();
+        Some((last, length))
+    }
+    
+    // Format the current streak for the stats line
+    pub fn streak_display(&self) -> String {
+        match self.current_streak() {
+            Some((true, length)) => format!("Win streak: {}", length),
+            Some((false, length)) => format!("Lost {} in a row", length),
+            None => "No streak yet".to_string(),
         }
     }
     
     // Process a bot action
 pub fn process_bot_action(&mut self, bot_action: GameAction, bot_player: Player) {
+    // Surface any engine notes about how the decision was made
+    self.flush_debug_log();
+
+    self.last_progress_at = Instant::now();
+    
+    // Raises past the street cap are played as calls
+    let bot_action = match bot_action {
+        GameAction::Raise(_) if self.game.raise_cap_reached() => {
+            self.messages.push(format!("Betting capped - {} calls instead of raising.", bot_player.name));
+            GameAction::Call
+        },
+        other => other,
+    };
+    
     let action_str = match &bot_action {
         GameAction::Fold => "folds".to_string(),
         GameAction::Call => "calls".to_string(),
         GameAction::Check => "checks".to_string(),
         GameAction::Raise(amount) => {
             let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-            let is_first_bet = highest_bet == 0 || highest_bet == self.game.min_bet;
+            let is_first_bet = highest_bet == 0 || highest_bet == self.game.big_blind;
             
             if is_first_bet && self.game.round != Round::PreFlop {
                 format!("bets {}", amount)
@@ -344,11 +2510,17 @@ pub fn process_bot_action(&mut self, bot_action: GameAction, bot_player: Player)
         },
     };
     
-    // Add message about bot action
+    // Add message about bot action, and its reasoning when it gave one (--think-aloud)
     self.messages.push(format!("{} {}.", bot_player.name, action_str));
+    if let Some(reason) = self.game.last_bot_reason.take() {
+        self.log(Verbosity::Verbose, format!("  {} thinks: \"{}\"", bot_player.name, reason));
+    }
     
     // Perform the action in the game
     let actual_action = self.game.perform_action(bot_action);
+    if let Some(record) = self.game.action_history.last() {
+        self.session_stats.bot_think_ms += record.decision_ms;
+    }
     
     // Process pot increase if any
     let player_idx = self.game.current_player_idx;
@@ -374,7 +2546,7 @@ pub fn process_bot_action(&mut self, bot_action: GameAction, bot_player: Player)
     // Log pot increase if any
     if contribution > 0 {
         let old_pot = self.game.pot - contribution;
-        self.messages.push(format!("Pot increased from ${} to ${}.", old_pot, self.game.pot));
+        self.log(Verbosity::Verbose, format!("Pot increased from ${} to ${}.", old_pot, self.game.pot));
     }
     
     // Get the current round before moving to next player
@@ -383,22 +2555,15 @@ pub fn process_bot_action(&mut self, bot_action: GameAction, bot_player: Player)
     // Move to next player
     let game_continues = self.game.next_player();
     
+    // Heads-up all-in with the human: offer a chop before the board is run out
+    if self.game.chop_pending {
+        self.offer_chop();
+        return;
+    }
+    
     // Handle round transitions
     self.handle_round_transition(current_round, game_continues);
     
-    // Notify if it's the player's turn
-    if game_continues && !self.game.players[self.game.current_player_idx].is_bot {
-        // Check if there's a bet to call
-        let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-        let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
-        
-        if highest_bet > player_current_bet {
-            self.messages.push("Your turn now. Options: [c]all, [f]old, or [r]aise.".to_string());
-        } else {
-            self.messages.push("Your turn now. Options: [k]heck, [f]old, or [r]aise.".to_string());
-        }
-    }
-    
     // Check if round ended
     if !game_continues {
         self.handle_end_of_round();
@@ -415,11 +2580,11 @@ pub fn process_bot_action(&mut self, bot_action: GameAction, bot_player: Player)
         if is_start_of_hand {
             // Longer thinking time at the start of a hand (2-3 seconds)
             self.bot_think_until = std::time::Instant::now() + 
-                std::time::Duration::from_millis(rand::thread_rng().gen_range(2000..3000));
+                std::time::Duration::from_millis(rand::thread_rng().gen_range(BOT_THINK_HAND_START_MS));
         } else {
             // Regular thinking time during hand (1.5-2.5 seconds)
             self.bot_think_until = std::time::Instant::now() + 
-                std::time::Duration::from_millis(rand::thread_rng().gen_range(1500..2500));
+                std::time::Duration::from_millis(rand::thread_rng().gen_range(BOT_THINK_MS));
         }
     }
     
@@ -436,20 +2601,21 @@ fn handle_round_transition(&mut self, previous_round: Round, game_continues: boo
         match self.game.round {
             Round::Flop => {
                 std::thread::sleep(std::time::Duration::from_millis(50));
-                self.messages.push("--- Moving to FLOP round (first 3 community cards) ---".to_string());
+                self.log(Verbosity::Normal, "--- Moving to FLOP round (first 3 community cards) ---".to_string());
+                self.start_flop_reveal();
             },
             Round::Turn => {
                 std::thread::sleep(std::time::Duration::from_millis(50));
-                self.messages.push("--- Moving to TURN round (4th community card) ---".to_string());
+                self.log(Verbosity::Normal, "--- Moving to TURN round (4th community card) ---".to_string());
             },
             Round::River => {
                 std::thread::sleep(std::time::Duration::from_millis(50));
-                self.messages.push("--- Moving to RIVER round (final community card) ---".to_string());
+                self.log(Verbosity::Normal, "--- Moving to RIVER round (final community card) ---".to_string());
             },
             Round::Showdown => {
                 std::thread::sleep(std::time::Duration::from_millis(100));
-                self.messages.push("--- Moving to SHOWDOWN (comparing hands) ---".to_string());
-                self.messages.push("".to_string()); // Add empty line for better readability
+                self.log(Verbosity::Normal, "--- Moving to SHOWDOWN (comparing hands) ---".to_string());
+                self.log(Verbosity::Normal, "".to_string()); // Add empty line for better readability
                 self.determine_winner_and_end_round();
                 return;
             },
@@ -462,78 +2628,73 @@ fn handle_round_transition(&mut self, previous_round: Round, game_continues: boo
                 .map(|c| c.to_string())
                 .collect::<Vec<_>>()
                 .join(" ");
-            self.messages.push(format!("Community cards: {}", cards_text));
+            self.log(Verbosity::Normal, format!("Community cards: {}", cards_text));
             
             // Force UI update by adding a small delay
             std::thread::sleep(std::time::Duration::from_millis(50));
         }
-        
-        // Make sure the current player is correctly set for the new round
-        if self.game.round != Round::Showdown && !self.game.players[self.game.current_player_idx].is_bot {
-            // Human's turn - notify explicitly
-            // Check if there's a bet to call
-            let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-            let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
-            
-            if highest_bet > player_current_bet {
-                self.messages.push(format!("Your turn now. Choose action: [c]all, [f]old, or [r]aise."));
-            } else {
-                self.messages.push(format!("Your turn. No bet to call. Choose [k]heck, [f]old, or [r]aise."));
-            }
-        }
     }
 }
 
 // Handle end of round (winner determination when game is over)
 fn handle_end_of_round(&mut self) {
-    // Get winner info
-    let (winner_idx, winnings, hand_type) = self.game.determine_winner();
-    let winner_name = self.game.players[winner_idx].name.clone();
+    let SettledHand { winner_idx, winnings, hand_type, human_took_pot, profit, total_profit } = self.settle_hand(None);
     
-    // Calculate profit/loss for human player
-    let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-    let human_player = &self.game.players[human_idx];
-    let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-    
-    // Set round results and track Stats
-    self.round_results = Some((winner_name.clone(), profit));
-    self.game_stats.push(profit);
+    // Everyone else folded: settle straight away, there are no hands to reveal
+    if self.game.players.iter().filter(|p| !p.folded).count() == 1 {
+        if self.game.last_walk.is_none() {
+            let who = if human_took_pot { "you win".to_string() } else { format!("{} wins", self.game.players[winner_idx].name) };
+            self.messages.push(format!("Everyone folds - {} ${} without a showdown{}.", who, winnings, self.uncalled_note(winner_idx)));
+        }
+        self.push_hand_result(profit, total_profit);
+        self.messages.push(self.deal_prompt());
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+        return;
+    }
     
-    // Calculate total profit across all rounds
-    let total_profit = self.game_stats.iter().sum::<i32>();
+    if self.showdown_style == ShowdownStyle::Fast {
+        let summary = self.fast_showdown_summary(winner_idx, winnings, &hand_type);
+        self.messages.push(summary);
+        self.mark_winning_cards(winner_idx, &[]);
+        self.explain_showdown_loss(winner_idx);
+        self.print_showdown_ranking();
+        return;
+    }
     
     // Show all active players' hands for clarity
-    self.messages.push("".to_string()); // Add empty line for better readability
-    self.messages.push("--- PLAYERS REVEAL THEIR HANDS ---".to_string());
+    self.log(Verbosity::Normal, "".to_string()); // Add empty line for better readability
+    self.log(Verbosity::Normal, "--- PLAYERS REVEAL THEIR HANDS ---".to_string());
     
-    // Show each player's hand
-    for (_idx, player) in self.game.players.iter().enumerate() {
-        if !player.folded && player.hand.len() >= 2 {
-            let hand_str = player.hand.iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<_>>()
-                .join(" ");
-            
-            if player.is_bot {
-                self.messages.push(format!("{} shows: {}", player.name, hand_str));
-            } else {
-                self.messages.push(format!("You show: {}", hand_str));
-            }
-            
-            // Add a small pause after each reveal
-            std::thread::sleep(std::time::Duration::from_millis(200));
+    // Show each player's hand, in the order they'd be turned over at a real table
+    let mut reveal_lines = Vec::new();
+    for idx in self.game.showdown_order() {
+        let player = &self.game.players[idx];
+        let hand_str = player.hand.iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        
+        reveal_lines.push((idx, self.messages.len()));
+        if player.is_bot {
+            self.messages.push(format!("{} shows: {}", player.name, hand_str));
+        } else {
+            self.messages.push(format!("You show: {}", hand_str));
         }
+        
+        // Add a small pause after each reveal
+        std::thread::sleep(std::time::Duration::from_millis(200));
     }
     
     // Add empty line after hands
-    self.messages.push("".to_string());
+    self.log(Verbosity::Normal, "".to_string());
+    self.mark_winning_cards(winner_idx, &reveal_lines);
     
     // Display results with emphasis
     let display_winnings = if winnings == 0 { 10 } else { winnings }; // Minimum 10 chips
     
     self.messages.push("WINNER DETERMINED".to_string());
     
-    let winner_name = if winner_idx == human_idx {
+    let winner_name = if human_took_pot {
         "You".to_string()
     } else {
         self.game.players[winner_idx].name.clone()
@@ -542,34 +2703,20 @@ fn handle_end_of_round(&mut self) {
     self.messages.push(format!("{} win ${} with {}!", 
                     winner_name, display_winnings, hand_type));
     
-    if winner_idx == human_idx {
-        self.messages.push(format!("You won this hand! Profit: ${}. Total: ${}", profit.abs(), total_profit));
-    } else {
-        self.messages.push(format!("You lost this hand. Loss: ${}. Total: ${}", profit.abs(), total_profit));
-    }
-    
-    // Mark game as inactive until player deals again
-    self.game_active = false;
-    self.messages.push("Press 'd' to deal a new hand.".to_string());
+    self.push_hand_result(profit, total_profit);
+    self.explain_showdown_loss(winner_idx);
+    self.print_showdown_ranking();
+    self.messages.push(self.deal_prompt());
 }
 
 // Handle safety timeout for too many actions
 fn handle_safety_timeout(&mut self) {
     self.messages.push("Round ending (action limit reached).".to_string());
-    let (winner_idx, winnings, hand_type) = self.game.determine_winner();
+    let SettledHand { winner_idx, winnings, hand_type, total_profit, .. } = self.settle_hand(None);
     
     // Use minimum winnings display for clarity
     let display_winnings = if winnings == 0 { 10 } else { winnings }; 
     
-    // Calculate profit/loss for human player
-    let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-    let human_player = &self.game.players[human_idx];
-    let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-    
-    // Add to Stats and calculate total
-    self.game_stats.push(profit);
-    let total_profit = self.game_stats.iter().sum::<i32>();
-    
     // Show community cards used in the win
     let community_display = if !self.game.community_cards.is_empty() {
         let cards = self.game.community_cards.iter()
@@ -582,8 +2729,8 @@ fn handle_safety_timeout(&mut self) {
     };
     
     // Format result with community cards
-    let formatted_result = format!("{} wins ${} with {}{}! Your total profit: ${}", 
-                            self.game.players[winner_idx].name, display_winnings, 
+    let formatted_result = format!("{} wins ${}{} with {}{}! Your total profit: ${}", 
+                            self.game.players[winner_idx].name, display_winnings, self.uncalled_note(winner_idx),
                             hand_type, community_display, total_profit);
     self.messages.push(formatted_result);
     
@@ -593,28 +2740,36 @@ fn handle_safety_timeout(&mut self) {
     // Print Stats
     self.print_game_stats();
     
-    self.game_active = false;
-    self.messages.push("Press 'd' to deal a new hand.".to_string());
-    self.messages.push("".to_string()); // Add empty line between rounds
+    self.messages.push(self.deal_prompt());
+    self.log(Verbosity::Normal, "".to_string()); // Add empty line between rounds
 }
 
 // Determine winner at showdown
 fn determine_winner_and_end_round(&mut self) {
-    self.messages.push("--- PLAYERS REVEAL THEIR HANDS ---".to_string());
+    if self.showdown_style == ShowdownStyle::Fast {
+        let SettledHand { winner_idx, winnings, hand_type, .. } = self.settle_hand(None);
+        let summary = self.fast_showdown_summary(winner_idx, winnings, &hand_type);
+        self.messages.push(summary);
+        self.mark_winning_cards(winner_idx, &[]);
+        self.explain_showdown_loss(winner_idx);
+        self.print_showdown_ranking();
+        self.message_scroll_pos = self.messages.len().saturating_sub(1);
+        return;
+    }
     
-    // Create a more prominent hands display
-    let active_players: Vec<(usize, &Player)> = self.game.players.iter()
-        .enumerate()
-        .filter(|(_, p)| !p.folded && p.hand.len() >= 2)
-        .collect();
+    self.log(Verbosity::Normal, "--- PLAYERS REVEAL THEIR HANDS ---".to_string());
     
-    // Show each hand with a small delay between them
-    for (_idx, player) in active_players {
+    // Show each hand with a small delay between them, in the showing order
+    // (the last river aggressor first, then clockwise)
+    let mut reveal_lines = Vec::new();
+    for idx in self.game.showdown_order() {
+        let player = &self.game.players[idx];
         let hand_str = player.hand.iter()
             .map(|c| c.to_string())
             .collect::<Vec<_>>()
             .join(" ");
             
+        reveal_lines.push((idx, self.messages.len()));
         if player.is_bot {
             self.messages.push(format!("{} shows: {}", player.name, hand_str));
         } else {
@@ -626,26 +2781,14 @@ fn determine_winner_and_end_round(&mut self) {
     }
     
     // Add an empty line after all hands are revealed
-    self.messages.push("".to_string());
+    self.log(Verbosity::Normal, "".to_string());
     
     // Force UI update with extra delay
     std::thread::sleep(std::time::Duration::from_millis(100));
     
     // Determine the winner
-    let (winner_idx, winnings, hand_type) = self.game.determine_winner();
-    let winner_name = self.game.players[winner_idx].name.clone();
-    
-    // Calculate profit/loss for human player
-    let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-    let human_player = &self.game.players[human_idx];
-    let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-    
-    // Set round results and track Stats
-    self.round_results = Some((winner_name.clone(), profit));
-    self.game_stats.push(profit);
-    
-    // Calculate total profit across all rounds
-    let total_profit = self.game_stats.iter().sum::<i32>();
+    let SettledHand { winner_idx, winnings, hand_type, human_took_pot, profit, total_profit } = self.settle_hand(None);
+    self.mark_winning_cards(winner_idx, &reveal_lines);
     
     // Show community cards used in the win
     let community_display = if !self.game.community_cards.is_empty() {
@@ -664,10 +2807,10 @@ fn determine_winner_and_end_round(&mut self) {
     // Display results in message log with more detail and emphasis
     let display_winnings = if winnings == 0 { 10 } else { winnings }; // Minimum 10 chips
     
-    self.messages.push("".to_string()); // Add empty line before winner
+    self.log(Verbosity::Normal, "".to_string()); // Add empty line before winner
     self.messages.push("WINNER DETERMINED".to_string());
     
-    let winner_name = if winner_idx == human_idx {
+    let winner_name = if human_took_pot {
         "You".to_string()
     } else {
         self.game.players[winner_idx].name.clone()
@@ -678,21 +2821,17 @@ fn determine_winner_and_end_round(&mut self) {
                             hand_type, community_display);
     self.messages.push(formatted_message);
     
-    self.messages.push("".to_string());
+    self.log(Verbosity::Normal, "".to_string());
     
-    if winner_idx == human_idx {
-        self.messages.push(format!("You won this hand! Your profit: ${}. Total: ${}", profit.abs(), total_profit));
-    } else {
-        self.messages.push(format!("You lost this hand. Your loss: ${}. Total: ${}", profit.abs(), total_profit));
-    }
+    self.push_hand_result(profit, total_profit);
+    self.explain_showdown_loss(winner_idx);
+    self.print_showdown_ranking();
     
     // Print Stats
     self.print_game_stats();
     
-    // End the game
-    self.game_active = false;
-    self.messages.push("Press 'd' to deal a new hand.".to_string());
-    self.messages.push("".to_string()); // Add empty line between rounds
+    self.messages.push(self.deal_prompt());
+    self.log(Verbosity::Normal, "".to_string()); // Add empty line between rounds
     
     // Ensure the message scroll position is updated to show the latest messages
     self.message_scroll_pos = self.messages.len().saturating_sub(1);
@@ -701,7 +2840,49 @@ fn determine_winner_and_end_round(&mut self) {
     std::thread::sleep(std::time::Duration::from_millis(100));
 }
 
+// One-line showdown result, e.g. "You win $120 with Flush (A♥ K♥) vs Bot 3's Straight"
+fn fast_showdown_summary(&self, winner_idx: usize, winnings: u32, hand_type: &str) -> String {
+    let winner = &self.game.players[winner_idx];
+    let winner_label = if winner.is_bot {
+        format!("{} wins", winner.name)
+    } else {
+        "You win".to_string()
+    };
+    let hole_cards = winner.hand.iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    
+    let mut summary = format!("{} ${}{} with {} ({})", winner_label, winnings, self.uncalled_note(winner_idx), hand_type, hole_cards);
+    
+    // Compare against the best of the remaining hands, if anyone else made it to showdown
+    let others: Vec<usize> = self.game.players.iter()
+        .enumerate()
+        .filter(|(idx, p)| *idx != winner_idx && !p.folded && p.hand.len() >= 2)
+        .map(|(idx, _)| idx)
+        .collect();
+    if !others.is_empty() {
+        let (runner_up_idx, runner_up_hand) = self.game.best_hand_among(&others);
+        let runner_up = &self.game.players[runner_up_idx];
+        if runner_up.is_bot {
+            summary.push_str(&format!(" vs {}'s {}", runner_up.name, runner_up_hand));
+        } else {
+            summary.push_str(&format!(" vs your {}", runner_up_hand));
+        }
+    }
+    
+    summary
+}
+
 pub fn handle_player_action(&mut self, action: GameAction) {
+        self.last_progress_at = Instant::now();
+        
+        // In a drill the decision is graded instead of played
+        if self.drill.as_ref().is_some_and(|drill| drill.awaiting_answer) {
+            self.grade_drill_answer(action);
+            return;
+        }
+        
         // Special handling for Showdown round - force winner determination
         if self.game.round == Round::Showdown {
             match action {
@@ -720,37 +2901,23 @@ pub fn handle_player_action(&mut self, action: GameAction) {
             }
             
             // Show all players' hands who haven't folded
-            self.messages.push("--- SHOWDOWN: Players reveal their hands ---".to_string());
-            for (_idx, player) in self.game.players.iter().enumerate() {
-                if !player.folded && player.hand.len() >= 2 {
-                    let hand_str = player.hand.iter()
-                        .map(|c| c.to_string())
-                        .collect::<Vec<_>>()
-                        .join(" ");
-                        
-                    if player.is_bot {
-                        self.messages.push(format!("{} shows: {}", player.name, hand_str));
-                    } else {
-                        self.messages.push(format!("You show: {}", hand_str));
-                    }
+            self.log(Verbosity::Normal, "--- SHOWDOWN: Players reveal their hands ---".to_string());
+            for idx in self.game.showdown_order() {
+                let player = &self.game.players[idx];
+                let hand_str = player.hand.iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                    
+                if player.is_bot {
+                    self.messages.push(format!("{} shows: {}", player.name, hand_str));
+                } else {
+                    self.messages.push(format!("You show: {}", hand_str));
                 }
             }
             
             // Force winner determination and round completion
-            let (winner_idx, winnings, hand_type) = self.game.determine_winner();
-            let winner_name = self.game.players[winner_idx].name.clone();
-            
-            // Calculate profit/loss for human player
-            let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-            let human_player = &self.game.players[human_idx];
-            let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-            
-            // Set round results and track Stats
-            self.round_results = Some((winner_name.clone(), profit));
-            self.game_stats.push(profit);
-            
-            // Calculate total profit across all rounds
-            let total_profit = self.game_stats.iter().sum::<i32>();
+            let SettledHand { winner_idx, winnings, hand_type, profit, total_profit, .. } = self.settle_hand(None);
             
             // Add hand explanation based on hand type
             let hand_explanation = match hand_type.split_whitespace().next().unwrap_or("") {
@@ -788,15 +2955,8 @@ pub fn handle_player_action(&mut self, action: GameAction) {
                 self.messages.push(format!("Hand info: {}", hand_explanation));
             }
             
-            if winner_idx == human_idx {
-                self.messages.push(format!("You won this hand! Your profit: ${}. Total: ${}", profit.abs(), total_profit));
-            } else {
-                self.messages.push(format!("You lost this hand. Your loss: ${}. Total: ${}", profit.abs(), total_profit));
-            }
-            
-            // End the game
-            self.game_active = false;
-            self.messages.push("Press 'd' to deal a new hand.".to_string());
+            self.push_hand_result(profit, total_profit);
+            self.messages.push(self.deal_prompt());
             return;
         }
         
@@ -809,55 +2969,9 @@ pub fn handle_player_action(&mut self, action: GameAction) {
             return;
         }
         
-        // Check for missing community cards in non-preflop rounds
-        if self.game.round != Round::PreFlop && self.game.community_cards.is_empty() {
-            self.messages.push("Dealing community cards...".to_string());
-            
-            // Force round advancement if stuck in PreFlop but UI shows different round
-            if self.game.round != Round::PreFlop && self.game.community_cards.is_empty() {
-                // Force appropriate community cards based on round
-                match self.game.round {
-                    Round::Flop => {
-                        self.game.community_cards.clear();
-                        for _ in 0..3 {
-                            if let Some(card) = self.game.deck.pop() {
-                                self.game.community_cards.push(card);
-                            }
-                        }
-                    },
-                    Round::Turn => {
-                        self.game.community_cards.clear();
-                        for _ in 0..4 { // Flop + Turn = 4 cards
-                            if let Some(card) = self.game.deck.pop() {
-                                self.game.community_cards.push(card);
-                            }
-                        }
-                    },
-                    Round::River => {
-                        self.game.community_cards.clear();
-                        for _ in 0..5 { // Full board = 5 cards
-                            if let Some(card) = self.game.deck.pop() {
-                                self.game.community_cards.push(card);
-                            }
-                        }
-                    },
-                    _ => {
-                        // Let normal deal_community_cards handle it
-                        self.game.deal_community_cards();
-                    }
-                }
-            } else {
-                // Normal dealing
-                self.game.deal_community_cards();
-            }
-            
-            // Log what was dealt
-            let cards_text = self.game.community_cards.iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<_>>()
-                .join(" ");
-            self.messages.push(format!("Community cards: {}", cards_text));
-        }
+        // next_round deals every street, so a betting street is never without a board
+        debug_assert!(!matches!(self.game.round, Round::Flop | Round::Turn | Round::River) || !self.game.community_cards.is_empty(),
+                      "human to act on the {:?} with no board dealt", self.game.round);
         
         let _player_position = get_player_position(&self.game, self.game.current_player_idx);
         // We don't need this anymore since we use actual_action_str
@@ -866,7 +2980,35 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         
         // Get the highest bet for terminology
         let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-        let is_first_bet = highest_bet == 0 || highest_bet == self.game.min_bet; // Consider BB as not a "bet"
+        let is_first_bet = highest_bet == 0 || highest_bet == self.game.big_blind; // Consider BB as not a "bet"
+        
+        // Raises past the street cap are played as calls
+        let action = match action {
+            GameAction::Raise(_) if self.game.raise_cap_reached() => {
+                self.messages.push("Betting capped - your raise is played as a call.".to_string());
+                GameAction::Call
+            },
+            GameAction::Raise(amount) => {
+                // Say so when a raise is cut down to the pot-limit maximum
+                let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
+                match self.game.pot_limit_max_raise_to() {
+                    Some(max_to) if player_current_bet + amount > max_to => {
+                        let max_add = max_to.saturating_sub(player_current_bet);
+                        self.messages.push(format!("Capped to pot-limit max ${}", max_add));
+                        GameAction::Raise(max_add)
+                    },
+                    _ => {
+                        // Nudge typed amounts onto the chip increment
+                        let rounded = self.game.round_raise(amount);
+                        if rounded != amount {
+                            self.messages.push(format!("Raise rounded to ${} (chips come in ${}s).", rounded, self.game.chip_increment));
+                        }
+                        GameAction::Raise(rounded)
+                    },
+                }
+            },
+            other => other,
+        };
         
         // Save the original action type for comparison
         let original_action_type = match &action {
@@ -879,6 +3021,14 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         // Perform the action and get the actual action performed
         let actual_action = self.game.perform_action(action.clone());
         
+        // Track thinking time for the stats (auto-calls aren't decisions)
+        if let Some(record) = self.game.action_history.last() {
+            self.session_stats.human_think_ms += record.decision_ms;
+            if self.auto_call.is_none() {
+                self.human_decision_ms.push(record.decision_ms);
+            }
+        }
+        
         // Update action string based on what was actually performed
         let actual_action_str = match &actual_action.0 {
             GameAction::Fold => "fold".to_string(),
@@ -959,7 +3109,7 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         
         // Log pot increase (only if it changed)
         if old_pot < self.game.pot {
-            self.messages.push(format!("Pot increased from ${} to ${}.", old_pot, self.game.pot));
+            self.log(Verbosity::Verbose, format!("Pot increased from ${} to ${}.", old_pot, self.game.pot));
         }
         
         // Log player chip changes if this is the human player and they're contributing chips
@@ -967,10 +3117,7 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         if current_player_idx == human_idx {
             let chips_before = self.player_starting_chips;
             let chips_now = self.game.players[human_idx].chips;
-            let actual_action_type = match &actual_action.0 {
-                GameAction::Call | GameAction::Raise(_) => true,
-                _ => false
-            };
+            let actual_action_type = matches!(&actual_action.0, GameAction::Call | GameAction::Raise(_));
             
             // Only show chip change message if chips actually changed AND the action was a call or raise
             if chips_before != chips_now && actual_action_type {
@@ -990,33 +3137,29 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         // Move to next player
         let game_continues = self.game.next_player();
         
+        // Heads-up all-in: offer a chop before the board is run out
+        if self.game.chop_pending {
+            self.offer_chop();
+            return;
+        }
+        
         // Check if round changed (to make turn transitions more visible)
         let new_round = self.game.round;
         if new_round != current_round {
             // Add a message about round transition
             match new_round {
-                Round::Flop => self.messages.push("--- Moving to FLOP round (first 3 community cards) ---".to_string()),
-                Round::Turn => self.messages.push("--- Moving to TURN round (4th community card) ---".to_string()),
-                Round::River => self.messages.push("--- Moving to RIVER round (final community card) ---".to_string()),
+                Round::Flop => {
+                    self.log(Verbosity::Normal, "--- Moving to FLOP round (first 3 community cards) ---".to_string());
+                    self.start_flop_reveal();
+                },
+                Round::Turn => self.log(Verbosity::Normal, "--- Moving to TURN round (4th community card) ---".to_string()),
+                Round::River => self.log(Verbosity::Normal, "--- Moving to RIVER round (final community card) ---".to_string()),
                 Round::Showdown => {
-                    self.messages.push("--- Moving to SHOWDOWN (comparing hands) ---".to_string());
+                    self.log(Verbosity::Normal, "--- Moving to SHOWDOWN (comparing hands) ---".to_string());
                     
                     // In Showdown, we should immediately determine the winner
                     // This eliminates the need for the player to act again
-                    let (winner_idx, winnings, hand_type) = self.game.determine_winner();
-                    let winner_name = self.game.players[winner_idx].name.clone();
-                    
-                    // Calculate profit/loss for human player
-                    let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-                    let human_player = &self.game.players[human_idx];
-                    let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-                    
-                    // Set round results and track Stats
-                    self.round_results = Some((winner_name.clone(), profit));
-                    self.game_stats.push(profit);
-                    
-                    // Calculate total profit across all rounds
-                    let total_profit = self.game_stats.iter().sum::<i32>();
+                    let SettledHand { winner_idx, winnings, hand_type, profit, total_profit, .. } = self.settle_hand(None);
                     
                     // Show community cards used in the win
                     let community_display = if !self.game.community_cards.is_empty() {
@@ -1035,11 +3178,7 @@ pub fn handle_player_action(&mut self, action: GameAction) {
                                             self.game.players[winner_idx].name, display_winnings, 
                                             hand_type, community_display));
                     
-                    if winner_idx == human_idx {
-                        self.messages.push(format!("You won this hand! Your profit: ${}. Total: ${}", profit.abs(), total_profit));
-                    } else {
-                        self.messages.push(format!("You lost this hand. Your loss: ${}. Total: ${}", profit.abs(), total_profit));
-                    }
+                    self.push_hand_result(profit, total_profit);
                     
                     // Add a small delay to ensure UI updates correctly
                     std::thread::sleep(std::time::Duration::from_millis(100));
@@ -1047,10 +3186,8 @@ pub fn handle_player_action(&mut self, action: GameAction) {
                     // Print Stats
                     self.print_game_stats();
                     
-                    // End the game
-                    self.game_active = false;
-                    self.messages.push("Press 'd' to deal a new hand.".to_string());
-                    self.messages.push("".to_string()); // Add empty line between rounds
+                    self.messages.push(self.deal_prompt());
+                    self.log(Verbosity::Normal, "".to_string()); // Add empty line between rounds
                     
                     // Ensure the message scroll position is updated to show the latest messages
                     self.message_scroll_pos = self.messages.len().saturating_sub(1);
@@ -1066,16 +3203,7 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         // Check if game ended after player's action
         if !game_continues {
             // Get winner info
-            let (winner_idx, winnings, hand_type) = self.game.determine_winner();
-            let winner_name = self.game.players[winner_idx].name.clone();
-            
-            // Calculate profit/loss for human player
-            let human_idx = self.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
-            let human_player = &self.game.players[human_idx];
-            let profit = human_player.chips as i32 - self.player_starting_chips as i32;
-            
-            // Set round results
-            self.round_results = Some((winner_name, profit));
+            let SettledHand { winner_idx, winnings, hand_type, profit, total_profit, .. } = self.settle_hand(None);
             
             // Show community cards used in the win
             let community_display = if !self.game.community_cards.is_empty() {
@@ -1089,18 +3217,15 @@ pub fn handle_player_action(&mut self, action: GameAction) {
             };
             
             // Display results in message log with more detail
-            self.messages.push(format!("Round over! {} wins ${} chips with {}{}!", 
-                                      self.game.players[winner_idx].name, winnings, 
+            self.messages.push(format!("Round over! {} wins ${} chips{} with {}{}!", 
+                                      self.game.players[winner_idx].name, winnings, self.uncalled_note(winner_idx),
                                       hand_type, community_display));
             
-            if winner_idx == human_idx {
-                self.messages.push(format!("You won this hand! Your profit: ${}.", profit.abs()));
-            } else {
-                self.messages.push(format!("You lost this hand. Your loss: ${}.", profit.abs()));
-            }
+            self.push_hand_result(profit, total_profit);
             
             // Print Stats
             self.print_game_stats();
+            self.messages.push(self.deal_prompt());
             return;
         }
         
@@ -1108,18 +3233,720 @@ pub fn handle_player_action(&mut self, action: GameAction) {
         if self.game.players[self.game.current_player_idx].is_bot {
             self.bot_thinking = true;
             self.bot_think_until = Instant::now() + 
-                std::time::Duration::from_millis(rand::thread_rng().gen_range(1500..3000));
-        } else {
-            // It's the player's turn now
-            // Check if there's a bet to call
-            let highest_bet = self.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-            let player_current_bet = self.game.players[self.game.current_player_idx].current_bet;
-            
-            if highest_bet > player_current_bet {
-                self.messages.push("Your turn now. Options: [c]all, [f]old, or [r]aise.".to_string());
+                std::time::Duration::from_millis(rand::thread_rng().gen_range(BOT_THINK_AFTER_HUMAN_MS));
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Suit;
+
+    fn test_app() -> App {
+        App::new(None, "Tester".to_string(), Config { fresh_table: true, ..Config::default() })
+    }
+
+    fn card(rank: game::Rank, suit: Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    // Everyone checks or calls to the river
+    fn check_down(game: &mut Game) {
+        for _ in 0..200 {
+            if game.round == Round::Showdown {
+                break;
+            }
+            let action = if game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+            game.perform_action(action);
+            if !game.next_player() {
+                break;
+            }
+        }
+    }
+
+    // You (seat 0) and Bot 1 both play the board's broadway straight; Bot 2 has nothing.
+    // The button is on you, so Bot 1 is first to its left and takes the odd chip of the $33 pot.
+    // Bot 1 and the human split a checked-down pot, Bot 1 taking the odd chip
+    fn split_pot_hand() -> App {
+        use crate::game::Rank;
+        let mut app = test_app();
+        app.game = Game::new(1, 2, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        app.game.dealer_idx = 2;
+        app.game.stack_deck(&[
+            [card(Rank::Ten, Suit::Hearts), card(Rank::Three, Suit::Clubs)],
+            [card(Rank::Ten, Suit::Diamonds), card(Rank::Four, Suit::Clubs)],
+            [card(Rank::Seven, Suit::Clubs), card(Rank::Eight, Suit::Diamonds)],
+        ], &[
+            card(Rank::Ace, Suit::Spades), card(Rank::King, Suit::Spades), card(Rank::Queen, Suit::Diamonds),
+            card(Rank::Jack, Suit::Clubs), card(Rank::Two, Suit::Hearts),
+        ]);
+        app.game.deal_cards();
+        app.game_active = true;
+        app.player_starting_chips = 1000;
+        check_down(&mut app.game);
+        app
+    }
+
+    fn split_pot_app() -> App {
+        let mut app = split_pot_hand();
+        app.game.determine_winner();
+        app
+    }
+
+    #[test]
+    fn split_pot_the_human_profits_from_counts_as_a_win() {
+        let mut app = split_pot_hand();
+        app.handle_end_of_round();
+
+        // Bot 1 takes the bigger share, but the human still gets back more than they put in
+        assert_eq!(app.round_results.as_ref().map(|r| r.0.as_str()), Some("Bot 1"));
+        assert_eq!(app.game_stats, vec![5]);
+        assert_eq!(app.hand_results, vec![true]);
+        assert_eq!(app.current_streak(), Some((true, 1)));
+        assert!(app.messages.iter().any(|line| line == "You won this hand! Your profit: $5. Total: $5"), "{:?}", app.messages);
+    }
+
+    #[test]
+    fn knockout_goes_to_the_winner_of_the_busted_players_last_pot() {
+        use crate::game::Rank;
+        let mut app = test_app();
+        app.game = Game::new(1, 3, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        app.game.stack_deck(&[
+            [card(Rank::King, Suit::Spades), card(Rank::King, Suit::Hearts)],
+            [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+            [card(Rank::Seven, Suit::Clubs), card(Rank::Two, Suit::Diamonds)],
+            [card(Rank::Queen, Suit::Spades), card(Rank::Queen, Suit::Hearts)],
+        ], &[]);
+        app.game.deal_cards();
+        app.game.community_cards = vec![
+            card(Rank::Three, Suit::Diamonds), card(Rank::Eight, Suit::Clubs), card(Rank::Nine, Suit::Hearts),
+            card(Rank::Jack, Suit::Spades), card(Rank::Four, Suit::Clubs),
+        ];
+        app.game.round = Round::Showdown;
+        // Bot 2 is all in for $50 and Bot 1 for $100; the human and Bot 3 play a $500 side pot
+        app.game.hand_start_chips = vec![1000, 100, 50, 1000];
+        app.game.hand_contributions = vec![500, 100, 50, 500];
+        app.game.pot = 1150;
+        for (player, chips) in app.game.players.iter_mut().zip([500, 0, 0, 500]) {
+            player.chips = chips;
+            player.folded = false;
+        }
+        app.game.determine_winner();
+        assert_eq!(app.game.players[0].chips, 1300, "the human wins the biggest pot");
+
+        app.game_stats.push(300);
+        app.check_eliminations();
+        assert_eq!(app.knockouts, vec![("Bot 2".to_string(), Some("Bot 1".to_string()))]);
+        assert!(app.messages.iter().any(|line| line == "Bot 2 is out of chips - knocked out by Bot 1."), "{:?}", app.messages);
+    }
+
+    #[test]
+    fn hand_result_line_follows_the_sign_of_the_profit() {
+        let mut app = test_app();
+        app.push_hand_result(40, 115);
+        app.push_hand_result(-25, 90);
+        app.push_hand_result(0, 90);
+        assert_eq!(&app.messages[app.messages.len() - 3..], [
+            "You won this hand! Your profit: $40. Total: $115",
+            "You lost this hand. Your loss: $25. Total: $90",
+            "You broke even this hand. Total: $90",
+        ]);
+    }
+
+    #[test]
+    fn split_pot_lists_each_winner_with_their_share() {
+        let mut app = split_pot_app();
+        app.messages.clear();
+        app.announce_side_pots();
+        assert!(app.messages.iter().any(|line| line.starts_with("Pot ($33, 3 players): split between Bot 1 ($17) and You ($16) with ")),
+                "{:?}", app.messages);
+    }
+
+    #[test]
+    fn split_pot_share_lands_in_the_ledger() {
+        let mut app = split_pot_app();
+        app.game.debug_log.clear();
+        assert_eq!(app.hand_profit(), 16 - 11);
+        let hand = app.game.hand_number;
+        let shares: Vec<i64> = app.session_stats.ledger.iter()
+            .filter(|e| e.hand == hand && e.kind == LedgerKind::PotShare)
+            .map(|e| e.amount)
+            .collect();
+        assert_eq!(shares, vec![16]);
+        assert!(!app.game.debug_log.iter().any(|line| line.contains("ledger")), "{:?}", app.game.debug_log);
+    }
+
+    #[test]
+    fn tied_human_is_not_told_what_beat_them() {
+        let mut app = split_pot_app();
+        app.messages.clear();
+        app.explain_showdown_loss(1);
+        assert!(app.messages.is_empty(), "{:?}", app.messages);
+    }
+
+    // A bot seat to act in a freshly dealt hand, and a pending API request for it that the test controls
+    fn waiting_on_api() -> (App, Player, std::sync::mpsc::Sender<game::ApiDecision>) {
+        let mut app = test_app();
+        app.game.deal_cards();
+        app.game.current_player_idx = 1;
+        let bot = app.game.players[1].clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        app.pending_bot_action = Some(PendingBotAction::new(1, app.game.hand_number, receiver));
+        (app, bot, sender)
+    }
+
+    #[test]
+    fn without_an_api_key_the_bot_decides_on_the_spot() {
+        let mut app = test_app();
+        app.game.deal_cards();
+        app.game.current_player_idx = 1;
+        let bot = app.game.players[1].clone();
+        assert!(app.poll_bot_decision(&bot).is_some());
+        assert!(app.pending_bot_action.is_none());
+    }
+
+    #[test]
+    fn api_decision_is_polled_until_it_arrives() {
+        let (mut app, bot, sender) = waiting_on_api();
+        assert!(app.poll_bot_decision(&bot).is_none());
+        assert!(app.pending_bot_action.is_some(), "still waiting");
+
+        let stats = game::ApiStats { calls: 1, ..Default::default() };
+        sender.send(game::ApiDecision {
+            action: Ok(GameAction::Fold),
+            api_stats: Some(stats),
+            debug_log: vec!["AI avg: 900ms, last: 900ms".to_string()],
+            reason: Some("weak hand".to_string()),
+        }).unwrap();
+        assert!(matches!(app.poll_bot_decision(&bot), Some(GameAction::Fold)));
+        assert!(app.pending_bot_action.is_none());
+        assert_eq!(app.game.api_stats.calls, 1);
+        assert_eq!(app.game.last_bot_reason.as_deref(), Some("weak hand"));
+    }
+
+    #[test]
+    fn stopping_mid_decision_drops_the_pending_api_request() {
+        let (mut app, _bot, sender) = waiting_on_api();
+        app.game_active = true;
+        app.stop_hand();
+        assert!(app.pending_bot_action.is_none());
+        assert!(sender.send(game::ApiDecision {
+            action: Ok(GameAction::Raise(100)),
+            api_stats: None,
+            debug_log: Vec::new(),
+            reason: None,
+        }).is_err(), "nobody is listening for the reply any more");
+
+        // The next hand's bot decides for itself rather than picking up the stale request
+        app.game.deal_cards();
+        app.game.current_player_idx = 1;
+        let bot = app.game.players[1].clone();
+        assert!(app.poll_bot_decision(&bot).is_some());
+        assert!(app.pending_bot_action.is_none());
+    }
+
+    #[test]
+    fn slow_api_decision_falls_back_to_the_built_in_bot() {
+        let (mut app, bot, _sender) = waiting_on_api();
+        let pending = app.pending_bot_action.as_mut().unwrap();
+        pending.started_at = Instant::now() - API_DECISION_TIMEOUT;
+        assert!(app.poll_bot_decision(&bot).is_some());
+        assert!(app.pending_bot_action.is_none());
+        assert_eq!(app.game.api_stats.failures, 1);
+        assert!(app.game.debug_log.iter().any(|line| line.contains("no reply from the API")));
+    }
+
+    #[test]
+    fn failed_api_decision_falls_back_to_the_built_in_bot() {
+        let (mut app, bot, sender) = waiting_on_api();
+        drop(sender);
+        assert!(app.poll_bot_decision(&bot).is_some());
+        assert_eq!(app.game.api_stats.failures, 1);
+    }
+
+    // Bot 1 takes the pot uncontested with seven-deuce; `started_with` is its stack at the deal
+    fn uncontested_bot_win(started_with: u32) -> App {
+        use crate::game::Rank;
+        let mut app = test_app();
+        app.game.deal_cards();
+        for (idx, player) in app.game.players.iter_mut().enumerate() {
+            player.folded = idx != 1;
+        }
+        app.game.players[1].hand = vec![card(Rank::Seven, Suit::Clubs), card(Rank::Two, Suit::Diamonds)];
+        app.leaderboard = vec![(1, started_with)];
+        app
+    }
+
+    // Settle the same hand over and over (and then new hands), collecting every line said
+    fn talk_lines(app: &mut App, settlements: usize, new_hands: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        for _ in 0..settlements {
+            if new_hands {
+                app.game.hand_number += 1;
+            }
+            app.queue_table_talk(1);
+            lines.extend(app.table_talk.take());
+        }
+        lines
+    }
+
+    #[test]
+    fn table_talk_is_one_chance_per_hand() {
+        let mut app = uncontested_bot_win(0);
+        assert!(talk_lines(&mut app, 200, false).len() <= 1);
+        assert!(!talk_lines(&mut app, 200, true).is_empty(), "bots still talk on later hands");
+    }
+
+    #[test]
+    fn table_talk_off_means_no_lines() {
+        let mut app = uncontested_bot_win(0);
+        app.config.table_talk = false;
+        assert!(talk_lines(&mut app, 200, true).is_empty());
+    }
+
+    #[test]
+    fn bluff_talk_shows_the_cards_it_brags_about() {
+        let stack = test_app().game.players[1].chips;
+        let mut app = uncontested_bot_win(stack);
+        let shown = format!("Bot 1 shows {} {}: ", app.game.players[1].hand[0], app.game.players[1].hand[1]);
+        let lines = talk_lines(&mut app, 200, true);
+        assert!(!lines.is_empty());
+        for line in lines {
+            assert!(line.starts_with(&shown), "{}", line);
+        }
+    }
+
+    #[test]
+    fn bad_beat_talk_needs_the_losing_hand_shown() {
+        use crate::game::Rank;
+        let mut app = test_app();
+        app.game.deal_cards();
+        app.showdown_style = ShowdownStyle::Fast;
+        app.game.community_cards = vec![
+            card(Rank::Two, Suit::Hearts), card(Rank::Three, Suit::Hearts), card(Rank::Four, Suit::Hearts),
+            card(Rank::Nine, Suit::Spades), card(Rank::King, Suit::Clubs),
+        ];
+        for (idx, player) in app.game.players.iter_mut().enumerate() {
+            player.folded = idx > 3;
+        }
+        // Bot 1 wins with the flush; Bot 2's straight is the hand shown against it, and Bot 3's
+        // smaller straight is never seen
+        app.game.players[1].hand = vec![card(Rank::Ace, Suit::Hearts), card(Rank::Ten, Suit::Hearts)];
+        app.game.players[2].hand = vec![card(Rank::Five, Suit::Clubs), card(Rank::Six, Suit::Diamonds)];
+        app.game.players[3].hand = vec![card(Rank::Ace, Suit::Clubs), card(Rank::Five, Suit::Diamonds)];
+        app.game.players[0].hand = vec![card(Rank::Queen, Suit::Clubs), card(Rank::Jack, Suit::Diamonds)];
+        app.leaderboard = vec![(1, app.game.players[1].chips)];
+        assert_eq!(app.revealed_seats(1), vec![1, 2]);
+
+        for _ in 0..200 {
+            app.game.hand_number += 1;
+            app.queue_table_talk(1);
+            if let Some(line) = app.table_talk.take() {
+                assert!(!line.starts_with("Bot 3"), "{}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn table_changes_go_into_the_autosave_and_exit_summary() {
+        let mut app = test_app();
+        app.record_table_change("Bot 3 is now Hard".to_string());
+        let snapshot = SessionSnapshot::capture(&app.game, app.player_starting_chips, &app.game_stats, &app.hand_results, &app.table_changes,
+                                                &app.session_stats);
+        assert_eq!(snapshot.table_changes, vec!["Before hand 1: Bot 3 is now Hard".to_string()]);
+
+        let mut restored = test_app();
+        restored.restore_session(&snapshot);
+        assert!(restored.exit_summary().iter().any(|line| line.contains("Before hand 1: Bot 3 is now Hard")));
+    }
+
+    #[test]
+    fn startup_summary_names_every_key_setting() {
+        let config = Config {
+            fresh_table: true,
+            seed: Some(42),
+            pot_limit: true,
+            short_deck: true,
+            max_raises_per_street: Some(4),
+            button_ante: true,
+            allow_straddle: true,
+            rake_percent: 5,
+            rake_cap: Some(30),
+            ..Config::default()
+        };
+        let app = App::new(None, "Tester".to_string(), config.clone());
+        let summary = startup_summary(&config, &app.game, app.seed).join("\n");
+        for expected in ["seed 42", BuildInfo::current().short().as_str(), "you + 8 bots", "$1000 stacks", "blinds $5/$10",
+                         "Pot-limit", "short deck", "4 raises per street", "button ante", "straddles", "5% rake, cap $30",
+                         "Bots: built-in heuristics", "Config:"] {
+            assert!(summary.contains(expected), "{:?} missing from:\n{}", expected, summary);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_button() {
+        let seeded = || App::new(None, "Tester".to_string(), Config { fresh_table: true, seed: Some(7), ..Config::default() });
+        let buttons: Vec<usize> = (0..3).map(|_| seeded().game.dealer_idx).collect();
+        assert!(buttons.iter().all(|&b| b == buttons[0]), "{:?}", buttons);
+    }
+
+    // A dealt hand where every bot but Bot 1 has folded and it's the human's turn
+    fn human_against_bot_1() -> App {
+        let mut app = test_app();
+        app.game.deal_cards();
+        app.game_active = true;
+        app.player_starting_chips = app.game.hand_start_chips[0];
+        for idx in 2..app.game.players.len() {
+            app.game.players[idx].folded = true;
+        }
+        app.game.current_player_idx = 0;
+        app
+    }
+
+    #[test]
+    fn hand_ended_by_the_humans_fold_is_settled_once() {
+        let mut app = human_against_bot_1();
+        let chips = app.game.players.iter().map(|p| p.chips).sum::<u32>() + app.game.pot;
+        app.handle_player_action(GameAction::Fold);
+        assert!(!app.game_active);
+        assert_eq!(app.game_stats.len(), 1, "the hand counts in the session stats");
+        assert_eq!(app.hand_results, vec![false]);
+        assert_eq!(app.round_results.as_ref().map(|r| r.0.as_str()), Some("Bot 1"));
+        assert_eq!(app.game.players.iter().map(|p| p.chips).sum::<u32>(), chips);
+        assert!(app.messages.iter().any(|line| line.starts_with("You lost this hand.")), "{:?}", app.messages);
+    }
+
+    #[test]
+    fn hand_ended_by_a_bots_fold_is_settled_once() {
+        let mut app = human_against_bot_1();
+        app.game.current_player_idx = 1;
+        let bot = app.game.players[1].clone();
+        app.process_bot_action(GameAction::Fold, bot);
+        assert!(!app.game_active);
+        assert_eq!(app.game_stats.len(), 1);
+        assert_eq!(app.hand_results, vec![true]);
+        assert!(app.messages.iter().any(|line| line.starts_with("You won this hand!")), "{:?}", app.messages);
+    }
+
+    #[test]
+    fn stopped_hand_is_settled_once() {
+        let mut app = human_against_bot_1();
+        app.stop_hand();
+        assert!(!app.game_active);
+        assert_eq!(app.game_stats.len(), 1);
+        assert_eq!(app.hand_results, vec![false]);
+    }
+
+    #[test]
+    fn starting_chips_only_change_between_hands() {
+        let mut app = human_against_bot_1();
+        let before = app.next_session_chips;
+        app.input = "500".to_string();
+        app.on_action_key(KeyAction::SetChips, true);
+        assert_eq!(app.next_session_chips, before);
+        assert_eq!(app.input, "500", "the typed raise amount is kept");
+        assert_eq!(app.messages.last().map(String::as_str), Some("Starting chips are set between hands."));
+
+        app.game_active = false;
+        app.on_action_key(KeyAction::SetChips, true);
+        assert_eq!(app.next_session_chips, 500);
+        assert!(app.input.is_empty());
+    }
+
+    // The human's turn began `turn_secs` ago and a menu has just been opened
+    fn menu_open_on_turn(turn_secs: u64) -> App {
+        let mut app = human_against_bot_1();
+        app.config.modal_timeout_secs = Some(5);
+        app.game.last_action_at = Instant::now() - Duration::from_secs(turn_secs);
+        app.input_mode = InputMode::PlayerName;
+        app
+    }
+
+    fn human_actions(app: &App) -> usize {
+        app.game.action_history.iter().filter(|a| a.player_idx == 0).count()
+    }
+
+    #[test]
+    fn menu_past_the_turn_timeout_plays_exactly_one_action() {
+        let mut app = menu_open_on_turn(6);
+        app.check_modal_timeout();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(human_actions(&app), 1);
+
+        // Reopening the menu afterwards doesn't act again
+        app.input_mode = InputMode::PlayerName;
+        app.game.current_player_idx = 0;
+        app.check_modal_timeout();
+        app.check_modal_timeout();
+        assert_eq!(human_actions(&app), 1);
+    }
+
+    #[test]
+    fn menu_timeout_counts_from_the_start_of_the_turn() {
+        // The menu was opened a moment ago, but the turn started 6s ago
+        let mut app = menu_open_on_turn(6);
+        app.modal_opened_at = Some(Instant::now());
+        app.check_modal_timeout();
+        assert_eq!(human_actions(&app), 1);
+
+        let mut app = menu_open_on_turn(2);
+        app.check_modal_timeout();
+        assert_eq!(app.input_mode, InputMode::PlayerName);
+        assert_eq!(human_actions(&app), 0);
+    }
+
+    #[test]
+    fn broken_key_map_falls_back_to_the_default_keys() {
+        let path = std::env::temp_dir().join(format!("p_kr_app_keys_{}.json", std::process::id()));
+        std::fs::write(&path, "not json").unwrap();
+        let app = App::new(None, "Tester".to_string(), Config { fresh_table: true, keys: Some(path.clone()), ..Config::default() });
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(app.keys.key(KeyAction::Call), KeyAction::Call.default_key());
+        assert!(app.messages.iter().any(|line| line.starts_with("Key map not used (") && line.ends_with("Using the default keys.")),
+                "{:?}", app.messages);
+    }
+
+    fn limit_prompts(app: &App) -> usize {
+        app.messages.iter().filter(|line| line.starts_with("You've hit your")).count()
+    }
+
+    // Settle one more hand with the given result for the human and run the end-of-hand check
+    fn settle_with(app: &mut App, profit: i32) {
+        app.game_stats.push(profit);
+        app.check_session_limits();
+    }
+
+    #[test]
+    fn stop_loss_prompt_appears_exactly_once() {
+        let mut app = test_app();
+        app.config.stop_loss = Some(config::SessionLimit::BigBlinds(10));
+        settle_with(&mut app, -60);
+        assert_eq!(limit_prompts(&app), 0, "down $60 of a $100 stop-loss");
+
+        settle_with(&mut app, -50);
+        assert_eq!(app.input_mode, InputMode::SessionLimit);
+        assert_eq!(limit_prompts(&app), 1);
+        assert!(app.messages.iter().any(|line| line.starts_with("You've hit your stop-loss (-$110).")), "{:?}", app.messages);
+
+        // Playing on and losing more doesn't ask again
+        app.input_mode = InputMode::Normal;
+        settle_with(&mut app, -40);
+        settle_with(&mut app, -40);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(limit_prompts(&app), 1);
+    }
+
+    #[test]
+    fn stop_win_prompt_appears_exactly_once() {
+        let mut app = test_app();
+        app.config.stop_win = Some(config::SessionLimit::Chips(200));
+        settle_with(&mut app, 250);
+        assert_eq!(limit_prompts(&app), 1);
+        assert!(app.messages.iter().any(|line| line.starts_with("You've hit your stop-win (+$250).")), "{:?}", app.messages);
+
+        app.input_mode = InputMode::Normal;
+        settle_with(&mut app, -100);
+        settle_with(&mut app, 100);
+        assert_eq!(limit_prompts(&app), 1);
+    }
+
+    #[test]
+    fn stop_loss_waits_for_the_hand_to_end() {
+        let mut app = test_app();
+        app.config.stop_loss = Some(config::SessionLimit::Chips(50));
+        app.game_active = true;
+        settle_with(&mut app, -100);
+        assert_eq!(limit_prompts(&app), 0);
+        app.game_active = false;
+        app.check_session_limits();
+        assert_eq!(limit_prompts(&app), 1);
+    }
+
+    fn type_keys(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.on_key(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn amount_typed_on_the_humans_turn_is_kept_while_it_lasts() {
+        let mut app = human_against_bot_1();
+        type_keys(&mut app, "0050");
+        assert_eq!(app.input, "50", "leading zeros are dropped");
+        app.clear_stale_input();
+        assert_eq!(app.input, "50");
+    }
+
+    #[test]
+    fn amount_is_cleared_once_the_turn_passes() {
+        let mut app = human_against_bot_1();
+        type_keys(&mut app, "50");
+        app.game.current_player_idx = 1;
+        app.clear_stale_input();
+        assert!(app.input.is_empty());
+        assert_eq!(app.input_cursor, 0);
+    }
+
+    #[test]
+    fn amount_is_cleared_when_the_street_or_hand_moves_on() {
+        let mut app = human_against_bot_1();
+        type_keys(&mut app, "50");
+        app.game.round = Round::Flop;
+        app.clear_stale_input();
+        assert!(app.input.is_empty());
+
+        let mut app = human_against_bot_1();
+        type_keys(&mut app, "50");
+        app.game.hand_number += 1;
+        app.clear_stale_input();
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn starting_stack_typed_between_hands_keeps_until_the_deal() {
+        let mut app = test_app();
+        type_keys(&mut app, "2000");
+        app.clear_stale_input();
+        assert_eq!(app.input, "2000");
+
+        app.game.deal_cards();
+        app.game_active = true;
+        app.clear_stale_input();
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn transients_expire_after_their_lifetime() {
+        let mut app = test_app();
+        app.transients.push(("+$10 from B1".to_string(), Instant::now() - TRANSIENT_LIFETIME));
+        app.transients.push(("+$20 from B2".to_string(), Instant::now()));
+        app.expire_transients();
+        let left: Vec<&str> = app.transients.iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(left, vec!["+$20 from B2"]);
+    }
+
+    #[test]
+    fn transients_never_outlive_the_hand() {
+        let mut app = human_against_bot_1();
+        app.game.current_player_idx = 1;
+        let bot = app.game.players[1].clone();
+        app.process_bot_action(GameAction::Raise(40), bot);
+        app.narrate_game_events();
+        assert!(app.transients.iter().any(|(text, _)| text == "+$40 from B1"), "{:?}", app.transients);
+
+        // Still fresh, but the next deal starts with none
+        app.game_active = false;
+        app.deal_new_hand();
+        assert!(app.transients.is_empty(), "{:?}", app.transients);
+    }
+
+    #[test]
+    fn fast_showdowns_show_no_transients() {
+        let mut app = human_against_bot_1();
+        app.showdown_style = ShowdownStyle::Fast;
+        app.handle_player_action(GameAction::Fold);
+        app.narrate_game_events();
+        assert!(app.transients.is_empty(), "{:?}", app.transients);
+    }
+
+    fn turn_prompts(app: &App) -> usize {
+        app.messages.iter().filter(|line| line.starts_with("Your turn now")).count()
+    }
+
+    #[test]
+    fn one_turn_prompt_per_human_decision() {
+        let mut app = test_app();
+        app.showdown_style = ShowdownStyle::Fast;
+        app.game.dealer_idx = 5;
+        app.deal_new_hand();
+        let mut decisions = 0;
+        for _ in 0..200 {
+            if !app.game_active {
+                break;
+            }
+            app.narrate_game_events();
+            let action = if app.game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+            if app.game.players[app.game.current_player_idx].is_bot {
+                let bot = app.game.players[app.game.current_player_idx].clone();
+                app.process_bot_action(action, bot);
             } else {
-                self.messages.push("Your turn now. Options: [k]heck, [f]old, or [r]aise.".to_string());
+                // Redrawing or polling again never repeats the prompt
+                app.narrate_game_events();
+                assert_eq!(turn_prompts(&app), decisions + 1, "{:?}", app.messages);
+                app.handle_player_action(action);
+                decisions += 1;
             }
         }
+        app.narrate_game_events();
+        assert!(!app.game_active);
+        assert_eq!(decisions, 4, "one decision on each street");
+        assert_eq!(turn_prompts(&app), decisions);
+    }
+
+    fn chips_in_play(app: &App) -> u32 {
+        app.game.players.iter().map(|p| p.chips).sum::<u32>() + app.game.pot
+    }
+
+    #[test]
+    fn stop_keeps_every_chip_at_the_table() {
+        // Full table, mid-preflop, a bot to act: the human is folded out of turn
+        let mut app = test_app();
+        app.game.deal_cards();
+        app.game_active = true;
+        app.player_starting_chips = app.game.hand_start_chips[0];
+        let total = chips_in_play(&app);
+        app.game.current_player_idx = 3;
+        let bot = app.game.players[3].clone();
+        app.process_bot_action(GameAction::Raise(40), bot);
+
+        app.stop_hand();
+        assert!(!app.game_active);
+        assert!(app.game.players[0].folded);
+        assert_eq!(app.game.pot, 0, "the pot was paid out");
+        assert_eq!(chips_in_play(&app), total);
+        assert_eq!(app.hand_results, vec![false], "recorded as a fold for the human");
+    }
+
+    #[test]
+    fn stop_on_the_humans_turn_keeps_every_chip_at_the_table() {
+        let mut app = human_against_bot_1();
+        let total = chips_in_play(&app);
+        app.stop_hand();
+        assert_eq!(app.game.pot, 0);
+        assert_eq!(chips_in_play(&app), total);
+        assert!(app.game.action_history.iter().any(|a| a.player_idx == 0 && matches!(a.action, GameAction::Fold)));
+    }
+
+    #[test]
+    fn uncalled_bet_returned_to_the_human_is_logged_and_ledgered() {
+        // You bet 80, Bot 1 calls all-in for 30 and Bot 2 folded its blind
+        let mut app = human_against_bot_1();
+        let hand = app.game.hand_number;
+        app.game.hand_contributions = vec![0; app.game.players.len()];
+        app.game.hand_contributions[..3].copy_from_slice(&[80, 30, 10]);
+        app.game.pot = 120;
+        app.game.players[1].chips = 0;
+        app.game.round = Round::Showdown;
+        app.messages.clear();
+        app.settle_hand(None);
+        assert!(app.messages.iter().any(|line| line == "Uncalled bet of $50 returned to You."), "{:?}", app.messages);
+        let refunds: Vec<i64> = app.session_stats.ledger.iter()
+            .filter(|e| e.hand == hand && e.kind == LedgerKind::Refund)
+            .map(|e| e.amount)
+            .collect();
+        assert_eq!(refunds, vec![50]);
+    }
+
+    #[test]
+    fn uncalled_bet_returned_to_a_bot_is_logged() {
+        let mut app = human_against_bot_1();
+        app.game.hand_contributions = vec![0; app.game.players.len()];
+        app.game.hand_contributions[..2].copy_from_slice(&[30, 80]);
+        app.game.pot = 110;
+        app.game.players[0].chips = 0;
+        app.game.round = Round::Showdown;
+        app.messages.clear();
+        app.settle_hand(None);
+        assert!(app.messages.iter().any(|line| line == "Uncalled bet of $50 returned to Bot 1."), "{:?}", app.messages);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,262 @@
+// Deal fairness audit: counts which cards reach each seat and the board over many hands,
+// then checks the counts against what a uniform shuffle would give
+
+use crate::game::{Card, Game, Suit};
+use crate::simulator::Simulator;
+use crate::strategy::{CallingStation, Strategy};
+
+// One-sided z-score for the chi-squared limit: a fair shuffle fails a given check
+// about once in a thousand runs
+const AUDIT_Z: f64 = 3.09;
+
+// Full deck only (the simulator never plays short deck)
+const DECK_SIZE: usize = 52;
+
+pub const AUDIT_SEATS: usize = 9;
+
+pub struct DealAudit {
+    pub hands: u64,
+    pub repeats: u64,             // Hands where a card turned up twice (hole cards and board together)
+    hole: Vec<[u64; DECK_SIZE]>,  // Per seat: times each card was dealt to it
+    board: [u64; DECK_SIZE],      // Times each card came on the board
+}
+
+// A check's verdict with the card furthest from its expected count
+pub struct UniformityCheck {
+    pub chi_squared: f64,
+    pub limit: f64,
+    pub worst_card: Card,
+    pub worst_count: u64,
+    pub expected: f64,
+}
+
+impl UniformityCheck {
+    pub fn passed(&self) -> bool {
+        self.chi_squared <= self.limit
+    }
+
+    // "chi-squared 47.3 (limit 86.7) PASS - most off: [A♠] 4102 vs 3846 expected (+6.7%)"
+    pub fn describe(&self) -> String {
+        let deviation = if self.expected > 0.0 { (self.worst_count as f64 / self.expected - 1.0) * 100.0 } else { 0.0 };
+        format!("chi-squared {:.1} (limit {:.1}) {} - most off: {} {} vs {:.0} expected ({:+.1}%)",
+                self.chi_squared, self.limit, if self.passed() { "PASS" } else { "FAIL" },
+                self.worst_card, self.worst_count, self.expected, deviation)
+    }
+}
+
+fn card_index(card: &Card) -> usize {
+    let suit = match card.suit {
+        Suit::Hearts => 0,
+        Suit::Diamonds => 1,
+        Suit::Clubs => 2,
+        Suit::Spades => 3,
+    };
+    // Same order as Game::create_deck: suit by suit, Two to Ace
+    suit * 13 + (card.rank.value() as usize - 2)
+}
+
+// Chi-squared limit for `df` degrees of freedom at AUDIT_Z (Wilson-Hilferty approximation)
+fn chi_squared_limit(df: f64) -> f64 {
+    let scale = 2.0 / (9.0 * df);
+    df * (1.0 - scale + AUDIT_Z * scale.sqrt()).powi(3)
+}
+
+// Compare per-card counts with an even spread over the deck
+pub fn uniformity(counts: &[u64; DECK_SIZE]) -> UniformityCheck {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / DECK_SIZE as f64;
+    let chi_squared = if expected > 0.0 {
+        counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum()
+    } else {
+        0.0
+    };
+    let (worst, &worst_count) = counts.iter().enumerate()
+        .max_by(|a, b| (*a.1 as f64 - expected).abs().total_cmp(&(*b.1 as f64 - expected).abs()))
+        .unwrap_or((0, &0));
+    UniformityCheck {
+        chi_squared,
+        limit: chi_squared_limit((DECK_SIZE - 1) as f64),
+        worst_card: Game::create_deck()[worst].clone(),
+        worst_count,
+        expected,
+    }
+}
+
+impl DealAudit {
+    pub fn new(seats: usize) -> Self {
+        DealAudit { hands: 0, repeats: 0, hole: vec![[0; DECK_SIZE]; seats], board: [0; DECK_SIZE] }
+    }
+
+    // Count the cards of a hand that has just been played (hole cards stay with folded players)
+    pub fn record(&mut self, game: &Game) {
+        let mut seen = [false; DECK_SIZE];
+        let mut repeated = false;
+        for (seat, player) in game.players.iter().enumerate().take(self.hole.len()) {
+            for card in &player.hand {
+                let idx = card_index(card);
+                self.hole[seat][idx] += 1;
+                repeated |= std::mem::replace(&mut seen[idx], true);
+            }
+        }
+        for card in &game.community_cards {
+            let idx = card_index(card);
+            self.board[idx] += 1;
+            repeated |= std::mem::replace(&mut seen[idx], true);
+        }
+        self.hands += 1;
+        if repeated {
+            self.repeats += 1;
+        }
+    }
+
+    // The report lines, and whether every check passed
+    pub fn report(&self) -> (Vec<String>, bool) {
+        let mut lines = Vec::new();
+        let mut all_passed = true;
+
+        let mut all_hole = [0; DECK_SIZE];
+        for seat in &self.hole {
+            for (total, count) in all_hole.iter_mut().zip(seat) {
+                *total += count;
+            }
+        }
+        let mut checks = vec![("Hole cards, all seats".to_string(), uniformity(&all_hole))];
+        for (seat, counts) in self.hole.iter().enumerate() {
+            checks.push((format!("Seat {} hole cards", seat + 1), uniformity(counts)));
+        }
+        checks.push(("Board cards".to_string(), uniformity(&self.board)));
+
+        for (name, check) in checks {
+            all_passed &= check.passed();
+            lines.push(format!("{}: {}", name, check.describe()));
+        }
+
+        let repeats_ok = self.repeats == 0;
+        all_passed &= repeats_ok;
+        lines.push(format!("Hands with a repeated card: {} {}", self.repeats, if repeats_ok { "PASS" } else { "FAIL" }));
+        (lines, all_passed)
+    }
+}
+
+// Play `hands` hands at a table of calling stations and count every card dealt. Calling
+// stations see every hand to the river, so the whole board is dealt each time.
+pub fn audit_deals(hands: u64, seed: u64) -> DealAudit {
+    let strategies: Vec<Box<dyn Strategy>> = (0..AUDIT_SEATS).map(|_| Box::new(CallingStation) as Box<dyn Strategy>).collect();
+    let mut simulator = Simulator::new(strategies, 1000, 5, 10, seed);
+    let mut audit = DealAudit::new(AUDIT_SEATS);
+    for _ in 0..hands {
+        simulator.play_hand();
+        audit.record(&simulator.game);
+    }
+    audit
+}
+
+// The `simulate --audit-deals N [--seed S]` subcommand, if it was asked for: the hand count and seed
+pub fn simulate_command(args: &[String]) -> Option<Result<(u64, Option<u64>), String>> {
+    if args.first().map(String::as_str) != Some("simulate") {
+        return None;
+    }
+    let mut hands = None;
+    let mut seed = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--audit-deals" => match rest.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) if n > 0 => hands = Some(n),
+                _ => return Some(Err("--audit-deals needs a number of hands".to_string())),
+            },
+            "--seed" => match rest.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) => seed = Some(n),
+                None => return Some(Err("--seed needs a number".to_string())),
+            },
+            other => return Some(Err(format!("Unknown simulate option: {}", other))),
+        }
+    }
+    Some(hands.map(|hands| (hands, seed)).ok_or_else(|| "simulate needs --audit-deals N".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Rank;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn even_counts_pass_with_zero_chi_squared() {
+        let check = uniformity(&[1000; DECK_SIZE]);
+        assert_eq!(check.chi_squared, 0.0);
+        assert_eq!(check.expected, 1000.0);
+        assert!(check.passed());
+    }
+
+    #[test]
+    fn small_noise_passes() {
+        // Alternating +-20 around 1000: chi-squared 52 * 400 / 1000 = 20.8, well under the ~87 limit
+        let mut counts = [0; DECK_SIZE];
+        for (i, count) in counts.iter_mut().enumerate() {
+            *count = if i.is_multiple_of(2) { 1020 } else { 980 };
+        }
+        let check = uniformity(&counts);
+        assert!((check.chi_squared - 20.8).abs() < 1e-9);
+        assert!(check.limit > 80.0 && check.limit < 95.0, "limit {}", check.limit);
+        assert!(check.passed());
+    }
+
+    #[test]
+    fn one_heavy_card_fails_and_is_named() {
+        let mut counts = [1000; DECK_SIZE];
+        let ace_of_spades = card_index(&Card { rank: Rank::Ace, suit: Suit::Spades });
+        counts[ace_of_spades] = 1500;
+        let check = uniformity(&counts);
+        assert!(!check.passed(), "chi-squared {}", check.chi_squared);
+        assert_eq!(check.worst_card, Card { rank: Rank::Ace, suit: Suit::Spades });
+        assert_eq!(check.worst_count, 1500);
+        assert!(check.describe().contains("FAIL"));
+    }
+
+    #[test]
+    fn card_index_matches_the_deck_order() {
+        for (i, card) in Game::create_deck().iter().enumerate() {
+            assert_eq!(card_index(card), i);
+        }
+    }
+
+    #[test]
+    fn repeated_card_in_a_hand_is_caught() {
+        let mut game = Game::headless(2, 1000, 5, 10);
+        let ace = Card { rank: Rank::Ace, suit: Suit::Spades };
+        game.players[0].hand = vec![ace.clone(), Card { rank: Rank::King, suit: Suit::Spades }];
+        game.players[1].hand = vec![Card { rank: Rank::Two, suit: Suit::Clubs }, Card { rank: Rank::Three, suit: Suit::Clubs }];
+        let mut audit = DealAudit::new(2);
+        audit.record(&game);
+        assert_eq!(audit.repeats, 0);
+
+        game.community_cards = vec![ace];
+        audit.record(&game);
+        assert_eq!((audit.hands, audit.repeats), (2, 1));
+        let (lines, passed) = audit.report();
+        assert!(!passed);
+        assert!(lines.last().unwrap().ends_with("1 FAIL"));
+    }
+
+    #[test]
+    fn seeded_audit_passes() {
+        let (lines, passed) = audit_deals(2000, 11).report();
+        assert!(passed, "{:#?}", lines);
+        // All seats, each seat, the board and the repeat check
+        assert_eq!(lines.len(), AUDIT_SEATS + 3);
+    }
+
+    #[test]
+    fn simulate_command_parsing() {
+        assert!(simulate_command(&args(&["--seed", "3"])).is_none());
+        assert_eq!(simulate_command(&args(&["simulate", "--audit-deals", "500"])), Some(Ok((500, None))));
+        assert_eq!(simulate_command(&args(&["simulate", "--seed", "9", "--audit-deals", "10"])), Some(Ok((10, Some(9)))));
+        assert!(matches!(simulate_command(&args(&["simulate"])), Some(Err(_))));
+        assert!(matches!(simulate_command(&args(&["simulate", "--audit-deals", "lots"])), Some(Err(_))));
+        assert!(matches!(simulate_command(&args(&["simulate", "--audit-deals", "5", "--fast"])), Some(Err(_))));
+    }
+}
@@ -0,0 +1,211 @@
+// Trainer hints for the human's hand (--coach): outs to improve on the next card.
+
+use rs_poker::core::{Hand, Rank as PokerRank, Rankable};
+
+use crate::game::{Card, Game, Rank, Suit};
+
+// What the next card can do for a hand on the flop or turn
+#[derive(Clone, Debug, Default)]
+pub struct OutsReport {
+    pub outs: Vec<Card>,          // Unseen cards that improve the hand
+    pub draws: Vec<&'static str>, // "Flush draw", "Open-ended straight draw", "Gutshot", "Two Pair", ...
+    groups: Vec<String>,          // The outs in short: "any ♦", "any Q", "[9♣]"
+}
+
+impl OutsReport {
+    // "Flush draw + gutshot: 12 outs - any ♦, any Q", or None without outs
+    pub fn describe(&self) -> Option<String> {
+        if self.outs.is_empty() {
+            return None;
+        }
+        let mut draws = self.draws.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        for draw in draws.iter_mut().skip(1) {
+            *draw = draw.to_lowercase();
+        }
+        Some(format!("{}: {} out{} - {}", draws.join(" + "), self.outs.len(),
+                     if self.outs.len() == 1 { "" } else { "s" }, self.groups.join(", ")))
+    }
+}
+
+// Hand categories, worst to best
+fn category(rank: &PokerRank) -> u8 {
+    match rank {
+        PokerRank::HighCard(_) => 0,
+        PokerRank::OnePair(_) => 1,
+        PokerRank::TwoPair(_) => 2,
+        PokerRank::ThreeOfAKind(_) => 3,
+        PokerRank::Straight(_) => 4,
+        PokerRank::Flush(_) => 5,
+        PokerRank::FullHouse(_) => 6,
+        PokerRank::FourOfAKind(_) => 7,
+        PokerRank::StraightFlush(_) => 8,
+    }
+}
+
+fn rank_of(cards: &[&Card]) -> PokerRank {
+    Hand::new_with_cards(cards.iter().map(|&c| Game::to_poker_card(c)).collect()).rank()
+}
+
+// Outs on the flop or turn (standard deck): every unseen card that lifts the hand to a better
+// category, using the hole cards. Pairing an unpaired hand isn't counted, and nor is a card
+// that improves the board as much as the hand (a fourth flush card with no hole card of it,
+// or a card pairing the board for a second pair).
+pub fn count_outs(hole: &[Card], board: &[Card]) -> OutsReport {
+    let mut report = OutsReport::default();
+    if hole.len() != 2 || !(3..=4).contains(&board.len()) {
+        return report;
+    }
+
+    let known: Vec<&Card> = hole.iter().chain(board.iter()).collect();
+    let unseen: Vec<Card> = Game::create_deck().into_iter().filter(|c| !known.contains(&c)).collect();
+    let current = category(&rank_of(&known));
+
+    let mut made = Vec::new(); // Category each out makes
+    for card in &unseen {
+        let mut with_card = known.clone();
+        with_card.push(card);
+        let improved = category(&rank_of(&with_card));
+        let mut board_only: Vec<&Card> = board.iter().collect();
+        board_only.push(card);
+        let board_category = if board_only.len() >= 5 { category(&rank_of(&board_only)) } else { 0 };
+        // Pairing the board gives everyone that second pair
+        let pairs_board = improved == 2 && board.iter().any(|c| c.rank == card.rank);
+        if improved > current && improved >= 2 && improved > board_category && !pairs_board {
+            report.outs.push(card.clone());
+            made.push(improved);
+        }
+    }
+
+    // Name the draws: straights by how many ranks fill them, flushes and the rest by category
+    let mut straight_ranks: Vec<u8> = report.outs.iter().zip(&made)
+        .filter(|&(_, &made)| made == 4)
+        .map(|(card, _)| card.rank.value())
+        .collect();
+    straight_ranks.sort();
+    straight_ranks.dedup();
+    if made.iter().any(|&m| m == 5 || m == 8) {
+        report.draws.push("Flush draw");
+    }
+    match straight_ranks.len() {
+        0 => {}
+        1 => report.draws.push("Gutshot"),
+        _ => report.draws.push("Open-ended straight draw"),
+    }
+    for (value, name) in [(2, "Two Pair"), (3, "Trips"), (6, "Full House"), (7, "Quads")] {
+        if made.contains(&value) {
+            report.draws.push(name);
+        }
+    }
+
+    report.groups = group_outs(&report.outs, &unseen);
+    report
+}
+
+// Shorten a list of outs: a whole suit or rank of unseen cards becomes "any ♦" / "any Q"
+fn group_outs(outs: &[Card], unseen: &[Card]) -> Vec<String> {
+    let mut groups = Vec::new();
+    let mut covered: Vec<&Card> = Vec::new();
+
+    for suit in [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
+        let of_suit: Vec<&Card> = unseen.iter().filter(|c| c.suit == suit).collect();
+        let suit_outs = outs.iter().filter(|c| c.suit == suit).count();
+        if suit_outs >= 5 && suit_outs == of_suit.len() {
+            groups.push(format!("any {}", suit.symbol()));
+            covered.extend(of_suit);
+        }
+    }
+
+    let mut ranks: Vec<Rank> = outs.iter().map(|c| c.rank.clone()).collect();
+    ranks.sort_by_key(|r| std::cmp::Reverse(r.value()));
+    ranks.dedup();
+    for rank in ranks {
+        let left: Vec<&Card> = outs.iter().filter(|c| c.rank == rank && !covered.contains(c)).collect();
+        if left.is_empty() {
+            continue;
+        }
+        let of_rank = unseen.iter().filter(|c| c.rank == rank && !covered.contains(c)).count();
+        if left.len() == of_rank && of_rank > 1 {
+            groups.push(format!("any {}", rank));
+        } else {
+            groups.extend(left.iter().map(|c| c.to_string()));
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "Ah" -> the ace of hearts
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(|c| {
+            let rank = match &c[..1] {
+                "A" => Rank::Ace, "K" => Rank::King, "Q" => Rank::Queen, "J" => Rank::Jack, "T" => Rank::Ten,
+                "9" => Rank::Nine, "8" => Rank::Eight, "7" => Rank::Seven, "6" => Rank::Six,
+                "5" => Rank::Five, "4" => Rank::Four, "3" => Rank::Three, _ => Rank::Two,
+            };
+            let suit = match &c[1..] {
+                "h" => Suit::Hearts, "d" => Suit::Diamonds, "c" => Suit::Clubs, _ => Suit::Spades,
+            };
+            Card { rank, suit }
+        }).collect()
+    }
+
+    fn outs(hole: &str, board: &str) -> OutsReport {
+        count_outs(&cards(hole), &cards(board))
+    }
+
+    #[test]
+    fn flush_draw_has_nine_outs() {
+        let report = outs("Ah Kh", "2h 7h Qc");
+        assert_eq!(report.outs.len(), 9);
+        assert!(report.outs.iter().all(|c| c.suit == Suit::Hearts));
+        assert_eq!(report.draws, vec!["Flush draw"]);
+        assert!(report.describe().unwrap().starts_with("Flush draw: 9 outs"));
+    }
+
+    #[test]
+    fn open_ended_straight_draw_has_eight_outs() {
+        let report = outs("8s 9d", "6c 7h Ks");
+        assert_eq!(report.outs.len(), 8);
+        assert!(report.outs.iter().all(|c| c.rank == Rank::Five || c.rank == Rank::Ten));
+        assert_eq!(report.draws, vec!["Open-ended straight draw"]);
+    }
+
+    #[test]
+    fn gutshot_has_four_outs() {
+        let report = outs("8s 9d", "5c 7h Ks");
+        assert_eq!(report.outs.len(), 4);
+        assert!(report.outs.iter().all(|c| c.rank == Rank::Six));
+        assert_eq!(report.draws, vec!["Gutshot"]);
+    }
+
+    #[test]
+    fn combo_draw_counts_each_out_once() {
+        // Nine hearts plus the three other fives and three other tens
+        let report = outs("8h 9h", "6h 7h Kc");
+        assert_eq!(report.outs.len(), 15);
+        assert_eq!(report.draws, vec!["Flush draw", "Open-ended straight draw"]);
+    }
+
+    #[test]
+    fn made_straight_has_no_outs() {
+        let report = outs("9s Td", "6c 7h 8d");
+        assert!(report.outs.is_empty(), "{:?}", report.outs);
+        assert!(report.describe().is_none());
+    }
+
+    #[test]
+    fn set_draws_to_a_full_house_or_quads() {
+        let report = outs("7s 7d", "7c Kh 2d");
+        assert_eq!(report.outs.len(), 7);
+        assert_eq!(report.draws, vec!["Full House", "Quads"]);
+    }
+
+    #[test]
+    fn no_outs_before_the_flop_or_on_the_river() {
+        assert!(outs("Ah Kh", "").outs.is_empty());
+        assert!(outs("Ah Kh", "2h 7h Qc 3s 4d").outs.is_empty());
+    }
+}
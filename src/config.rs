@@ -0,0 +1,429 @@
+// Runtime configuration assembled from command-line flags
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::game::{ApiLimiter, Game, PreflopClass, DEFAULT_AI_CONCURRENCY};
+
+// Stacks shallower than this many big blinds leave little room to play anything but push/fold
+pub const SHORT_STACK_BB: u32 = 20;
+
+// How much detail goes into the game log
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,   // Actions and results only
+    Normal,  // Plus street headers, the board, spacing and stats
+    Verbose, // Plus pot updates and engine debug notes
+}
+
+impl Verbosity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Normal => "normal",
+            Verbosity::Verbose => "verbose",
+        }
+    }
+    
+    pub fn next(&self) -> Verbosity {
+        match self {
+            Verbosity::Quiet => Verbosity::Normal,
+            Verbosity::Normal => Verbosity::Verbose,
+            Verbosity::Verbose => Verbosity::Quiet,
+        }
+    }
+}
+
+// A stop-loss or stop-win threshold, in chips or in big blinds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionLimit {
+    Chips(u32),
+    BigBlinds(u32),
+}
+
+impl SessionLimit {
+    pub fn chips(&self, big_blind: u32) -> u32 {
+        match *self {
+            SessionLimit::Chips(chips) => chips,
+            SessionLimit::BigBlinds(bbs) => bbs.saturating_mul(big_blind),
+        }
+    }
+}
+
+/// Startup settings, from the command line or built in code.
+///
+/// ```
+/// use p_kr::config::Config;
+///
+/// let args = ["--blinds", "25/50", "--chips", "5000", "--seed", "7"].map(String::from);
+/// let config = Config::from_args(args.into_iter()).unwrap();
+/// assert_eq!((config.small_blind, config.big_blind, config.starting_chips), (25, 50, 5000));
+/// assert_eq!(config.seed, Some(7));
+/// assert!(config.validate().is_ok());
+///
+/// let broken = Config { small_blind: 20, big_blind: 10, ..Config::default() };
+/// assert!(broken.validate().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub starting_chips: u32,
+    pub max_raises_per_street: Option<u32>, // None = unlimited
+    pub fast_showdown: bool, // Start with the one-line showdown instead of the reveal sequence
+    pub pot_limit: bool, // Pot-limit betting instead of no-limit
+    pub max_name_width: usize, // Longest player name allowed, in terminal cells
+    pub ascii_suits: bool, // Draw suits as h/d/c/s instead of unicode glyphs
+    pub allow_straddle: bool, // UTG may straddle (two big blinds)
+    pub bot_straddle_percent: u32, // How often a bot in UTG straddles, 0-100
+    pub button_ante: bool, // The button posts everyone's ante
+    pub prompt_blind_post: bool, // Ask before posting the human's blinds (declining sits the hand out)
+    pub rake_percent: u32, // House cut of each pot, 0-100
+    pub rake_cap: Option<u32>, // Most the house takes from one pot (None = uncapped)
+    pub no_flop_no_drop: bool, // No rake on hands that end before the flop
+    pub rabbit_hunts: u32, // How many times per session the human may see the undealt board after folding
+    pub chip_increment: u32, // Smallest chip: bets are rounded to it, blinds must be multiples (1 = off)
+    pub modal_timeout_secs: Option<u64>, // Close a menu left open on the human's turn and check/fold (None = wait forever)
+    pub short_deck: bool, // Six-plus Hold'em: deal from a 36-card deck with short-deck hand rankings
+    pub seed: Option<u64>, // Seed for the deals, button and bot decisions, to replay a session (None = random each session)
+    pub auto_fold_below: Option<PreflopClass>, // Fold the human's hands weaker than this to a preflop raise (None = off)
+    pub table_talk: bool, // Bots occasionally react to big pots, bad beats and bluffs
+    pub showdown_ranking: bool, // List every showdown hand best to worst, with the kickers that decided it
+    pub stop_loss: Option<SessionLimit>, // Offer to quit once the session is down this much
+    pub stop_win: Option<SessionLimit>, // Offer to quit once the session is up this much
+    pub coach: bool, // Trainer hints: the human's outs on the flop and turn
+    pub pot_odds: bool, // On the human's turn facing a bet, show pot odds, the equity needed and a call verdict
+    pub ai_concurrency: usize, // Most OpenAI requests in flight at once; the rest queue
+    pub think_aloud: bool, // API bots give a one-line reason with each action, logged at verbose
+    pub debug: bool, // Development self-checks: showdown hands re-evaluated by brute force, disagreements logged
+    pub bot_rebuys: u32, // Times each busted bot buys back in to the starting stack (0 = bots stay busted)
+    pub drill: Option<PathBuf>, // Training drill: a scenario file or directory to play instead of normal hands
+    pub keys: Option<PathBuf>, // Key map file rebinding the single-key commands (None = default keys)
+    pub fresh_table: bool, // Ignore the saved profile: default bots, no carried-over reads, nothing saved
+    pub verbosity: Verbosity, // Starting log detail (can be cycled in game)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            small_blind: 5,
+            big_blind: 10,
+            starting_chips: 1000,
+            max_raises_per_street: None,
+            fast_showdown: false,
+            pot_limit: false,
+            max_name_width: 16,
+            ascii_suits: false,
+            allow_straddle: false,
+            bot_straddle_percent: 25,
+            button_ante: false,
+            prompt_blind_post: false,
+            rake_percent: 0,
+            rake_cap: None,
+            no_flop_no_drop: false,
+            rabbit_hunts: 3,
+            chip_increment: 1,
+            modal_timeout_secs: None,
+            short_deck: false,
+            seed: None,
+            auto_fold_below: None,
+            table_talk: true,
+            showdown_ranking: false,
+            stop_loss: None,
+            stop_win: None,
+            coach: false,
+            pot_odds: false,
+            ai_concurrency: DEFAULT_AI_CONCURRENCY,
+            think_aloud: false,
+            debug: false,
+            bot_rebuys: 0,
+            drill: None,
+            keys: None,
+            fresh_table: false,
+            verbosity: Verbosity::Normal,
+        }
+    }
+}
+
+impl Config {
+    // Build a config from command-line arguments (program name already skipped)
+    // Supported flags: --small-blind N, --big-blind N, --blinds SB/BB, --chips N,
+    // --max-raises N, --casual, --fast-showdown, --pot-limit, --max-name-width N, --ascii,
+    // --straddle, --straddle-chance PCT, --button-ante, --prompt-blinds,
+    // --rake PCT, --rake-cap N, --no-flop-no-drop, --rabbit-hunts N, --modal-timeout SECS,
+    // --chip-increment N, --short-deck, --seed N, --auto-fold CLASS, --no-chat,
+    // --fresh-table, --verbosity LEVEL, --rank-showdown, --bot-rebuys N, --keys PATH,
+    // --pot-odds, --stop-loss N[bb], --stop-win N[bb], --ai-concurrency N, --coach,
+    // --think-aloud, --debug, and the `drill PATH` command
+    pub fn from_args<I: Iterator<Item = String>>(mut args: I) -> Result<Config, String> {
+        let mut config = Config::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--small-blind" => {
+                    config.small_blind = parse_amount(&arg, args.next())?;
+                },
+                "--big-blind" => {
+                    config.big_blind = parse_amount(&arg, args.next())?;
+                },
+                "--blinds" => {
+                    // Accept the usual "25/50" notation
+                    let value = args.next().ok_or_else(|| format!("{} requires a value like 25/50", arg))?;
+                    let (sb, bb) = value.split_once('/')
+                        .ok_or_else(|| format!("Invalid blinds '{}', expected SB/BB", value))?;
+                    config.small_blind = parse_amount(&arg, Some(sb.to_string()))?;
+                    config.big_blind = parse_amount(&arg, Some(bb.to_string()))?;
+                },
+                "--chips" => {
+                    config.starting_chips = parse_amount(&arg, args.next())?;
+                },
+                "--max-raises" => {
+                    config.max_raises_per_street = Some(parse_amount(&arg, args.next())?);
+                },
+                "--casual" => {
+                    // Casual preset: limit the escalation to four bets/raises per street
+                    config.max_raises_per_street = Some(4);
+                },
+                "--max-name-width" => {
+                    config.max_name_width = parse_amount(&arg, args.next())? as usize;
+                },
+                "--straddle" => {
+                    config.allow_straddle = true;
+                },
+                "--straddle-chance" => {
+                    config.allow_straddle = true;
+                    config.bot_straddle_percent = parse_amount(&arg, args.next())?;
+                },
+                "--button-ante" => {
+                    config.button_ante = true;
+                },
+                "--prompt-blinds" => {
+                    config.prompt_blind_post = true;
+                },
+                "--rake" => {
+                    config.rake_percent = parse_amount(&arg, args.next())?;
+                },
+                "--rake-cap" => {
+                    config.rake_cap = Some(parse_amount(&arg, args.next())?);
+                },
+                "--no-flop-no-drop" => {
+                    config.no_flop_no_drop = true;
+                },
+                "--rabbit-hunts" => {
+                    config.rabbit_hunts = parse_amount(&arg, args.next())?;
+                },
+                "--chip-increment" => {
+                    config.chip_increment = parse_amount(&arg, args.next())?;
+                },
+                "--seed" => {
+                    let value = args.next().ok_or_else(|| format!("{} requires a value", arg))?;
+                    config.seed = Some(value.trim().parse::<u64>()
+                        .map_err(|_| format!("Invalid seed '{}' for {}", value, arg))?);
+                },
+                "--auto-fold" => {
+                    let value = args.next().ok_or_else(|| format!("{} requires a value", arg))?;
+                    let class = PreflopClass::from_name(&value)
+                        .ok_or_else(|| format!("Invalid hand class '{}' for {} (use weak, playable, strong or premium)", value, arg))?;
+                    config.auto_fold_below = Some(class);
+                },
+                "--verbosity" => {
+                    let value = args.next().ok_or_else(|| format!("{} requires a value", arg))?;
+                    config.verbosity = [Verbosity::Quiet, Verbosity::Normal, Verbosity::Verbose].into_iter()
+                        .find(|level| level.name() == value.trim().to_lowercase())
+                        .ok_or_else(|| format!("Invalid verbosity '{}' for {} (use quiet, normal or verbose)", value, arg))?;
+                },
+                "--fresh-table" => {
+                    config.fresh_table = true;
+                },
+                "--no-chat" => {
+                    config.table_talk = false;
+                },
+                "drill" => {
+                    let path = args.next().ok_or_else(|| "drill requires a scenario file or directory".to_string())?;
+                    config.drill = Some(PathBuf::from(path));
+                },
+                "--keys" => {
+                    let path = args.next().ok_or_else(|| format!("{} requires a key map file", arg))?;
+                    config.keys = Some(PathBuf::from(path));
+                },
+                "--ai-concurrency" => {
+                    config.ai_concurrency = parse_amount(&arg, args.next())? as usize;
+                },
+                "--bot-rebuys" => {
+                    config.bot_rebuys = parse_amount(&arg, args.next())?;
+                },
+                "--stop-loss" => {
+                    config.stop_loss = Some(parse_limit(&arg, args.next())?);
+                },
+                "--stop-win" => {
+                    config.stop_win = Some(parse_limit(&arg, args.next())?);
+                },
+                "--coach" => {
+                    config.coach = true;
+                },
+                "--think-aloud" => {
+                    config.think_aloud = true;
+                },
+                "--debug" => {
+                    config.debug = true;
+                },
+                "--pot-odds" => {
+                    config.pot_odds = true;
+                },
+                "--rank-showdown" => {
+                    config.showdown_ranking = true;
+                },
+                "--short-deck" => {
+                    config.short_deck = true;
+                },
+                "--modal-timeout" => {
+                    config.modal_timeout_secs = Some(parse_amount(&arg, args.next())? as u64);
+                },
+                "--ascii" => {
+                    config.ascii_suits = true;
+                },
+                "--pot-limit" => {
+                    config.pot_limit = true;
+                },
+                "--fast-showdown" => {
+                    config.fast_showdown = true;
+                },
+                _ => return Err(format!("Unknown option '{}'", arg)),
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Check that the blind structure is playable
+    pub fn validate(&self) -> Result<(), String> {
+        if self.big_blind == 0 {
+            return Err("Big blind must be greater than zero".to_string());
+        }
+        if self.big_blind < self.small_blind {
+            return Err(format!("Big blind ({}) must be at least the small blind ({})",
+                               self.big_blind, self.small_blind));
+        }
+        if self.max_raises_per_street == Some(0) {
+            return Err("Max raises per street must be at least 1".to_string());
+        }
+        if self.bot_straddle_percent > 100 {
+            return Err(format!("Straddle chance must be a percentage (got {})", self.bot_straddle_percent));
+        }
+        if self.chip_increment == 0 {
+            return Err("Chip increment must be at least 1".to_string());
+        }
+        if !self.small_blind.is_multiple_of(self.chip_increment) || !self.big_blind.is_multiple_of(self.chip_increment) {
+            return Err(format!("Blinds ({}/{}) must be multiples of the chip increment ({})",
+                               self.small_blind, self.big_blind, self.chip_increment));
+        }
+        if self.modal_timeout_secs == Some(0) {
+            return Err("Modal timeout must be at least 1 second".to_string());
+        }
+        if self.rake_percent > 100 {
+            return Err(format!("Rake must be a percentage (got {})", self.rake_percent));
+        }
+        if self.max_name_width == 0 {
+            return Err("Max name width must be at least 1".to_string());
+        }
+        if self.stop_loss.is_some_and(|limit| limit.chips(self.big_blind) == 0)
+            || self.stop_win.is_some_and(|limit| limit.chips(self.big_blind) == 0) {
+            return Err("Stop-loss and stop-win must be greater than zero".to_string());
+        }
+        if self.ai_concurrency == 0 {
+            return Err("AI concurrency must be at least 1".to_string());
+        }
+        if self.starting_chips == 0 {
+            return Err("Starting chips must be greater than zero".to_string());
+        }
+        if self.starting_chips < self.big_blind {
+            return Err(format!("Starting chips ({}) can't cover the big blind ({})",
+                               self.starting_chips, self.big_blind));
+        }
+        Ok(())
+    }
+
+    // Playable but shallow: worth telling the player before the first hand
+    pub fn short_stack_notice(&self) -> Option<String> {
+        short_stack_notice(self.starting_chips, self.big_blind)
+    }
+
+    // Set up a newly created table with the rules and bot settings chosen here. The blinds,
+    // stacks and seed are given when the game is created and reseeded.
+    pub fn apply_to(&self, game: &mut Game) {
+        game.max_raises_per_street = self.max_raises_per_street;
+        game.pot_limit = self.pot_limit;
+        game.allow_straddle = self.allow_straddle;
+        game.bot_straddle_chance = self.bot_straddle_percent as f64 / 100.0;
+        game.button_ante = self.button_ante;
+        game.rake_percent = self.rake_percent;
+        game.rake_cap = self.rake_cap;
+        game.no_flop_no_drop = self.no_flop_no_drop;
+        game.chip_increment = self.chip_increment;
+        game.bot_auto_rebuy = self.bot_rebuys > 0;
+        game.max_bot_rebuys = self.bot_rebuys;
+        game.api_limiter = Arc::new(ApiLimiter::new(self.ai_concurrency));
+        game.short_deck = self.short_deck;
+        game.think_aloud = self.think_aloud;
+        game.eval_self_check = self.debug;
+    }
+}
+
+// Warn about stacks under SHORT_STACK_BB big blinds, suggesting a stack that would clear it
+pub fn short_stack_notice(starting_chips: u32, big_blind: u32) -> Option<String> {
+    if big_blind == 0 || starting_chips >= big_blind.saturating_mul(SHORT_STACK_BB) {
+        return None;
+    }
+    Some(format!("Stacks are only {}bb (${} with a ${} big blind), so expect push/fold poker. \
+                  For deeper play use --chips {} or smaller blinds.",
+                 starting_chips / big_blind, starting_chips, big_blind, big_blind.saturating_mul(SHORT_STACK_BB)))
+}
+
+// Parse a chip amount for the given flag
+fn parse_amount(flag: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value.trim().parse::<u32>()
+        .map_err(|_| format!("Invalid amount '{}' for {}", value, flag))
+}
+
+// Parse a session limit for the given flag: chips ("200") or big blinds ("20bb")
+fn parse_limit(flag: &str, value: Option<String>) -> Result<SessionLimit, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    let text = value.trim().to_lowercase();
+    let limit = match text.strip_suffix("bb") {
+        Some(bbs) => bbs.trim().parse::<u32>().map(SessionLimit::BigBlinds),
+        None => text.parse::<u32>().map(SessionLimit::Chips),
+    };
+    limit.map_err(|_| format!("Invalid amount '{}' for {} (use chips, e.g. 200, or big blinds, e.g. 20bb)", value, flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_to_sets_every_table_rule() {
+        let args = ["--pot-limit", "--straddle", "--straddle-chance", "40", "--button-ante", "--rake", "5", "--rake-cap", "30",
+                    "--no-flop-no-drop", "--chip-increment", "5", "--bot-rebuys", "2", "--ai-concurrency", "3",
+                    "--short-deck", "--think-aloud", "--debug", "--max-raises", "4"];
+        let config = Config::from_args(args.map(String::from).into_iter()).unwrap();
+        let mut game = Game::headless(3, 1000, 5, 10);
+        config.apply_to(&mut game);
+        assert!(game.pot_limit && game.allow_straddle && game.button_ante && game.no_flop_no_drop);
+        assert!(game.short_deck && game.think_aloud && game.eval_self_check && game.bot_auto_rebuy);
+        assert_eq!(game.bot_straddle_chance, 0.4);
+        assert_eq!((game.rake_percent, game.rake_cap), (5, Some(30)));
+        assert_eq!((game.chip_increment, game.max_bot_rebuys), (5, 2));
+        assert_eq!(game.max_raises_per_street, Some(4));
+        assert_eq!(game.api_limiter.max(), 3);
+    }
+
+    #[test]
+    fn apply_to_with_defaults_leaves_a_plain_table() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        Config::default().apply_to(&mut game);
+        assert!(!game.pot_limit && !game.allow_straddle && !game.short_deck && !game.bot_auto_rebuy);
+        assert_eq!(game.rake_percent, 0);
+    }
+}
@@ -0,0 +1,296 @@
+// Training drills: preset decision points loaded from scenario files.
+// Each scenario sets up one heads-up spot (cards, board, pot, stacks, the villain's action),
+// the player acts, and the choice is checked against the scenario's annotated answers.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::game::{BotDifficulty, Card, Game, GameAction, Rank, Round, Suit};
+
+const DEFAULT_BIG_BLIND: u32 = 10;
+
+// One drill, as written in a scenario file (JSON)
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,    // Extra context shown before the decision (optional)
+    pub hero_cards: Vec<String>, // Two cards like "Ah", "Td" or "10d"
+    pub board: Vec<String>,     // 0, 3, 4 or 5 cards; the count sets the street (optional, preflop)
+    pub pot: u32,               // Chips in the middle from earlier streets
+    pub hero_stack: u32,        // Behind, not counting hero_bet
+    pub villain_stack: u32,     // Behind, not counting the villain's bet
+    pub hero_position: String,  // "button" or "big blind"
+    pub hero_bet: u32,          // Hero's chips already in on this street, e.g. the big blind (optional, 0)
+    pub villain_action: String, // "check", or "bet N" / "raise N" with N the villain's total on the street
+    pub big_blind: u32,         // Optional, DEFAULT_BIG_BLIND
+    pub correct: Vec<String>,   // Acceptable answers: "fold", "check", "call" and/or "raise"
+    pub explanation: String,
+}
+
+impl Scenario {
+    // Read a scenario file's JSON object. Read field by field rather than derived, so drills
+    // don't need the serde derives the history and llm features bring in.
+    pub fn from_json(json: &str) -> Result<Scenario, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let fields = value.as_object().ok_or("a scenario must be a JSON object")?;
+        Ok(Scenario {
+            name: required("name", json_string(fields, "name")?)?,
+            description: json_string(fields, "description")?.unwrap_or_default(),
+            hero_cards: required("hero_cards", json_strings(fields, "hero_cards")?)?,
+            board: json_strings(fields, "board")?.unwrap_or_default(),
+            pot: required("pot", json_amount(fields, "pot")?)?,
+            hero_stack: required("hero_stack", json_amount(fields, "hero_stack")?)?,
+            villain_stack: required("villain_stack", json_amount(fields, "villain_stack")?)?,
+            hero_position: required("hero_position", json_string(fields, "hero_position")?)?,
+            hero_bet: json_amount(fields, "hero_bet")?.unwrap_or(0),
+            villain_action: required("villain_action", json_string(fields, "villain_action")?)?,
+            big_blind: json_amount(fields, "big_blind")?.unwrap_or(DEFAULT_BIG_BLIND),
+            correct: required("correct", json_strings(fields, "correct")?)?,
+            explanation: required("explanation", json_string(fields, "explanation")?)?,
+        })
+    }
+
+    // The villain's total bet on this street (0 for a check)
+    fn villain_bet(&self) -> Result<u32, String> {
+        let action = self.villain_action.trim().to_lowercase();
+        if action == "check" {
+            return Ok(0);
+        }
+        let amount = action.strip_prefix("bet ").or_else(|| action.strip_prefix("raise "))
+            .ok_or_else(|| format!("Invalid villain_action '{}' (use check, bet N or raise N)", self.villain_action))?;
+        amount.trim().parse::<u32>()
+            .map_err(|_| format!("Invalid amount in villain_action '{}'", self.villain_action))
+    }
+
+    // A heads-up game paused on the hero's decision: seat 0 is the hero, seat 1 the villain
+    pub fn build_game(&self, hero_name: String) -> Result<Game, String> {
+        let hero_cards = parse_cards(&self.hero_cards)?;
+        let board = parse_cards(&self.board)?;
+        if hero_cards.len() != 2 {
+            return Err(format!("{}: hero_cards needs exactly 2 cards", self.name));
+        }
+        let round = match board.len() {
+            0 => Round::PreFlop,
+            3 => Round::Flop,
+            4 => Round::Turn,
+            5 => Round::River,
+            n => return Err(format!("{}: a board has 0, 3, 4 or 5 cards, not {}", self.name, n)),
+        };
+        let used: Vec<&Card> = hero_cards.iter().chain(board.iter()).collect();
+        if (1..used.len()).any(|i| used[..i].contains(&used[i])) {
+            return Err(format!("{}: a card appears twice", self.name));
+        }
+        let hero_on_button = match self.hero_position.trim().to_lowercase().as_str() {
+            "button" | "btn" => true,
+            "big blind" | "bb" => false,
+            other => return Err(format!("{}: hero_position '{}' should be button or big blind", self.name, other)),
+        };
+        let villain_bet = self.villain_bet()?;
+        if villain_bet > 0 && villain_bet <= self.hero_bet {
+            return Err(format!("{}: the villain's bet must be more than hero_bet", self.name));
+        }
+
+        let mut game = Game::new(1, 1, BotDifficulty::Medium, 0, self.big_blind / 2, self.big_blind, None, hero_name);
+        game.players[1].name = "Villain".to_string();
+        game.deck.retain(|card| !used.contains(&card));
+        game.shuffle_deck();
+        let villain_cards: Vec<Card> = game.deck.drain(..2).collect();
+
+        game.players[0].hand = hero_cards;
+        game.players[0].chips = self.hero_stack;
+        game.players[0].current_bet = self.hero_bet;
+        game.players[1].hand = villain_cards;
+        game.players[1].chips = self.villain_stack;
+        game.players[1].current_bet = villain_bet;
+        game.community_cards = board;
+        game.round = round;
+        game.pot = self.pot + self.hero_bet + villain_bet;
+
+        // Heads-up, the button is the small blind
+        game.dealer_idx = if hero_on_button { 0 } else { 1 };
+        game.small_blind_idx = game.dealer_idx;
+        game.big_blind_idx = 1 - game.dealer_idx;
+        game.player_contributions_this_round = vec![self.hero_bet, villain_bet];
+        game.hand_contributions = vec![self.pot / 2 + self.hero_bet, self.pot - self.pot / 2 + villain_bet];
+        game.players_acted_this_round = vec![1];
+        if villain_bet > 0 {
+            game.last_aggressor = Some(1);
+            game.raises_this_street = 1;
+        }
+        game.current_player_idx = 0;
+        Ok(game)
+    }
+
+    // Whether the hero's action is one of the annotated answers
+    pub fn is_correct(&self, action: &GameAction) -> bool {
+        let name = action_name(action);
+        self.correct.iter().any(|answer| answer.trim().eq_ignore_ascii_case(name))
+    }
+}
+
+pub fn action_name(action: &GameAction) -> &'static str {
+    match action {
+        GameAction::Fold => "fold",
+        GameAction::Check => "check",
+        GameAction::Call => "call",
+        GameAction::Raise(_) => "raise",
+    }
+}
+
+// "Ah", "td", "10s" -> Card
+fn parse_card(text: &str) -> Option<Card> {
+    let text = text.trim();
+    let split = text.len().checked_sub(1)?;
+    let (rank, suit) = text.split_at(split);
+    let rank = match rank.to_uppercase().as_str() {
+        "2" => Rank::Two, "3" => Rank::Three, "4" => Rank::Four, "5" => Rank::Five,
+        "6" => Rank::Six, "7" => Rank::Seven, "8" => Rank::Eight, "9" => Rank::Nine,
+        "T" | "10" => Rank::Ten, "J" => Rank::Jack, "Q" => Rank::Queen, "K" => Rank::King,
+        "A" => Rank::Ace,
+        _ => return None,
+    };
+    let suit = match suit.to_lowercase().as_str() {
+        "h" => Suit::Hearts, "d" => Suit::Diamonds, "c" => Suit::Clubs, "s" => Suit::Spades,
+        _ => return None,
+    };
+    Some(Card { rank, suit })
+}
+
+fn parse_cards(texts: &[String]) -> Result<Vec<Card>, String> {
+    texts.iter()
+        .map(|text| parse_card(text).ok_or_else(|| format!("Invalid card '{}' (use e.g. Ah, Td, 9c)", text)))
+        .collect()
+}
+
+fn required<T>(key: &str, value: Option<T>) -> Result<T, String> {
+    value.ok_or_else(|| format!("missing field `{}`", key))
+}
+
+// A field of a scenario object: None when it's missing, an error when it's the wrong type
+fn json_string(fields: &Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    fields.get(key)
+        .map(|value| value.as_str().map(str::to_string).ok_or_else(|| format!("`{}` must be a string", key)))
+        .transpose()
+}
+
+fn json_amount(fields: &Map<String, Value>, key: &str) -> Result<Option<u32>, String> {
+    fields.get(key)
+        .map(|value| value.as_u64().and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| format!("`{}` must be a whole number of chips", key)))
+        .transpose()
+}
+
+fn json_strings(fields: &Map<String, Value>, key: &str) -> Result<Option<Vec<String>>, String> {
+    fields.get(key)
+        .map(|value| value.as_array()
+            .and_then(|items| items.iter().map(|item| item.as_str().map(str::to_string)).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| format!("`{}` must be a list of strings", key)))
+        .transpose()
+}
+
+// A scenario file, or every .json file in a directory (in file name order)
+pub fn load_scenarios(path: &Path) -> Result<Vec<Scenario>, String> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        let entries = fs::read_dir(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        for entry in entries.flatten() {
+            let file = entry.path();
+            if file.extension().is_some_and(|ext| ext == "json") {
+                files.push(file);
+            }
+        }
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut scenarios = Vec::new();
+    for file in files {
+        let json = fs::read_to_string(&file).map_err(|e| format!("Could not read {}: {}", file.display(), e))?;
+        let scenario = Scenario::from_json(&json).map_err(|e| format!("{}: {}", file.display(), e))?;
+        // Catch bad cards or amounts now rather than mid-drill
+        scenario.build_game(String::new()).map_err(|e| format!("{}: {}", file.display(), e))?;
+        scenarios.push(scenario);
+    }
+    if scenarios.is_empty() {
+        return Err(format!("No scenarios found in {}", path.display()));
+    }
+    Ok(scenarios)
+}
+
+// Progress through a set of scenarios, cycling back to the first after the last
+pub struct DrillSession {
+    pub scenarios: Vec<Scenario>,
+    pub current: usize,  // Scenario on the table (or next up, once answered)
+    pub answered: u32,
+    pub correct: u32,
+    pub awaiting_answer: bool, // A scenario is set up and the hero hasn't acted yet
+}
+
+impl DrillSession {
+    pub fn new(scenarios: Vec<Scenario>) -> Self {
+        DrillSession { scenarios, current: 0, answered: 0, correct: 0, awaiting_answer: false }
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenarios[self.current]
+    }
+
+    // Record the hero's answer to the current scenario and move on; returns whether it was right
+    pub fn answer(&mut self, action: &GameAction) -> bool {
+        let right = self.scenario().is_correct(action);
+        self.answered += 1;
+        if right {
+            self.correct += 1;
+        }
+        self.awaiting_answer = false;
+        self.current = (self.current + 1) % self.scenarios.len();
+        right
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.answered > 0 { self.correct as f64 * 100.0 / self.answered as f64 } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREFLOP_SHOVE: &str = r#"{
+        "name": "Kings facing a shove",
+        "hero_cards": ["Kh", "Kd"],
+        "pot": 15,
+        "hero_stack": 990,
+        "villain_stack": 0,
+        "hero_position": "big blind",
+        "villain_action": "raise 1000",
+        "correct": ["call"],
+        "explanation": "Only aces are ahead."
+    }"#;
+
+    #[test]
+    fn optional_fields_take_their_defaults() {
+        let scenario = Scenario::from_json(PREFLOP_SHOVE).unwrap();
+        assert_eq!(scenario.hero_cards, vec!["Kh", "Kd"]);
+        assert!(scenario.board.is_empty());
+        assert!(scenario.description.is_empty());
+        assert_eq!((scenario.hero_bet, scenario.big_blind), (0, DEFAULT_BIG_BLIND));
+        assert!(scenario.is_correct(&GameAction::Call));
+    }
+
+    #[test]
+    fn missing_or_mistyped_fields_are_named() {
+        let missing = PREFLOP_SHOVE.replace(r#""pot": 15,"#, "");
+        assert_eq!(Scenario::from_json(&missing).unwrap_err(), "missing field `pot`");
+
+        let mistyped = PREFLOP_SHOVE.replace(r#""pot": 15"#, r#""pot": "15""#);
+        assert_eq!(Scenario::from_json(&mistyped).unwrap_err(), "`pot` must be a whole number of chips");
+
+        let bad_list = PREFLOP_SHOVE.replace(r#"["call"]"#, r#"["call", 2]"#);
+        assert_eq!(Scenario::from_json(&bad_list).unwrap_err(), "`correct` must be a list of strings");
+        assert!(Scenario::from_json("[]").is_err());
+    }
+}
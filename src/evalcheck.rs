@@ -0,0 +1,153 @@
+// Showdown self-check (--debug): every live hand is evaluated a second time by brute force,
+// best five of the seven cards scored from scratch, and any disagreement with rs_poker is
+// reported. It shares nothing with the main evaluation path but the cards.
+
+use std::cmp::Ordering;
+
+use rs_poker::core::Rank as PokerRank;
+
+use crate::game::{Card, Game};
+
+const CATEGORY_NAMES: [&str; 9] = [
+    "High Card", "Pair", "Two Pair", "Three of a Kind", "Straight",
+    "Flush", "Full House", "Four of a Kind", "Straight Flush",
+];
+
+// A hand's score: category (0 = high card ... 8 = straight flush), then the ranks that break
+// ties within it, most important first. Scores compare in the table's hand order.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Score {
+    order: u8,          // Category position in this game's ranking (short deck swaps flush and full house)
+    category: u8,
+    tiebreak: Vec<u8>,
+}
+
+fn order_of(category: u8, short_deck: bool) -> u8 {
+    match category {
+        5 if short_deck => 6,
+        6 if short_deck => 5,
+        other => other,
+    }
+}
+
+// Score exactly five cards
+fn score_five(cards: &[&Card], short_deck: bool) -> Score {
+    let mut values: Vec<u8> = cards.iter().map(|c| c.rank.value()).collect();
+    values.sort_unstable_by(|a, b| b.cmp(a));
+    let flush = cards.iter().all(|c| c.suit == cards[0].suit);
+
+    // The ace plays low below the lowest rank in the deck: A-2-3-4-5, or A-6-7-8-9 in short deck
+    let low_straight: [u8; 5] = if short_deck { [14, 9, 8, 7, 6] } else { [14, 5, 4, 3, 2] };
+    let distinct = values.windows(2).all(|pair| pair[0] != pair[1]);
+    let straight_high = if distinct && values[0] - values[4] == 4 {
+        Some(values[0])
+    } else if values == low_straight {
+        Some(values[1])
+    } else {
+        None
+    };
+
+    // Groups of equal rank, biggest group first, then highest rank
+    let mut groups: Vec<(u8, u8)> = Vec::new(); // (count, value)
+    for &value in &values {
+        match groups.iter_mut().find(|(_, v)| *v == value) {
+            Some((count, _)) => *count += 1,
+            None => groups.push((1, value)),
+        }
+    }
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let by_group: Vec<u8> = groups.iter().map(|&(_, value)| value).collect();
+
+    let (category, tiebreak) = match (straight_high, flush, groups[0].0, groups.get(1).map_or(0, |g| g.0)) {
+        (Some(high), true, _, _) => (8, vec![high]),
+        (_, _, 4, _) => (7, by_group),
+        (_, _, 3, 2) => (6, by_group),
+        (_, true, _, _) => (5, values),
+        (Some(high), false, _, _) => (4, vec![high]),
+        (_, _, 3, _) => (3, by_group),
+        (_, _, 2, 2) => (2, by_group),
+        (_, _, 2, _) => (1, by_group),
+        _ => (0, values),
+    };
+    Score { order: order_of(category, short_deck), category, tiebreak }
+}
+
+// Best score over every five-card subset of five to seven cards
+fn best_score(cards: &[&Card], short_deck: bool) -> Option<Score> {
+    let n = cards.len();
+    if !(5..=7).contains(&n) {
+        return None;
+    }
+    let mut best: Option<Score> = None;
+    for mask in 0u32..(1 << n) {
+        if mask.count_ones() != 5 {
+            continue;
+        }
+        let five: Vec<&Card> = (0..n).filter(|&i| mask & (1 << i) != 0).map(|i| cards[i]).collect();
+        let score = score_five(&five, short_deck);
+        if best.as_ref().is_none_or(|b| score > *b) {
+            best = Some(score);
+        }
+    }
+    best
+}
+
+fn poker_category(rank: &PokerRank) -> u8 {
+    match rank {
+        PokerRank::HighCard(_) => 0,
+        PokerRank::OnePair(_) => 1,
+        PokerRank::TwoPair(_) => 2,
+        PokerRank::ThreeOfAKind(_) => 3,
+        PokerRank::Straight(_) => 4,
+        PokerRank::Flush(_) => 5,
+        PokerRank::FullHouse(_) => 6,
+        PokerRank::FourOfAKind(_) => 7,
+        PokerRank::StraightFlush(_) => 8,
+    }
+}
+
+fn ordering_word(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Greater => "ahead of",
+        Ordering::Less => "behind",
+        Ordering::Equal => "tied with",
+    }
+}
+
+// Disagreements between rs_poker and the brute-force evaluator over the hands still live,
+// as warning lines: a hand put in a different category, or two hands ordered differently
+pub fn cross_check(game: &Game) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut checked: Vec<(usize, PokerRank, Score)> = Vec::new();
+    for (idx, player) in game.players.iter().enumerate().filter(|(_, p)| !p.folded) {
+        let mut cards: Vec<&Card> = Vec::with_capacity(7);
+        for card in player.hand.iter().chain(game.community_cards.iter()) {
+            if !cards.contains(&card) {
+                cards.push(card);
+            }
+        }
+        let (Some(rank), Some(score)) = (game.evaluate_hand(idx), best_score(&cards, game.short_deck)) else {
+            continue;
+        };
+        if poker_category(&rank) != score.category {
+            let shown = cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+            warnings.push(format!("evaluator self-check: {} ({}) is {} to rs_poker but {} by brute force",
+                                  player.name, shown, Game::hand_type_name(&rank), CATEGORY_NAMES[score.category as usize]));
+            continue;
+        }
+        checked.push((idx, rank, score));
+    }
+
+    for (i, (a_idx, a_rank, a_score)) in checked.iter().enumerate() {
+        for (b_idx, b_rank, b_score) in &checked[i + 1..] {
+            let by_poker = game.compare_ranks(a_rank, b_rank);
+            let by_brute = a_score.cmp(b_score);
+            if by_poker != by_brute {
+                let (a, b) = (&game.players[*a_idx].name, &game.players[*b_idx].name);
+                warnings.push(format!("evaluator self-check: rs_poker has {} {} {}, brute force has {} {} {}",
+                                      a, ordering_word(by_poker), b, a, ordering_word(by_brute), b));
+            }
+        }
+    }
+    warnings
+}
@@ -0,0 +1,47 @@
+// Canned table talk, so the bots have a little personality without an API key.
+// Difficulty stands in for persona: Easy bots are chatty amateurs, Hard bots are dry regulars.
+
+use rand::Rng;
+
+use crate::game::BotDifficulty;
+
+// Chance a bot says something when one of these happens
+pub const LINE_CHANCE: f64 = 0.35;
+
+// Pots at least this many big blinds count as big
+pub const BIG_POT_BB: u32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FlavorEvent {
+    BigPot,  // Won a big pot
+    BadBeat, // Lost a showdown holding a strong hand
+    Bluff,   // Took the pot uncontested with nothing (and shows it)
+}
+
+fn phrases(persona: &BotDifficulty, event: FlavorEvent) -> &'static [&'static str] {
+    match (persona, event) {
+        (BotDifficulty::Easy, FlavorEvent::BigPot) => &["Woohoo! Ship it!", "I knew it was my lucky night.", "Is this what winning feels like?"],
+        (BotDifficulty::Easy, FlavorEvent::BadBeat) => &["Are you kidding me?!", "That can't be right...", "Every single time."],
+        (BotDifficulty::Easy, FlavorEvent::Bluff) => &["Hehe, look what I had!", "Phew!"],
+        (BotDifficulty::Medium, FlavorEvent::BigPot) => &["Ship it.", "I'll take that.", "Nice pot."],
+        (BotDifficulty::Medium, FlavorEvent::BadBeat) => &["Unlucky.", "Of course it gets there.", "Nice river."],
+        (BotDifficulty::Medium, FlavorEvent::Bluff) => &["Thanks for the fold.", "Good laydown, I'm sure."],
+        (BotDifficulty::Hard, FlavorEvent::BigPot) => &["gg.", "As planned.", "Stack it."],
+        (BotDifficulty::Hard, FlavorEvent::BadBeat) => &["Variance.", "Still a good call.", "nh."],
+        (BotDifficulty::Hard, FlavorEvent::Bluff) => &["...", "Just so you know."],
+    }
+}
+
+// Made hands good enough that losing with them stings
+pub fn is_strong(hand_type: &str) -> bool {
+    matches!(hand_type, "Straight" | "Flush" | "Full House" | "Four of a Kind" | "Straight Flush")
+}
+
+// Maybe a line for this bot to say about the event (None most of the time)
+pub fn reaction<R: Rng>(persona: &BotDifficulty, event: FlavorEvent, rng: &mut R) -> Option<&'static str> {
+    if !rng.gen_bool(LINE_CHANCE) {
+        return None;
+    }
+    let lines = phrases(persona, event);
+    Some(lines[rng.gen_range(0..lines.len())])
+}
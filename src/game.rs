@@ -1,10 +1,37 @@
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::time::Instant;
 use rand::prelude::*;
 use rand::Rng;
+#[cfg(feature = "llm")]
 use reqwest::blocking::Client;
+#[cfg(feature = "llm")]
 use serde::{Deserialize, Serialize};
 use rs_poker::core::{Card as PokerCard, Suit as PokerSuit, Value as PokerValue, Hand, Rank as PokerRank, Rankable};
 
-// Card representation
+use crate::evalcheck;
+use crate::range::HandRange;
+
+// Render suits as letters (h/d/c/s) instead of glyphs, for terminals that draw them badly
+static ASCII_SUITS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_ascii_suits(enabled: bool) {
+    ASCII_SUITS.store(enabled, Ordering::Relaxed);
+}
+
+/// One playing card.
+///
+/// ```
+/// use p_kr::game::{Card, Rank, Suit};
+///
+/// let card = Card { rank: Rank::Ace, suit: Suit::Spades };
+/// assert_eq!(card.rank.value(), 14);
+/// assert_eq!(card.to_string(), "[A♠]");
+/// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Card {
     pub rank: Rank,
@@ -17,22 +44,38 @@ pub enum Rank {
     Jack, Queen, King, Ace,
 }
 
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Rank::Two => "2", Rank::Three => "3", Rank::Four => "4",
+            Rank::Five => "5", Rank::Six => "6", Rank::Seven => "7",
+            Rank::Eight => "8", Rank::Nine => "9", Rank::Ten => "10",
+            Rank::Jack => "J", Rank::Queen => "Q", Rank::King => "K",
+            Rank::Ace => "A",
+        };
+        f.write_str(text)
+    }
+}
+
 impl Rank {
-    pub fn to_string(&self) -> String {
+    // 2 through 14 (Ace high)
+    pub fn value(&self) -> u8 {
+        match self {
+            Rank::Two => 2, Rank::Three => 3, Rank::Four => 4, Rank::Five => 5,
+            Rank::Six => 6, Rank::Seven => 7, Rank::Eight => 8, Rank::Nine => 9,
+            Rank::Ten => 10, Rank::Jack => 11, Rank::Queen => 12, Rank::King => 13,
+            Rank::Ace => 14,
+        }
+    }
+    
+    // "Aces", "Nines", "Sixes" - for describing made hands
+    pub fn plural_name(&self) -> &'static str {
         match self {
-            Rank::Two => "2".to_string(),
-            Rank::Three => "3".to_string(),
-            Rank::Four => "4".to_string(),
-            Rank::Five => "5".to_string(),
-            Rank::Six => "6".to_string(),
-            Rank::Seven => "7".to_string(),
-            Rank::Eight => "8".to_string(),
-            Rank::Nine => "9".to_string(),
-            Rank::Ten => "10".to_string(),
-            Rank::Jack => "J".to_string(),
-            Rank::Queen => "Q".to_string(),
-            Rank::King => "K".to_string(),
-            Rank::Ace => "A".to_string(),
+            Rank::Two => "Twos", Rank::Three => "Threes", Rank::Four => "Fours",
+            Rank::Five => "Fives", Rank::Six => "Sixes", Rank::Seven => "Sevens",
+            Rank::Eight => "Eights", Rank::Nine => "Nines", Rank::Ten => "Tens",
+            Rank::Jack => "Jacks", Rank::Queen => "Queens", Rank::King => "Kings",
+            Rank::Ace => "Aces",
         }
     }
 }
@@ -42,34 +85,98 @@ pub enum Suit {
     Hearts, Diamonds, Clubs, Spades,
 }
 
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Suit::Hearts => "Hearts",
+            Suit::Diamonds => "Diamonds",
+            Suit::Clubs => "Clubs",
+            Suit::Spades => "Spades",
+        };
+        f.write_str(text)
+    }
+}
+
 impl Suit {
-    pub fn to_string(&self) -> String {
+    // The glyph used on cards (or h/d/c/s with --ascii)
+    pub fn symbol(&self) -> &'static str {
+        if ASCII_SUITS.load(Ordering::Relaxed) {
+            match self {
+                Suit::Hearts => "h", Suit::Diamonds => "d",
+                Suit::Clubs => "c", Suit::Spades => "s",
+            }
+        } else {
+            match self {
+                Suit::Hearts => "♥", Suit::Diamonds => "♦", 
+                Suit::Clubs => "♣", Suit::Spades => "♠",
+            }
+        }
+    }
+}
+
+// "[A♥]" (or "[Ah]" with --ascii)
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}{}]", self.rank, self.suit.symbol())
+    }
+}
+
+// Rough starting-hand tiers, weakest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreflopClass {
+    Trash,    // Unsuited, unconnected, no big cards (7-2, J-3)
+    Weak,     // Any ace, suited or connected cards, K-9+
+    Playable, // Small and middle pairs, two broadway cards, suited aces, suited connectors and one-gappers
+    Strong,   // 10-10 to J-J, A-Q, A-J suited, K-Q suited
+    Premium,  // Q-Q+, A-K
+}
+
+impl PreflopClass {
+    pub fn name(&self) -> &'static str {
         match self {
-            Suit::Hearts => "Hearts".to_string(),
-            Suit::Diamonds => "Diamonds".to_string(),
-            Suit::Clubs => "Clubs".to_string(),
-            Suit::Spades => "Spades".to_string(),
+            PreflopClass::Trash => "trash",
+            PreflopClass::Weak => "weak",
+            PreflopClass::Playable => "playable",
+            PreflopClass::Strong => "strong",
+            PreflopClass::Premium => "premium",
         }
     }
+    
+    pub fn from_name(name: &str) -> Option<PreflopClass> {
+        [PreflopClass::Trash, PreflopClass::Weak, PreflopClass::Playable, PreflopClass::Strong, PreflopClass::Premium]
+            .into_iter()
+            .find(|class| class.name() == name.trim().to_lowercase())
+    }
 }
 
-impl Card {
-    pub fn to_string(&self) -> String {
-        let rank_str = match self.rank {
-            Rank::Two => "2", Rank::Three => "3", Rank::Four => "4",
-            Rank::Five => "5", Rank::Six => "6", Rank::Seven => "7",
-            Rank::Eight => "8", Rank::Nine => "9", Rank::Ten => "10",
-            Rank::Jack => "J", Rank::Queen => "Q", Rank::King => "K",
-            Rank::Ace => "A",
-        };
-        
-        let suit_str = match self.suit {
-            Suit::Hearts => "♥", Suit::Diamonds => "♦", 
-            Suit::Clubs => "♣", Suit::Spades => "♠",
-        };
-        
-        format!("[{}{}]", rank_str, suit_str)
+// Sort two hole cards into a starting-hand tier (None until both are dealt)
+pub fn classify_preflop_hand(hole_cards: &[Card]) -> Option<PreflopClass> {
+    if hole_cards.len() < 2 {
+        return None;
     }
+    let (a, b) = (hole_cards[0].rank.value(), hole_cards[1].rank.value());
+    let (high, low) = (a.max(b), a.min(b));
+    let suited = hole_cards[0].suit == hole_cards[1].suit;
+    let gap = high - low;
+    
+    let class = if high == low {
+        match high {
+            12.. => PreflopClass::Premium,
+            10 | 11 => PreflopClass::Strong,
+            _ => PreflopClass::Playable,
+        }
+    } else if high == 14 && low == 13 {
+        PreflopClass::Premium
+    } else if (high == 14 && low == 12) || (suited && ((high == 14 && low == 11) || (high == 13 && low == 12))) {
+        PreflopClass::Strong
+    } else if low >= 10 || (suited && high == 14) || (suited && gap <= 2 && low >= 5) {
+        PreflopClass::Playable
+    } else if high == 14 || suited || gap == 1 || (high == 13 && low >= 9) {
+        PreflopClass::Weak
+    } else {
+        PreflopClass::Trash
+    };
+    Some(class)
 }
 
 // Representing a poker hand
@@ -88,7 +195,140 @@ pub enum HandRank {
     RoyalFlush,
 }
 
-// Player representation
+// A player's best five cards at showdown, with the evaluator's verdict
+#[derive(Clone, Debug)]
+pub struct BestHand {
+    pub owner: String, // "You" for the human, otherwise the player's name
+    pub rank: PokerRank,
+    pub cards: Vec<Card>,
+}
+
+impl BestHand {
+    // Ranks grouped by how many times they appear, most significant first
+    // (e.g. two pair A-A-9-9-K -> A, 9, K). A wheel straight counts its Ace low.
+    fn groups(&self) -> Vec<(Rank, usize)> {
+        let mut groups: Vec<(Rank, usize)> = Vec::new();
+        for card in &self.cards {
+            match groups.iter_mut().find(|(rank, _)| *rank == card.rank) {
+                Some(group) => group.1 += 1,
+                None => groups.push((card.rank.clone(), 1)),
+            }
+        }
+        // (A-2-3-4-5, or A-6-7-8-9 in short deck: any straight with an Ace but no King)
+        let is_wheel = matches!(self.rank, PokerRank::Straight(_) | PokerRank::StraightFlush(_))
+            && groups.iter().any(|(rank, _)| *rank == Rank::Ace)
+            && !groups.iter().any(|(rank, _)| *rank == Rank::King);
+        let sort_value = |rank: &Rank| if is_wheel && *rank == Rank::Ace { 1 } else { rank.value() };
+        groups.sort_by(|a, b| b.1.cmp(&a.1).then(sort_value(&b.0).cmp(&sort_value(&a.0))));
+        groups
+    }
+    
+    // "Two Pair (Aces and Nines)", "Flush (K-high ♦)", "Full House (Kings full of Fours)"
+    pub fn describe(&self) -> String {
+        let groups = self.groups();
+        let top = match groups.first() {
+            Some((rank, _)) => rank.clone(),
+            None => return "no hand".to_string(),
+        };
+        let second = groups.get(1).map(|(rank, _)| rank.clone()).unwrap_or(top.clone());
+        let suit = self.cards.first().map(|c| c.suit.symbol()).unwrap_or("");
+        match self.rank {
+            PokerRank::HighCard(_) => format!("High Card ({}-high)", top),
+            PokerRank::OnePair(_) => format!("Pair of {}", top.plural_name()),
+            PokerRank::TwoPair(_) => format!("Two Pair ({} and {})", top.plural_name(), second.plural_name()),
+            PokerRank::ThreeOfAKind(_) => format!("Three of a Kind ({})", top.plural_name()),
+            PokerRank::Straight(_) => format!("Straight ({}-high)", top),
+            PokerRank::Flush(_) => format!("Flush ({}-high {})", top, suit),
+            PokerRank::FullHouse(_) => format!("Full House ({} full of {})", top.plural_name(), second.plural_name()),
+            PokerRank::FourOfAKind(_) => format!("Four of a Kind ({})", top.plural_name()),
+            PokerRank::StraightFlush(_) => format!("Straight Flush ({}-high {})", top, suit),
+        }
+    }
+    
+    // Cards outside the made hand that still count, highest first: the side cards of a pair,
+    // two pair, trips or quads, and everything below the top card of a high card hand or flush.
+    // Straights and full houses are decided by their own cards, so they have none.
+    pub fn kickers(&self) -> Vec<Rank> {
+        let groups = self.groups();
+        match self.rank {
+            PokerRank::HighCard(_) | PokerRank::Flush(_) => groups.into_iter().skip(1).map(|(rank, _)| rank).collect(),
+            PokerRank::OnePair(_) | PokerRank::TwoPair(_) | PokerRank::ThreeOfAKind(_) | PokerRank::FourOfAKind(_) =>
+                groups.into_iter().filter(|&(_, count)| count == 1).map(|(rank, _)| rank).collect(),
+            _ => Vec::new(),
+        }
+    }
+    
+    // "Pair of Aces, K-Q-9 kickers" / "Straight (9-high)"
+    pub fn describe_with_kickers(&self) -> String {
+        let kickers = self.kickers();
+        match kickers.len() {
+            0 => self.describe(),
+            1 => format!("{}, {} kicker", self.describe(), kickers[0]),
+            _ => format!("{}, {} kickers", self.describe(),
+                         kickers.iter().map(|rank| rank.to_string()).collect::<Vec<_>>().join("-")),
+        }
+    }
+    
+    // "Your" / "Bot 4's" (or "your" mid-sentence)
+    fn possessive(&self, capitalized: bool) -> String {
+        match (self.owner == "You", capitalized) {
+            (true, true) => "Your".to_string(),
+            (true, false) => "your".to_string(),
+            (false, _) => format!("{}'s", self.owner),
+        }
+    }
+}
+
+// Explain a showdown from the loser's side: what beat what, or which kicker decided it.
+// "Your Two Pair (Aces and Nines) loses to Bot 4's Flush (K-high ♦)"
+// "Same Two Pair (Aces and Nines) - Bot 4's Q kicker beats your J"
+pub fn describe_comparison(winner: &BestHand, loser: &BestHand) -> String {
+    let winner_desc = winner.describe();
+    let loser_desc = loser.describe();
+    
+    if winner.rank == loser.rank {
+        return format!("{} {} ties {} {}", loser.possessive(true), loser_desc, winner.possessive(false), winner_desc);
+    }
+    if winner_desc != loser_desc {
+        return format!("{} {} loses to {} {}", loser.possessive(true), loser_desc, winner.possessive(false), winner_desc);
+    }
+    
+    match deciding_kicker(winner, loser) {
+        Some((winner_kicker, loser_kicker)) => format!("Same {} - {} {} kicker beats {} {}",
+            loser_desc, winner.possessive(false), winner_kicker,
+            loser.possessive(false), loser_kicker),
+        None => format!("{} {} loses to {} {}", loser.possessive(true), loser_desc, winner.possessive(false), winner_desc),
+    }
+}
+
+// Two hands with the same made hand: the first differing card outside it is the kicker
+fn deciding_kicker(better: &BestHand, worse: &BestHand) -> Option<(Rank, Rank)> {
+    better.groups().into_iter()
+        .zip(worse.groups())
+        .find(|(b, w)| b.0 != w.0)
+        .map(|(b, w)| (b.0, w.0))
+}
+
+// One pot of a showdown: the main pot, or a side pot above a short all-in
+#[derive(Clone, Debug)]
+pub struct PotAward {
+    pub winners: Vec<(usize, u32)>, // (Seat, share) for every tied best hand, first seat left of the button first
+    pub amount: u32,     // After rake
+    pub hand: String,    // Winning hand type ("Flush", "Two Pair", ...)
+    pub contenders: Vec<usize>, // Seats who paid into it and could win it
+}
+
+/// One seat at the table: a human or a bot, with their stack and hole cards.
+///
+/// ```
+/// use p_kr::game::Game;
+///
+/// let game = Game::headless(3, 500, 5, 10);
+/// let player = &game.players[0];
+/// assert!(player.is_bot);
+/// assert_eq!(player.chips, 500);
+/// assert!(player.hand.is_empty(), "nothing is dealt until deal_cards");
+/// ```
 #[derive(Clone)]
 pub struct Player {
     pub name: String,
@@ -96,11 +336,26 @@ pub struct Player {
     pub chips: u32,
     pub current_bet: u32,
     pub folded: bool,
+    pub all_in: bool, // Put their last chip in this hand (cleared at the next deal)
     pub is_bot: bool,
     pub bot_difficulty: BotDifficulty,
+    pub bot_id: u32, // Never reused at this table (or in the profile); 0 for humans
 }
 
-#[derive(Clone, Debug)]
+impl Player {
+    pub fn record_key(&self) -> String {
+        bot_record_key(&self.bot_difficulty, self.bot_id)
+    }
+}
+
+// Key for what's been learned about a bot: its persona (the difficulty stands in for one) and
+// its id, so a new bot that takes over a freed "Bot N" name starts with a clean record
+pub fn bot_record_key(persona: &BotDifficulty, id: u32) -> String {
+    format!("{:?} #{}", persona, id)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
 #[allow(dead_code)]
 pub enum BotDifficulty {
     Easy,
@@ -108,15 +363,58 @@ pub enum BotDifficulty {
     Hard,
 }
 
-// Game state
+// Most seats a table can hold
+pub const MAX_SEATS: usize = 9;
+
+// Mixed into the seed for the table's non-card draws, so they don't repeat the shuffle's numbers
+const TABLE_RNG_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The table and the hand in progress. Deal with `deal_cards`, then for the player at
+/// `current_player_idx` call `perform_action` and `next_player` until the round reaches
+/// `Round::Showdown`, and settle with `determine_winner`.
+///
+/// Playing one scripted hand through to the winner:
+///
+/// ```
+/// use p_kr::game::{Card, Game, GameAction, Rank, Round, Suit};
+///
+/// let card = |rank, suit| Card { rank, suit };
+/// let mut game = Game::headless(3, 1000, 5, 10);
+/// game.stack_deck(&[
+///     [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+///     [card(Rank::King, Suit::Clubs), card(Rank::Queen, Suit::Diamonds)],
+///     [card(Rank::Seven, Suit::Clubs), card(Rank::Two, Suit::Diamonds)],
+/// ], &[
+///     card(Rank::Ace, Suit::Diamonds), card(Rank::Nine, Suit::Clubs), card(Rank::Four, Suit::Spades),
+///     card(Rank::Jack, Suit::Hearts), card(Rank::Three, Suit::Clubs),
+/// ]);
+/// game.deal_cards();
+///
+/// // Everyone checks or calls down
+/// while game.round != Round::Showdown {
+///     let action = if game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+///     game.perform_action(action);
+///     if !game.next_player() {
+///         break;
+///     }
+/// }
+///
+/// let (winner, winnings, _) = game.determine_winner();
+/// assert_eq!(winner, 0, "trip aces");
+/// assert_eq!(winnings, 33);
+/// assert_eq!(game.players.iter().map(|p| p.chips).sum::<u32>(), 3000);
+/// ```
+#[derive(Clone)]
 pub struct Game {
     pub players: Vec<Player>,
     pub deck: Vec<Card>,
     pub community_cards: Vec<Card>,
     pub pot: u32,
     pub current_player_idx: usize,
-    pub min_bet: u32,
+    pub small_blind: u32,
+    pub big_blind: u32, // Also the minimum bet and minimum raise increment
     pub round: Round,
+    #[cfg(feature = "llm")]
     pub ai_client: Client,
     pub api_key: Option<String>,
     pub dealer_idx: usize,
@@ -126,11 +424,67 @@ pub struct Game {
     pub bb_has_acted_preflop: bool, // Track if BB has acted in pre-flop
     pub players_acted_this_round: Vec<usize>, // Track which players have already acted in current round
     pub last_aggressor: Option<usize>, // Track the last player who bet or raised
+    pub river_aggressor: Option<usize>, // Last to bet or raise on the river; shows first at showdown
     pub round_action_complete: bool, // Flag for whether a round of betting is complete
     pub player_contributions_this_round: Vec<u32>, // Track how much each player has contributed in the current round
+    pub max_raises_per_street: Option<u32>, // Cap on bets/raises per street (None = unlimited)
+    pub pot_limit: bool, // Pot-limit betting: a raise can't make the total bet more than the pot after calling
+    pub ante: u32, // Per-player ante (the button posts all of it under a button ante)
+    pub button_ante: bool, // The button posts the whole table's ante instead of everyone paying
+    pub rake_percent: u32, // House cut of each pot, 0-100
+    pub rake_cap: Option<u32>, // Most the house takes from one pot
+    pub no_flop_no_drop: bool, // No rake when the hand ends before the flop
+    pub last_rake: u32, // Rake taken from the most recently settled pot
+    pub last_pot: u32, // Size of the most recently settled pot, before rake
+    pub uncalled_return: Option<(usize, u32)>, // (Player, chips) handed back from a bet nobody matched at the last settlement
+    pub last_walk: Option<u32>, // Chips the big blind collected if the last hand was folded around to them
+    pub chip_increment: u32, // Smallest chip in play: bets and raises are rounded to it (1 = off)
+    pub short_deck: bool, // Six-plus Hold'em: 2s-5s removed, flush beats full house, A-6-7-8-9 is a straight
+    pub allow_straddle: bool, // UTG may post a blind straddle of two big blinds
+    pub bot_straddle_chance: f64, // Probability a bot in UTG straddles (0.0-1.0)
+    pub human_straddle_next: bool, // Human asked to straddle the next time they're UTG
+    pub human_sits_out_next: bool, // Human declined to post their blind: skip the next hand
+    pub straddle_idx: Option<usize>, // Who straddled this hand, if anyone
+    pub first_to_act_idx: usize, // Who opened the action on the current street
+    pub raises_this_street: u32, // Bets and raises made so far on the current street
+    pub hand_contributions: Vec<u32>, // Total chips each player has put in the pot this hand
+    pub side_pot_awards: Vec<(usize, u32)>, // Chips won by players other than the main pot winner (side pots, split shares)
+    pub pot_awards: Vec<PotAward>, // Last showdown's pots in order: main pot, then side pots as they were created
+    pub rng: StdRng, // Card source for shuffling (see reseed for reproducible deals)
+    pub table_rng: RefCell<StdRng>, // Everything else left to chance: the button draw, bot decisions and straddles, chop equity
+    pub chop_pending: bool, // Heads-up all-in involving the human, waiting on a chop decision
+    pub api_stats: ApiStats, // How API bots have been doing this session
+    pub api_limiter: Arc<ApiLimiter>, // Caps concurrent API requests (--ai-concurrency)
+    pub think_aloud: bool, // Ask the model for a one-sentence reason along with each action
+    pub last_bot_reason: Option<String>, // The reason given with the last API decision, until the app logs it
+    pub eval_self_check: bool, // Re-evaluate showdown hands by brute force and warn when rs_poker disagrees (--debug)
+    pub debug_log: Vec<String>, // Engine notes for the message log (drained by the app)
+    pub action_history: Vec<ActionRecord>, // Every action taken this hand, in order
+    pub hand_started_at: Instant, // When the current hand was dealt
+    pub last_action_at: Instant, // When the previous action (or the deal) happened
+    pub stacked_deck: Option<Vec<Card>>, // Cards the next deal hands out first, in dealing order (see stack_deck)
+    pub bot_auto_rebuy: bool, // Busted bots buy back in at the start of the next hand
+    pub max_bot_rebuys: u32, // Rebuys each bot gets
+    pub rebuy_stack: u32, // Chips a rebuy brings a bot back to
+    pub bot_rebuys: HashMap<String, u32>, // Rebuys used so far, keyed by bot name
+    pub hand_number: u32, // Hands dealt this session; the current hand's number once dealt
+    pub next_bot_id: u32, // Id the next bot to sit down gets
+    pub events: Vec<GameEvent>, // What this hand has done since the app last looked (cleared each deal)
+    pub hand_start_chips: Vec<u32>, // Each seat's stack as the hand was dealt, before antes and blinds
 }
 
+/// The betting street the hand is on.
+///
+/// ```
+/// use p_kr::game::{Game, Round};
+///
+/// let mut game = Game::headless(2, 1000, 5, 10);
+/// game.deal_cards();
+/// assert_eq!(game.round, Round::PreFlop);
+/// assert!(game.community_cards.is_empty());
+/// ```
 #[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
 pub enum Round {
     PreFlop,
     Flop,
@@ -139,6 +493,7 @@ pub enum Round {
     Showdown,
 }
 
+#[cfg(feature = "llm")]
 #[derive(Serialize, Deserialize)]
 pub struct OpenAIRequest {
     pub model: String,
@@ -146,23 +501,40 @@ pub struct OpenAIRequest {
     pub temperature: f32,
 }
 
+#[cfg(feature = "llm")]
 #[derive(Serialize, Deserialize)]
 pub struct Message {
     pub role: String,
     pub content: String,
 }
 
+#[cfg(feature = "llm")]
 #[derive(Deserialize)]
 pub struct OpenAIResponse {
     pub choices: Vec<Choice>,
 }
 
+#[cfg(feature = "llm")]
 #[derive(Deserialize)]
 pub struct Choice {
     pub message: Message,
 }
 
+/// A decision for the player to act. `Raise` carries the chips added now, not the new total.
+///
+/// ```
+/// use p_kr::game::{Game, GameAction};
+///
+/// let mut game = Game::headless(3, 1000, 5, 10);
+/// game.deal_cards();
+/// let seat = game.current_player_idx;
+/// let min_to = game.legal_actions().min_raise_to.unwrap();
+/// game.perform_action(GameAction::Raise(min_to));
+/// assert_eq!(game.players[seat].current_bet, min_to);
+/// assert!(matches!(game.action_history.last().unwrap().action, GameAction::Raise(_)));
+/// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameAction {
     Fold,
     Call,
@@ -170,9 +542,195 @@ pub enum GameAction {
     Check,
 }
 
+/// One action in a hand's history. Times are monotonic offsets, so clock changes don't affect them.
+///
+/// ```
+/// use p_kr::game::{Game, GameAction, Round};
+///
+/// let mut game = Game::headless(3, 1000, 5, 10);
+/// game.deal_cards();
+/// let seat = game.current_player_idx;
+/// game.perform_action(GameAction::Call);
+/// let record = &game.action_history[0];
+/// assert_eq!((record.player_idx, record.round), (seat, Round::PreFlop));
+/// assert_eq!(record.bet_total, Some(10));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionRecord {
+    pub player_idx: usize,
+    pub round: Round,
+    pub action: GameAction,  // What was actually played (after any conversions)
+    pub bet_total: Option<u32>, // Player's bet on the street afterwards
+    pub offset_ms: u64,      // Milliseconds since the hand was dealt
+    pub decision_ms: u64,    // Milliseconds since the previous action (or the deal)
+}
+
+// Which forced bet a blind was
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlindKind {
+    Small,
+    Big,
+    Straddle,
+}
+
+// What the hand has done so far, in order, so the UI narrates exactly the chips that moved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    HandStarted { hand_no: u32, dealer: usize },
+    SatOut { seat: usize },                                   // Declined to post; folded for the hand
+    AntesPosted { posted: Vec<(usize, u32)> },                // (Seat, chips) for every seat that paid
+    BlindPosted { seat: usize, kind: BlindKind, amount: u32 }, // Amount actually posted (short stacks post less)
+    ChipsIn { seat: usize, amount: u32 },                     // A call, bet or raise put chips in the pot
+    PotAwarded { seat: usize, amount: u32 },                  // Chips paid out at settlement, after rake
+    BetReturned { seat: usize, amount: u32 },                 // Uncalled part of a bet handed back
+    HumanToAct { to_call: u32 },                              // The action just passed to the human
+}
+
+// Counters for API-driven bot decisions over a session
+#[derive(Clone, Debug, Default)]
+pub struct ApiStats {
+    pub calls: u32,    // Requests sent to the API
+    pub failures: u32, // Requests that failed, falling back to the built-in bots
+    pub raises: u32,   // Raise decisions parsed from responses
+    pub clamped: u32,  // Raises outside the legal range that had to be adjusted
+    pub recent_ms: VecDeque<u64>, // Round-trip times of the latest calls (successful or not), oldest first
+}
+
+// How many API round trips the rolling latency average covers
+#[cfg(feature = "llm")]
+const API_LATENCY_WINDOW: usize = 20;
+
+impl ApiStats {
+    #[cfg(feature = "llm")]
+    fn record_latency(&mut self, ms: u64) {
+        if self.recent_ms.len() == API_LATENCY_WINDOW {
+            self.recent_ms.pop_front();
+        }
+        self.recent_ms.push_back(ms);
+    }
+    
+    // "AI avg: 820ms, last: 1.1s" over the recent calls
+    pub fn latency_line(&self) -> Option<String> {
+        let last = *self.recent_ms.back()?;
+        let avg = self.recent_ms.iter().sum::<u64>() / self.recent_ms.len() as u64;
+        let format_ms = |ms: u64| if ms < 1000 { format!("{}ms", ms) } else { format!("{:.1}s", ms as f64 / 1000.0) };
+        Some(format!("AI avg: {}, last: {}", format_ms(avg), format_ms(last)))
+    }
+}
+
+// API requests allowed in flight at once unless configured otherwise
+pub const DEFAULT_AI_CONCURRENCY: usize = 2;
+
+// Caps how many API requests are in flight at once; callers past the cap wait for a permit.
+// Kept behind an Arc so copies of the game handed to worker threads share the same permits.
+#[derive(Debug)]
+pub struct ApiLimiter {
+    max: usize,
+    in_flight: Mutex<usize>,
+    freed: Condvar,
+}
+
+// One in-flight request; dropping it lets the next waiting caller go
+pub struct ApiPermit<'a> {
+    limiter: &'a ApiLimiter,
+}
+
+impl ApiLimiter {
+    pub fn new(max: usize) -> Self {
+        ApiLimiter { max: max.max(1), in_flight: Mutex::new(0), freed: Condvar::new() }
+    }
+    
+    pub fn max(&self) -> usize {
+        self.max
+    }
+    
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    // Take a slot if one is free right now, without waiting
+    pub fn try_acquire(&self) -> Option<ApiPermit<'_>> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        if *in_flight >= self.max {
+            return None;
+        }
+        *in_flight += 1;
+        Some(ApiPermit { limiter: self })
+    }
+    
+    // Wait until fewer than `max` requests are in flight, then take a slot
+    pub fn acquire(&self) -> ApiPermit<'_> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        while *in_flight >= self.max {
+            in_flight = self.freed.wait(in_flight).unwrap_or_else(|e| e.into_inner());
+        }
+        *in_flight += 1;
+        ApiPermit { limiter: self }
+    }
+}
+
+impl Drop for ApiPermit<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.limiter.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        *in_flight = in_flight.saturating_sub(1);
+        self.limiter.freed.notify_one();
+    }
+}
+
+// A bot's API decision, made on a worker thread from a copy of the game. The copy's counters,
+// notes and reason come back with it so the real game can take them over.
+#[derive(Debug)]
+pub struct ApiDecision {
+    pub action: Result<GameAction, String>,
+    pub api_stats: Option<ApiStats>, // None when the worker never got to make the call
+    pub debug_log: Vec<String>,
+    pub reason: Option<String>,
+}
+
+// An API decision still being fetched, for the seat to act in the given hand
+pub struct PendingBotAction {
+    pub seat: usize,
+    pub hand_number: u32,
+    pub started_at: Instant,
+    receiver: mpsc::Receiver<ApiDecision>,
+}
+
+impl PendingBotAction {
+    pub fn new(seat: usize, hand_number: u32, receiver: mpsc::Receiver<ApiDecision>) -> Self {
+        PendingBotAction { seat, hand_number, started_at: Instant::now(), receiver }
+    }
+    
+    // The decision, once the worker has sent it. A worker that died without answering counts
+    // as a failed call.
+    pub fn poll(&self) -> Option<ApiDecision> {
+        match self.receiver.try_recv() {
+            Ok(decision) => Some(decision),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => Some(ApiDecision {
+                action: Err("API worker stopped without a reply".to_string()),
+                api_stats: None,
+                debug_log: Vec::new(),
+                reason: None,
+            }),
+        }
+    }
+}
+
+// What the player to act is allowed to do right now
+#[derive(Clone, Debug)]
+pub struct LegalActions {
+    pub can_check: bool,
+    pub to_call: u32,                // Chips needed to call (capped at the player's stack)
+    pub min_raise_to: Option<u32>,   // Smallest legal total bet when raising, None if raising isn't allowed
+    pub max_raise_to: Option<u32>,   // Largest total bet (all-in), None if raising isn't allowed
+    pub raises_left: Option<u32>,    // Raises remaining under the street cap (None = unlimited)
+}
+
 // Game implementation
 impl Game {
-    pub fn new(num_human_players: usize, num_bot_players: usize, bot_difficulty: BotDifficulty, starting_chips: u32, api_key: Option<String>, player_name: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(num_human_players: usize, num_bot_players: usize, bot_difficulty: BotDifficulty, starting_chips: u32, small_blind: u32, big_blind: u32, api_key: Option<String>, player_name: String) -> Self {
         let mut players = Vec::new();
         
         // Add human players
@@ -183,8 +741,10 @@ impl Game {
                 chips: starting_chips,
                 current_bet: 0,
                 folded: false,
+                all_in: false,
                 is_bot: false,
                 bot_difficulty: BotDifficulty::Easy, // Unused for human players
+                bot_id: 0,
             });
         }
         
@@ -196,19 +756,23 @@ impl Game {
                 chips: starting_chips,
                 current_bet: 0,
                 folded: false,
+                all_in: false,
                 is_bot: true,
                 bot_difficulty: bot_difficulty.clone(),
+                bot_id: i as u32 + 1,
             });
         }
         
         // Initialize with dealer at random position to ensure all players get different positions
-        let mut rng = thread_rng();
-        let dealer_idx = rng.gen_range(0..players.len());
+        // (drawn again from the seed by reseed)
+        let mut table_rng = StdRng::from_entropy();
+        let dealer_idx = table_rng.gen_range(0..players.len());
         let small_blind_idx = (dealer_idx + 1) % players.len();
         let big_blind_idx = (small_blind_idx + 1) % players.len();
         
         // Create player_contributions_this_round with the same length as players, initialized to 0
         let player_contributions_this_round = vec![0; players.len()];
+        let num_players = players.len();
         
         Game {
             players,
@@ -216,8 +780,10 @@ impl Game {
             community_cards: Vec::new(),
             pot: 0,
             current_player_idx: 0,
-            min_bet: 10, // Small blind
+            small_blind,
+            big_blind,
             round: Round::PreFlop,
+            #[cfg(feature = "llm")]
             ai_client: Client::new(),
             api_key,
             dealer_idx,
@@ -227,9 +793,92 @@ impl Game {
             bb_has_acted_preflop: false,
             players_acted_this_round: Vec::new(),
             last_aggressor: None,
+            river_aggressor: None,
             round_action_complete: false,
             player_contributions_this_round,
+            max_raises_per_street: None,
+            pot_limit: false,
+            ante: 1,
+            button_ante: false,
+            rake_percent: 0,
+            rake_cap: None,
+            no_flop_no_drop: false,
+            last_rake: 0,
+            last_pot: 0,
+            uncalled_return: None,
+            last_walk: None,
+            chip_increment: 1,
+            short_deck: false,
+            allow_straddle: false,
+            bot_straddle_chance: 0.25,
+            human_straddle_next: false,
+            human_sits_out_next: false,
+            straddle_idx: None,
+            first_to_act_idx: 0,
+            raises_this_street: 0,
+            hand_contributions: vec![0; num_players],
+            side_pot_awards: Vec::new(),
+            pot_awards: Vec::new(),
+            rng: StdRng::from_entropy(),
+            table_rng: RefCell::new(table_rng),
+            chop_pending: false,
+            api_stats: ApiStats::default(),
+            api_limiter: Arc::new(ApiLimiter::new(DEFAULT_AI_CONCURRENCY)),
+            think_aloud: false,
+            last_bot_reason: None,
+            eval_self_check: false,
+            debug_log: Vec::new(),
+            action_history: Vec::new(),
+            hand_started_at: Instant::now(),
+            last_action_at: Instant::now(),
+            stacked_deck: None,
+            bot_auto_rebuy: false,
+            max_bot_rebuys: 0,
+            rebuy_stack: starting_chips,
+            bot_rebuys: HashMap::new(),
+            hand_number: 0,
+            next_bot_id: num_bot_players as u32 + 1,
+            events: Vec::new(),
+            hand_start_chips: Vec::new(),
+        }
+    }
+    
+    // A table of `seats` bots and no API key, for driving hands from code (simulations, scripts)
+    pub fn headless(seats: usize, starting_chips: u32, small_blind: u32, big_blind: u32) -> Self {
+        Game::new(0, seats, BotDifficulty::Medium, starting_chips, small_blind, big_blind, None, String::new())
+    }
+    
+    // Script the next deal: `hands[i]` goes to seat i and `board` (up to five cards) comes out
+    // as the flop, turn and river. The rest of the deck is shuffled underneath as usual.
+    pub fn stack_deck(&mut self, hands: &[[Card; 2]], board: &[Card]) {
+        // Hole cards go round the table one at a time, seat 0 first, then the board
+        let mut order: Vec<Card> = hands.iter().map(|hand| hand[0].clone())
+            .chain(hands.iter().map(|hand| hand[1].clone()))
+            .collect();
+        
+        // Each street is preceded by a burn card, taken from the cards not scripted
+        let mut spare: Vec<Card> = self.fresh_deck().into_iter()
+            .filter(|card| !order.contains(card) && !board.contains(card))
+            .collect();
+        for (i, card) in board.iter().enumerate() {
+            if i == 0 || i == 3 || i == 4 {
+                order.extend(spare.pop());
+            }
+            order.push(card.clone());
         }
+        self.stacked_deck = Some(order);
+    }
+    
+    // Reset the card source to a fixed seed so the same sequence of deals can be replayed.
+    // The table's other draws get their own stream from the same seed, so a decision made some
+    // other way (by the API, say) doesn't change the cards that follow. The button is drawn
+    // again from it.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        let table_rng = self.table_rng.get_mut();
+        *table_rng = StdRng::seed_from_u64(seed ^ TABLE_RNG_SALT);
+        self.dealer_idx = table_rng.gen_range(0..self.players.len());
+        self.reset_seat_tracking();
     }
     
     pub fn create_deck() -> Vec<Card> {
@@ -252,12 +901,54 @@ impl Game {
         deck
     }
     
+    // The deck this table plays with: full, or Six-plus with the 2s through 5s taken out
+    pub fn fresh_deck(&self) -> Vec<Card> {
+        let mut deck = Game::create_deck();
+        if self.short_deck {
+            deck.retain(|card| card.rank.value() >= 6);
+        }
+        deck
+    }
+    
     pub fn shuffle_deck(&mut self) {
-        let mut rng = thread_rng();
-        self.deck.shuffle(&mut rng);
+        self.deck.shuffle(&mut self.rng);
+    }
+    
+    // Whether a busted bot would buy back in before the next deal
+    pub fn can_rebuy(&self, idx: usize) -> bool {
+        let player = &self.players[idx];
+        self.bot_auto_rebuy && player.is_bot
+            && self.bot_rebuys.get(&player.name).copied().unwrap_or(0) < self.max_bot_rebuys
+    }
+    
+    // With auto rebuy on, top every busted bot with rebuys left back up to the rebuy stack.
+    // Returns the seats that bought back in.
+    pub fn rebuy_busted_bots(&mut self) -> Vec<usize> {
+        let mut rebought = Vec::new();
+        if !self.bot_auto_rebuy {
+            return rebought;
+        }
+        for (idx, player) in self.players.iter_mut().enumerate() {
+            if !player.is_bot || player.chips > 0 {
+                continue;
+            }
+            let used = self.bot_rebuys.entry(player.name.clone()).or_insert(0);
+            if *used < self.max_bot_rebuys {
+                *used += 1;
+                player.chips = self.rebuy_stack;
+                rebought.push(idx);
+            }
+        }
+        rebought
     }
     
     pub fn deal_cards(&mut self) {
+        // Busted bots rebuy before anything is posted (the app does this itself first to announce them)
+        self.rebuy_busted_bots();
+        self.hand_number += 1;
+        self.events.clear();
+        self.hand_start_chips = self.players.iter().map(|p| p.chips).collect();
+        
         // Reset action counter
         self.last_action_count = 0;
         
@@ -267,31 +958,71 @@ impl Game {
         // Reset round action tracking
         self.players_acted_this_round = Vec::new();
         self.last_aggressor = None;
+        self.river_aggressor = None;
+        self.raises_this_street = 0;
         self.round_action_complete = false;
         
-        // Reset player contributions for the new round
+        // Reset player contributions for the new round (and the whole hand)
         self.player_contributions_this_round = vec![0; self.players.len()];
-        
-        // Rotate positions for the next hand
-        self.dealer_idx = (self.dealer_idx + 1) % self.players.len();
-        self.small_blind_idx = (self.dealer_idx + 1) % self.players.len();
-        self.big_blind_idx = (self.small_blind_idx + 1) % self.players.len();
-        
-        // Clear old hands and reset player state
+        self.hand_contributions = vec![0; self.players.len()];
+        self.side_pot_awards.clear();
+        self.pot_awards.clear();
+        self.chop_pending = false;
+        self.last_rake = 0;
+        self.last_pot = 0;
+        self.uncalled_return = None;
+        self.last_walk = None;
+        self.action_history.clear();
+        self.hand_started_at = Instant::now();
+        self.last_action_at = self.hand_started_at;
+        
+        // Rotate positions for the next hand. The button and blinds pass over busted seats,
+        // which are dealt out below and have nothing to post.
+        self.dealer_idx = self.next_seat_with_chips(self.dealer_idx);
+        self.small_blind_idx = self.next_seat_with_chips(self.dealer_idx);
+        self.big_blind_idx = self.next_seat_with_chips(self.small_blind_idx);
+        self.events.push(GameEvent::HandStarted { hand_no: self.hand_number, dealer: self.dealer_idx });
+        
+        // Clear old hands and reset player state. A seat with no chips left (a busted bot that
+        // can't rebuy) is dealt out: it has nothing to post and no stake in the pot.
         for player in &mut self.players {
             player.hand.clear();
-            player.folded = false;
+            player.folded = player.chips == 0;
+            player.all_in = false;
             player.current_bet = 0;
         }
         
+        // A human who won't post their blind sits the hand out: no ante, no blind, already folded.
+        // A skipped big blind passes to the next seat; a skipped small blind just isn't posted.
+        // Needs three players so there's still a hand to play without them.
+        let sitting_out = if self.human_sits_out_next && self.players.len() >= 3 {
+            self.players.iter().position(|p| !p.is_bot)
+        } else {
+            None
+        };
+        self.human_sits_out_next = false;
+        if let Some(idx) = sitting_out {
+            self.events.push(GameEvent::SatOut { seat: idx });
+            self.players[idx].folded = true;
+            if idx == self.big_blind_idx {
+                self.big_blind_idx = self.next_seat_with_chips(idx);
+            }
+        }
+        
         // Clear community cards and reset game state
         self.community_cards.clear();
         self.pot = 0;
         self.round = Round::PreFlop;
         
         // Create a fresh deck and shuffle it
-        self.deck = Game::create_deck();
+        self.deck = self.fresh_deck();
         self.shuffle_deck();
+        
+        // A scripted deal goes on top, in order (cards come off the end of the deck)
+        if let Some(top) = self.stacked_deck.take() {
+            self.deck.retain(|card| !top.contains(card));
+            self.deck.extend(top.into_iter().rev());
+        }
                 
         // Deal 2 cards to each player
         for _ in 0..2 {
@@ -303,89 +1034,240 @@ impl Game {
         }
         
         // Set up blinds and ante (ensure pot is never zero)
-        // Each player pays a small ante
-        let ante = 1; // 1 chip ante from each player
+        // Each player pays a small ante, or the button covers the whole table's under a button ante
+        // Short stacks only post what they have, so chips are never created
+        let num_players = self.players.len() as u32;
+        let dealer_idx = self.dealer_idx;
+        let button_ante = self.button_ante;
+        let ante = self.ante;
+        let mut antes_posted = Vec::new();
         for (idx, player) in self.players.iter_mut().enumerate() {
-            player.chips = player.chips.saturating_sub(ante);
-            self.pot += ante;
+            let owed = if Some(idx) == sitting_out {
+                0
+            } else if !button_ante {
+                ante
+            } else if idx == dealer_idx {
+                ante * num_players
+            } else {
+                0
+            };
+            let posted = owed.min(player.chips);
+            player.chips -= posted;
+            self.pot += posted;
             // Track the ante contribution
-            self.player_contributions_this_round[idx] += ante;
+            self.player_contributions_this_round[idx] += posted;
+            self.hand_contributions[idx] += posted;
+            if posted > 0 {
+                antes_posted.push((idx, posted));
+            }
         }
+        if !antes_posted.is_empty() {
+            self.events.push(GameEvent::AntesPosted { posted: antes_posted });
+        }
+        self.straddle_idx = None;
         
         if self.players.len() >= 2 {
-            // Small blind (minimum 5)
-            let small_blind = self.min_bet / 2;
-            self.players[self.small_blind_idx].chips = self.players[self.small_blind_idx].chips.saturating_sub(small_blind);
+            // Small blind
+            let small_blind = if Some(self.small_blind_idx) == sitting_out {
+                0
+            } else {
+                self.small_blind.min(self.players[self.small_blind_idx].chips)
+            };
+            self.players[self.small_blind_idx].chips -= small_blind;
             self.players[self.small_blind_idx].current_bet = small_blind;
             self.pot += small_blind;
             // Track the small blind contribution
             self.player_contributions_this_round[self.small_blind_idx] += small_blind;
+            self.hand_contributions[self.small_blind_idx] += small_blind;
+            if small_blind > 0 {
+                self.events.push(GameEvent::BlindPosted { seat: self.small_blind_idx, kind: BlindKind::Small, amount: small_blind });
+            }
             
-            // Big blind (minimum 10)
-            let big_blind = self.min_bet;
-            self.players[self.big_blind_idx].chips = self.players[self.big_blind_idx].chips.saturating_sub(big_blind);
+            // Big blind
+            let big_blind = self.big_blind.min(self.players[self.big_blind_idx].chips);
+            self.players[self.big_blind_idx].chips -= big_blind;
             self.players[self.big_blind_idx].current_bet = big_blind;
             self.pot += big_blind;
             // Track the big blind contribution
             self.player_contributions_this_round[self.big_blind_idx] += big_blind;
+            self.hand_contributions[self.big_blind_idx] += big_blind;
+            self.events.push(GameEvent::BlindPosted { seat: self.big_blind_idx, kind: BlindKind::Big, amount: big_blind });
             
-            // Start with player after big blind (UTG position)
-            self.current_player_idx = (self.big_blind_idx + 1) % self.players.len();
+            // Start with player after big blind (UTG position), passing over a seat that sat
+            // out or went all-in on the ante
+            self.current_player_idx = self.find_next_active_player(self.big_blind_idx)
+                .unwrap_or((self.big_blind_idx + 1) % self.players.len());
+            
+            // UTG may straddle: a blind raise to two big blinds that moves the first action
+            // one seat left and takes over the big blind's last option
+            let utg_idx = self.current_player_idx;
+            if self.wants_to_straddle(utg_idx) {
+                let straddle = (self.big_blind * 2).min(self.players[utg_idx].chips);
+                self.players[utg_idx].chips -= straddle;
+                self.players[utg_idx].current_bet = straddle;
+                self.pot += straddle;
+                self.player_contributions_this_round[utg_idx] += straddle;
+                self.hand_contributions[utg_idx] += straddle;
+                self.straddle_idx = Some(utg_idx);
+                self.events.push(GameEvent::BlindPosted { seat: utg_idx, kind: BlindKind::Straddle, amount: straddle });
+                self.current_player_idx = self.find_next_active_player(utg_idx)
+                    .unwrap_or((utg_idx + 1) % self.players.len());
+            }
+            self.human_straddle_next = false;
+            self.first_to_act_idx = self.current_player_idx;
         }
+        
+        // A short stack can go all-in just posting the ante or a blind
+        for (idx, player) in self.players.iter_mut().enumerate() {
+            if player.chips == 0 && self.hand_contributions[idx] > 0 {
+                player.all_in = true;
+            }
+        }
+        
+        // Catch a corrupted deal here rather than several actions later: a panic in debug builds,
+        // a warning for the debug log otherwise
+        let problems = self.deal_problems();
+        debug_assert!(problems.is_empty(), "illegal deal for hand #{}: {}", self.hand_number, problems.join("; "));
+        for problem in problems {
+            self.debug_log.push(format!("System warning: illegal deal - {}", problem));
+        }
+        self.announce_turn();
     }
     
-    pub fn deal_community_cards(&mut self) {
-        // Ensure we have enough cards in the deck
-        if self.deck.len() < 5 {
-            self.deck = Game::create_deck();
-            self.shuffle_deck();
+    // The next seat after `idx` that still has chips, or simply the next seat if nobody does
+    fn next_seat_with_chips(&self, idx: usize) -> usize {
+        let num_players = self.players.len();
+        (1..=num_players)
+            .map(|offset| (idx + offset) % num_players)
+            .find(|&seat| self.players[seat].chips > 0)
+            .unwrap_or((idx + 1) % num_players)
+    }
+    
+    // Queue a HumanToAct event if the human is now due to make a decision. Called wherever the
+    // action moves to a new seat, so each decision is announced exactly once.
+    fn announce_turn(&mut self) {
+        let player = &self.players[self.current_player_idx];
+        if self.round == Round::Showdown || player.is_bot || player.folded || player.chips == 0 {
+            return;
+        }
+        let to_call = self.legal_actions().to_call;
+        self.events.push(GameEvent::HumanToAct { to_call });
+    }
+    
+    // What's wrong with the state deal_cards just left, if anything
+    fn deal_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for player in &self.players {
+            if player.hand.len() != 2 {
+                problems.push(format!("{} has {} hole cards", player.name, player.hand.len()));
+            }
+        }
+        let expected_deck = self.fresh_deck().len().saturating_sub(2 * self.players.len());
+        if self.deck.len() != expected_deck {
+            problems.push(format!("deck has {} cards, expected {}", self.deck.len(), expected_deck));
+        }
+        let posted: u32 = self.hand_contributions.iter().sum();
+        if self.pot != posted {
+            problems.push(format!("pot ${} but ${} was posted", self.pot, posted));
+        }
+        // The first to act must be able to act, unless nobody can (everyone all-in on the blinds)
+        if self.players.len() >= 2 {
+            let first = &self.players[self.current_player_idx];
+            if first.folded || (first.chips == 0 && self.find_next_active_player(self.current_player_idx).is_some()) {
+                problems.push(format!("{} is first to act but {}", first.name,
+                                      if first.folded { "sat out" } else { "is all-in" }));
+            }
+        }
+        problems
+    }
+    
+    // Whether the player in UTG straddles this hand (needs a full ring so UTG isn't the button)
+    fn wants_to_straddle(&self, utg_idx: usize) -> bool {
+        if !self.allow_straddle || self.players.len() < 4 || self.players[utg_idx].chips <= self.big_blind * 2 {
+            return false;
+        }
+        if self.players[utg_idx].is_bot {
+            self.table_rng.borrow_mut().gen_bool(self.bot_straddle_chance.clamp(0.0, 1.0))
+        } else {
+            self.human_straddle_next
         }
+    }
+    
+    // The most that can change hands between two players: the smaller stack,
+    // counting what each has already bet on this street
+    pub fn effective_stack(&self, a: usize, b: usize) -> u32 {
+        let stack = |idx: usize| self.players[idx].chips + self.players[idx].current_bet;
+        stack(a).min(stack(b))
+    }
+    
+    // The order hands are shown at showdown: the last river bettor or raiser first, or with no
+    // river bet the first player left of the button; then clockwise
+    pub fn showdown_order(&self) -> Vec<usize> {
+        let num_players = self.players.len();
+        let in_hand = |idx: usize| !self.players[idx].folded && self.players[idx].hand.len() >= 2;
+        let first = self.river_aggressor
+            .filter(|&idx| in_hand(idx))
+            .unwrap_or((self.dealer_idx + 1) % num_players.max(1));
+        (0..num_players)
+            .map(|offset| (first + offset) % num_players)
+            .filter(|&idx| in_hand(idx))
+            .collect()
+    }
+    
+    // The player who gets the last preflop option: the straddler if there is one, else the big blind
+    pub fn option_player_idx(&self) -> usize {
+        self.straddle_idx.unwrap_or(self.big_blind_idx)
+    }
+    
+    // Smallest raise increment right now. A straddle counts as a full raise, so preflop raises
+    // must add at least the straddle over the big blind (i.e. two big blinds).
+    pub fn min_raise_increment(&self) -> u32 {
+        if self.round == Round::PreFlop && self.straddle_idx.is_some() {
+            self.big_blind * 2
+        } else {
+            self.big_blind
+        }
+    }
+    
+    // Bring the board up to the count for the current street (flop 3, turn 4, river 5).
+    // Cards already on the board are never replaced: if the count going into the street is off,
+    // that's a bug upstream, so it's reported with the table state and only the gap is dealt.
+    fn fill_board(&mut self, context: &str) {
+        let (before, expected) = match self.round {
+            Round::Flop => (0, 3),
+            Round::Turn => (3, 4),
+            Round::River => (4, 5),
+            _ => return, // No cards dealt in preflop or showdown
+        };
         
-        match self.round {
-            Round::Flop => {
-                // Deal 3 cards for the flop
-                for _ in 0..3 {
-                    if let Some(card) = self.deck.pop() {
-                        self.community_cards.push(card);
-                    }
-                }
-            },
-            Round::Turn => {
-                // Deal 1 card for turn
-                if let Some(card) = self.deck.pop() {
-                    self.community_cards.push(card);
-                }
-            },
-            Round::River => {
-                // Deal 1 card for river
-                if let Some(card) = self.deck.pop() {
-                    self.community_cards.push(card);
-                }
-            },
-            _ => {
-                // No cards dealt in preflop or showdown
-            },
+        let on_board = self.community_cards.len();
+        if on_board != before {
+            let board = self.community_cards.iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.debug_log.push(format!(
+                "System warning: {} found {} board card(s) going into the {:?}, expected {} (board [{}], deck {}, pot ${}). Keeping the existing cards{}.",
+                context, on_board, self.round, before, board, self.deck.len(), self.pot,
+                if on_board < expected { format!(" and dealing {} more", expected - on_board) } else { String::new() }
+            ));
         }
         
-        // Verify cards were dealt
-        if self.round != Round::PreFlop {
-            let expected_count = match self.round {
-                Round::Flop => 3,
-                Round::Turn => 4,
-                Round::River => 5,
-                _ => 0,
-            };
-            
-            // Force correct number of cards if needed
-            if self.community_cards.len() != expected_count {
-                // Start fresh
-                self.community_cards.clear();
-                
-                // Deal appropriate number of cards
-                for _ in 0..expected_count {
-                    if let Some(card) = self.deck.pop() {
-                        self.community_cards.push(card);
-                    }
+        self.deal_board_to(expected);
+    }
+    
+    // Deal streets until the board has `target` cards, burning a card before each one.
+    // next_round (and the run-outs when nobody can act) are the only places the board is dealt.
+    fn deal_board_to(&mut self, target: usize) {
+        while self.community_cards.len() < target {
+            let street_end = if self.community_cards.is_empty() { 3 } else { self.community_cards.len() + 1 };
+            if self.deck.pop().is_none() {
+                break;
+            }
+            while self.community_cards.len() < street_end.min(target) {
+                match self.deck.pop() {
+                    Some(card) => self.community_cards.push(card),
+                    None => return,
                 }
             }
         }
@@ -394,7 +1276,7 @@ impl Game {
     pub fn next_round(&mut self) {
         // Ensure deck is properly set up
         if self.deck.len() < 5 {
-            self.deck = Game::create_deck();
+            self.deck = self.fresh_deck();
             self.shuffle_deck();
         }
         
@@ -404,7 +1286,11 @@ impl Game {
             Round::PreFlop => self.round = Round::Flop,
             Round::Flop => self.round = Round::Turn,
             Round::Turn => self.round = Round::River,
-            Round::River => self.round = Round::Showdown,
+            Round::River => {
+                // Remembered past the street reset below, for the showing order
+                self.river_aggressor = self.last_aggressor;
+                self.round = Round::Showdown;
+            },
             Round::Showdown => {
                 // Start a new hand
                 self.deal_cards();
@@ -441,60 +1327,12 @@ impl Game {
         
         // Handle each round transition properly
         match self.round {
-            Round::Flop => {
-                // For flop, ensure we start fresh
-                self.community_cards.clear();
-                // Deal 3 cards
-                for _ in 0..3 {
-                    if let Some(card) = self.deck.pop() {
-                        self.community_cards.push(card);
-                    }
-                }
+            Round::Flop | Round::Turn | Round::River => {
+                self.fill_board("next_round");
             },
-            Round::Turn => {
-                // Ensure we have flop cards first
-                if self.community_cards.len() < 3 {
-                    self.community_cards.clear();
-                    // Add missing flop cards
-                    for _ in 0..3 {
-                        if let Some(card) = self.deck.pop() {
-                            self.community_cards.push(card);
-                        }
-                    }
-                }
-                // Add turn card
-                if let Some(card) = self.deck.pop() {
-                    self.community_cards.push(card);
-                }
-            },
-            Round::River => {
-                // Ensure we have flop and turn cards
-                if self.community_cards.len() < 4 {
-                    // Missing cards
-                    let missing = 4 - self.community_cards.len();
-                    for _ in 0..missing {
-                        if let Some(card) = self.deck.pop() {
-                            self.community_cards.push(card);
-                        }
-                    }
-                }
-                // Add river card
-                if let Some(card) = self.deck.pop() {
-                    self.community_cards.push(card);
-                }
-            },
-            Round::Showdown => {
-                // Ensure we have all 5 community cards
-                if self.community_cards.len() < 5 {
-                    // Missing cards
-                    let missing = 5 - self.community_cards.len();
-                    for _ in 0..missing {
-                        if let Some(card) = self.deck.pop() {
-                            self.community_cards.push(card);
-                        }
-                    }
-                }
-                // No additional cards dealt in Showdown
+            Round::Showdown => {
+                // Ensure we have all 5 community cards
+                self.deal_board_to(5);
             },
             _ => {}
         }
@@ -505,6 +1343,7 @@ impl Game {
         // Reset action tracking for the new round
         self.players_acted_this_round.clear();
         self.last_aggressor = None;
+        self.raises_this_street = 0;
         self.round_action_complete = false;
         
         // Reset player contributions for the new round
@@ -514,53 +1353,80 @@ impl Game {
         // This ensures all players get a chance to act in each round
         // (This is used for reference but now handled by players_acted_this_round)
         
-        // Reset to first active player after small blind (not after dealer)
+        // Postflop the first live player left of the button acts first (the small blind if still in)
         // For Showdown, we don't need to set the player index as we'll determine winner immediately
         if self.round != Round::Showdown {
-            // Start with the player after small blind in post-flop rounds
-            self.current_player_idx = self.find_next_active_player((self.small_blind_idx) % self.players.len());
+            match self.find_next_active_player(self.dealer_idx) {
+                Some(idx) => {
+                    self.current_player_idx = idx;
+                    self.first_to_act_idx = idx;
+                },
+                // Nobody can act (everyone left is all-in) - run the board out to showdown
+                None => self.next_round(),
+            }
         }
     }
     
-    pub fn find_next_active_player(&self, current_idx: usize) -> usize {
-        let mut idx = (current_idx + 1) % self.players.len();
-        
-        // Find the next player who hasn't folded and has chips
-        let start_idx = idx;
-        loop {
-            if !self.players[idx].folded && self.players[idx].chips > 0 {
-                return idx;
-            }
-            
-            idx = (idx + 1) % self.players.len();
-            
-            // If we've checked all players and come back to where we started,
-            // just return the original index
-            if idx == start_idx {
-                return current_idx;
-            }
+    // Find the next seat after current_idx whose player hasn't folded and still has chips.
+    // Wraps around the table and may return current_idx itself if it's the only live seat.
+    // Returns None when no live player remains.
+    pub fn find_next_active_player(&self, current_idx: usize) -> Option<usize> {
+        let num_players = self.players.len();
+        (1..=num_players)
+            .map(|offset| (current_idx + offset) % num_players)
+            .find(|&idx| !self.players[idx].folded && self.players[idx].chips > 0)
+    }
+    
+    // Close the current betting round: advance to the next street, or report that
+    // the hand is over if we're already at showdown
+    fn finish_betting_round(&mut self) -> bool {
+        if self.round == Round::Showdown {
+            false
+        } else {
+            self.next_round();
+            self.announce_turn();
+            true
         }
     }
     
     pub fn next_player(&mut self) -> bool {
         // STEP 1: Check if the round is over by counting active players
         let active_players = self.players.iter().filter(|p| !p.folded && p.chips > 0).count();
+        let players_in_hand = self.players.iter().filter(|p| !p.folded).count();
+        let highest_bet = self.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
+        
+        // A live player still facing an all-in bet gets to decide before we skip ahead
+        let live_player_owes = self.players.iter()
+            .any(|p| !p.folded && p.chips > 0 && p.current_bet < highest_bet);
+        
+        // Everyone else folded: the hand is over, with no showdown to play out
+        if players_in_hand <= 1 {
+            self.round = Round::Showdown;
+            return false;
+        }
         
-        // If only one player remains, the round (and game) is over
-        if active_players <= 1 {
-            // Special case: only one player left in the hand
+        // If only one player can still bet, no more decisions are possible
+        if active_players <= 1 && !live_player_owes {
             if self.round == Round::Showdown {
                 // End the hand and determine winner
                 return false;
             } else {
-                // Skip to showdown since there's only one player left
+                // Heads-up with the human involved: pause so they can agree to chop instead
+                let human_in_hand = self.players.iter().any(|p| !p.is_bot && !p.folded);
+                if players_in_hand == 2 && human_in_hand && self.community_cards.len() < 5 {
+                    self.chop_pending = true;
+                    return true;
+                }
+                
+                // Everyone left is all-in (e.g. both blinds posted their whole stacks):
+                // deal the full board so the showdown is between real hands
+                self.run_out_board();
                 self.round = Round::Showdown;
                 return true;
             }
         }
         
         // STEP 2: Check if all betting actions are complete for the current round
-        let highest_bet = self.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
         
         // Check if all players have either matched the highest bet, folded, or are all-in
         let bets_matched = self.players.iter()
@@ -570,10 +1436,16 @@ impl Game {
         // Force advancement after too many actions (safeguard)
         let force_advancement = self.last_action_count >= active_players * 3;
         
-        // Special check for PreFlop - ensure big blind has acted
+        // Track if BB (or the straddler) has acted in PreFlop - the player who just acted is
+        // still current, so record it before deciding whether the round is complete
+        if self.round == Round::PreFlop && self.current_player_idx == self.option_player_idx() {
+            self.bb_has_acted_preflop = true;
+        }
+        
+        // Special check for PreFlop - ensure big blind (or the straddler) has used their option
         let bb_rule_satisfied = if self.round == Round::PreFlop {
             // Only consider the round complete if BB has acted
-            self.bb_has_acted_preflop || self.players[self.big_blind_idx].folded
+            self.bb_has_acted_preflop || self.players[self.option_player_idx()].folded
         } else {
             true
         };
@@ -581,24 +1453,16 @@ impl Game {
         // Track the last player who raised (last aggressor)
         // If there's a last aggressor, we need to make sure everyone has acted after them
         let all_acted_after_aggressor = if let Some(aggressor_idx) = self.last_aggressor {
-            // Check if everyone has acted since the last aggressor
-            let mut idx = self.find_next_active_player(aggressor_idx);
-            let mut all_acted = true;
-            
-            while idx != aggressor_idx {
-                // If this player hasn't acted since the last raise AND they're not folded/all-in
-                if !self.players_acted_this_round.contains(&idx) && 
-                   !self.players[idx].folded && 
-                   self.players[idx].chips > 0 {
-                    all_acted = false;
-                    break;
-                }
-                idx = self.find_next_active_player(idx);
-            }
-            
-            all_acted
+            // Check if everyone has acted since the last aggressor. Walk each other seat once
+            // so this terminates even when the aggressor is now all-in.
+            let num_players = self.players.len();
+            (1..num_players)
+                .map(|offset| (aggressor_idx + offset) % num_players)
+                .filter(|&idx| !self.players[idx].folded && self.players[idx].chips > 0)
+                .all(|idx| self.players_acted_this_round.contains(&idx))
         } else {
-            // If no aggressor, check if all active players have acted at least once
+            // If no aggressor, check if all active players have acted at least once. A street
+            // checked around closes on the last live seat's check: nobody acts twice.
             let active_player_indices: Vec<usize> = self.players.iter()
                 .enumerate()
                 .filter(|(_, p)| !p.folded && p.chips > 0)
@@ -612,54 +1476,55 @@ impl Game {
         let round_complete = (bets_matched && bb_rule_satisfied && all_acted_after_aggressor) || force_advancement;
         
         if round_complete {
-            if self.round == Round::Showdown {
-                // Game is over - determine winner and distribute pot
-                return false;
-            } else {
-                // Move to the next round
-                self.next_round();
-                return true;
-            }
+            // Either move to the next round, or the game is over at showdown
+            return self.finish_betting_round();
         }
         
         // STEP 3: Move to the next player who still needs to act
         
-        // Track if BB has acted in PreFlop
-        if self.round == Round::PreFlop && self.current_player_idx == self.big_blind_idx {
-            self.bb_has_acted_preflop = true;
-        }
-        
         // Find the next active player
         if let Some(aggressor_idx) = self.last_aggressor {
-            // If there was a raise, start from after the aggressor to ensure everyone responds
-            let start_idx = self.find_next_active_player(aggressor_idx);
-            self.current_player_idx = start_idx;
-            
-            // Find the first player who still needs to act (after aggressor)
-            while self.players[self.current_player_idx].folded || 
-                  self.players[self.current_player_idx].chips == 0 ||
-                  (self.players_acted_this_round.contains(&self.current_player_idx) && 
-                   self.players[self.current_player_idx].current_bet == highest_bet) {
-                
-                self.current_player_idx = self.find_next_active_player(self.current_player_idx);
+            // If there was a raise, start from after the aggressor to ensure everyone responds.
+            // Find the first live player who still needs to act, visiting each seat at most once.
+            let mut candidate = self.find_next_active_player(aggressor_idx);
+            let mut next_to_act = None;
+            for _ in 0..self.players.len() {
+                let idx = match candidate {
+                    Some(idx) => idx,
+                    None => break,
+                };
                 
                 // If we've looped back to the aggressor, everyone has acted
-                if self.current_player_idx == aggressor_idx {
-                    // Round complete since we've gone full circle
-                    if self.round == Round::Showdown {
-                        return false; // End the hand
-                    } else {
-                        self.next_round();
-                        return true;
-                    }
+                if idx == aggressor_idx {
+                    break;
+                }
+                
+                let already_matched = self.players_acted_this_round.contains(&idx) &&
+                    self.players[idx].current_bet == highest_bet;
+                if !already_matched {
+                    next_to_act = Some(idx);
+                    break;
                 }
+                
+                candidate = self.find_next_active_player(idx);
+            }
+            
+            match next_to_act {
+                Some(idx) => self.current_player_idx = idx,
+                // Round complete since nobody is left to respond
+                None => return self.finish_betting_round(),
             }
         } else {
             // If no aggressor (everyone checked so far), just move to next player
-            self.current_player_idx = self.find_next_active_player(self.current_player_idx);
+            match self.find_next_active_player(self.current_player_idx) {
+                Some(idx) => self.current_player_idx = idx,
+                // No live player left to act - close out the betting round
+                None => return self.finish_betting_round(),
+            }
         }
         
         // Game continues with the next player
+        self.announce_turn();
         true
     }
     
@@ -667,6 +1532,14 @@ impl Game {
         // Get the current player index
         let current_player_idx = self.current_player_idx;
         
+        // Betting is over at showdown: nothing may touch the pot or the stacks any more.
+        // Played as a no-op check, and reported, since reaching here means a caller slipped.
+        if self.round == Round::Showdown {
+            self.debug_log.push(format!("System warning: {} tried to {:?} at showdown - ignored (pot ${}).",
+                                        self.players[current_player_idx].name, action, self.pot));
+            return (GameAction::Check, None);
+        }
+        
         // Calculate highest bet among players
         let highest_bet = self.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
         
@@ -679,6 +1552,17 @@ impl Game {
         // Determine if this is the first bet in this round
         let is_first_bet_in_round = highest_bet == 0;
         
+        // Once the street's raise cap is reached, further raises become calls
+        let action = match action {
+            GameAction::Raise(_) if self.raise_cap_reached() => GameAction::Call,
+            // Under pot-limit, oversized raises are cut down to the pot
+            GameAction::Raise(amount) => match self.pot_limit_max_raise_to() {
+                Some(max_to) => GameAction::Raise(self.round_raise(amount.min(max_to.saturating_sub(player_current_bet)))),
+                None => GameAction::Raise(self.round_raise(amount)),
+            },
+            other => other,
+        };
+        
         // Increment the action counter when a player acts
         // This is critical for proper round management
         self.last_action_count += 1;
@@ -720,6 +1604,7 @@ impl Game {
                     
                     // Update player's contribution for this round
                     self.player_contributions_this_round[current_player_idx] += actual_call;
+                    self.hand_contributions[current_player_idx] += actual_call;
                     
                     // Return the action and the changed values for logging
                     (GameAction::Call, Some(self.players[current_player_idx].current_bet))
@@ -732,7 +1617,7 @@ impl Game {
                     // Don't allow betting more than player has
                     let actual_bet = amount.min(self.players[current_player_idx].chips);
                     
-                    if actual_bet < self.min_bet {
+                    if actual_bet < self.big_blind {
                         // Not enough for minimum bet - convert to check
                         (GameAction::Check, Some(0))
                     } else {
@@ -743,9 +1628,11 @@ impl Game {
                         
                         // Update player's contribution for this round
                         self.player_contributions_this_round[current_player_idx] += actual_bet;
+                        self.hand_contributions[current_player_idx] += actual_bet;
                         
                         // Set this player as the last aggressor
                         self.last_aggressor = Some(current_player_idx);
+                        self.raises_this_street += 1;
                         
                         // Reset acted list to only include this player
                         self.players_acted_this_round.clear();
@@ -756,13 +1643,13 @@ impl Game {
                 } else {
                     // This is a raise (there was a previous bet)
                     // Raising requires at least the minimum bet above current highest
-                    let _min_raise = (highest_bet + self.min_bet).saturating_sub(player_current_bet); // Used in comments for clarity
+                    let _min_raise = (highest_bet + self.min_raise_increment()).saturating_sub(player_current_bet); // Used in comments for clarity
                     
                     // Calculate final bet amount after raise
                     let target_bet = player_current_bet + amount;
                     
                     // Check if the raise amount is sufficient
-                    if target_bet < highest_bet + self.min_bet {
+                    if target_bet < highest_bet + self.min_raise_increment() {
                         // Raise amount too small
                         if highest_bet > player_current_bet {
                             // There's a bet to call
@@ -775,6 +1662,7 @@ impl Game {
                             
                             // Update player's contribution for this round
                             self.player_contributions_this_round[current_player_idx] += actual_call;
+                            self.hand_contributions[current_player_idx] += actual_call;
                             
                             (GameAction::Call, Some(self.players[current_player_idx].current_bet))
                         } else {
@@ -796,9 +1684,11 @@ impl Game {
                         
                         // Update player's contribution for this round
                         self.player_contributions_this_round[current_player_idx] += actual_raise;
+                        self.hand_contributions[current_player_idx] += actual_raise;
                         
                         // Set this player as the last aggressor and reset who has acted
                         self.last_aggressor = Some(current_player_idx);
+                        self.raises_this_street += 1;
                         
                         // Reset acted list to only include this player
                         self.players_acted_this_round.clear();
@@ -823,6 +1713,7 @@ impl Game {
                     
                     // Update player's contribution for this round
                     self.player_contributions_this_round[current_player_idx] += actual_call;
+                    self.hand_contributions[current_player_idx] += actual_call;
                     
                     (GameAction::Call, Some(self.players[current_player_idx].current_bet))
                 } else {
@@ -843,26 +1734,277 @@ impl Game {
                      pot_increase, chip_decrease);
         }
         
+        // Putting the last chip in makes the player all-in for the rest of the hand
+        if chip_decrease > 0 && self.players[current_player_idx].chips == 0 {
+            self.players[current_player_idx].all_in = true;
+        }
+        if chip_decrease > 0 {
+            self.events.push(GameEvent::ChipsIn { seat: current_player_idx, amount: chip_decrease });
+        }
+        
+        // Record it with timing for history and replays
+        let now = Instant::now();
+        self.action_history.push(ActionRecord {
+            player_idx: current_player_idx,
+            round: self.round,
+            action: actual_action.0.clone(),
+            bet_total: actual_action.1,
+            offset_ms: now.duration_since(self.hand_started_at).as_millis() as u64,
+            decision_ms: now.duration_since(self.last_action_at).as_millis() as u64,
+        });
+        self.last_action_at = now;
+        
         // Return the actual action performed
         actual_action
     }
     
+    // Player indices with their chip counts, biggest stack first (ties broken by seat)
+    pub fn standings(&self) -> Vec<(usize, u32)> {
+        let mut standings: Vec<(usize, u32)> = self.players.iter()
+            .enumerate()
+            .map(|(idx, p)| (idx, p.chips))
+            .collect();
+        standings.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        standings
+    }
+    
+    // Seat index of the bot called "Bot N" (the "BN" shown in the header)
+    pub fn seat_of_bot(&self, number: usize) -> Option<usize> {
+        let name = format!("Bot {}", number);
+        self.players.iter().position(|p| p.is_bot && p.name == name)
+    }
+    
+    // Seat a new bot after the last seat, between hands. It takes the lowest free "Bot N" name.
+    pub fn add_bot(&mut self, chips: u32, difficulty: BotDifficulty) -> Result<usize, String> {
+        if self.players.len() >= MAX_SEATS {
+            return Err(format!("The table is full ({} seats).", MAX_SEATS));
+        }
+        let number = (1..).find(|&n| self.seat_of_bot(n).is_none()).unwrap_or(1);
+        self.players.push(Player {
+            name: format!("Bot {}", number),
+            hand: Vec::new(),
+            chips,
+            current_bet: 0,
+            folded: false,
+            all_in: false,
+            is_bot: true,
+            bot_difficulty: difficulty,
+            bot_id: self.next_bot_id,
+        });
+        self.next_bot_id += 1;
+        self.reset_seat_tracking();
+        Ok(self.players.len() - 1)
+    }
+    
+    // Give every bot a new id from `first_id` on: for a table of new bots that joins records
+    // which already used the lower ids
+    pub fn renumber_bots(&mut self, first_id: u32) {
+        self.next_bot_id = first_id.max(1);
+        for player in self.players.iter_mut().filter(|p| p.is_bot) {
+            player.bot_id = self.next_bot_id;
+            self.next_bot_id += 1;
+        }
+    }
+    
+    // Seats were restored from a save: carry on after the highest id in use, and give any bot
+    // saved before ids existed (id 0) a fresh one
+    pub fn settle_bot_ids(&mut self, saved_next_id: u32) {
+        let highest = self.players.iter().map(|p| p.bot_id).max().unwrap_or(0);
+        self.next_bot_id = saved_next_id.max(highest + 1).max(1);
+        for player in self.players.iter_mut().filter(|p| p.is_bot && p.bot_id == 0) {
+            player.bot_id = self.next_bot_id;
+            self.next_bot_id += 1;
+        }
+    }
+    
+    // Take a bot's seat away between hands. The button stays with the same player, or if
+    // it was on the removed seat, goes back one seat so the next deal moves it on as usual.
+    pub fn remove_bot(&mut self, idx: usize) -> Result<Player, String> {
+        if idx >= self.players.len() || !self.players[idx].is_bot {
+            return Err("Only bots can be removed from the table.".to_string());
+        }
+        if self.players.len() <= 2 {
+            return Err("At least one bot has to stay at the table.".to_string());
+        }
+        
+        let removed = self.players.remove(idx);
+        let num_players = self.players.len();
+        if idx < self.dealer_idx {
+            self.dealer_idx -= 1;
+        } else if idx == self.dealer_idx {
+            self.dealer_idx = (idx + num_players - 1) % num_players;
+        }
+        self.reset_seat_tracking();
+        Ok(removed)
+    }
+    
+    // After the seat count changes: recompute the blinds from the button and resize per-seat state
+    pub fn reset_seat_tracking(&mut self) {
+        let num_players = self.players.len();
+        self.dealer_idx %= num_players;
+        self.small_blind_idx = (self.dealer_idx + 1) % num_players;
+        self.big_blind_idx = (self.small_blind_idx + 1) % num_players;
+        self.current_player_idx %= num_players;
+        self.first_to_act_idx %= num_players;
+        self.straddle_idx = None;
+        self.players_acted_this_round.clear();
+        self.last_aggressor = None;
+        self.player_contributions_this_round = vec![0; num_players];
+        self.hand_contributions = vec![0; num_players];
+        self.side_pot_awards.clear();
+        self.pot_awards.clear();
+    }
+    
+    // Whether the per-street raise cap (if any) has been used up
+    pub fn raise_cap_reached(&self) -> bool {
+        match self.max_raises_per_street {
+            Some(cap) => self.raises_this_street >= cap,
+            None => false,
+        }
+    }
+    
+    // Largest total bet allowed under pot-limit for the player to act: call, then raise the size
+    // of the pot. None when playing no-limit.
+    pub fn pot_limit_max_raise_to(&self) -> Option<u32> {
+        if !self.pot_limit {
+            return None;
+        }
+        let player = &self.players[self.current_player_idx];
+        let highest_bet = self.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
+        let to_call = highest_bet.saturating_sub(player.current_bet);
+        Some(highest_bet + self.pot + to_call)
+    }
+    
+    // Round a raise (chips added now) by the current player to the chip increment.
+    // Rounds up rather than dropping under the minimum raise, never past the maximum,
+    // and leaves all-ins exactly as they are.
+    pub fn round_raise(&self, amount: u32) -> u32 {
+        let increment = self.chip_increment.max(1);
+        let player = &self.players[self.current_player_idx];
+        if increment == 1 || amount >= player.chips {
+            return amount;
+        }
+        let legal = self.legal_actions();
+        let (Some(min_to), Some(max_to)) = (legal.min_raise_to, legal.max_raise_to) else {
+            return amount;
+        };
+        
+        let total = player.current_bet + amount;
+        let mut rounded = (total + increment / 2) / increment * increment;
+        if rounded < min_to {
+            rounded = min_to.div_ceil(increment) * increment;
+        }
+        rounded.min(max_to) - player.current_bet
+    }
+    
+    // Compute the legal options for the player whose turn it is
+    pub fn legal_actions(&self) -> LegalActions {
+        let player = &self.players[self.current_player_idx];
+        let highest_bet = self.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
+        let to_call = highest_bet.saturating_sub(player.current_bet).min(player.chips);
+        let raises_left = self.max_raises_per_street
+            .map(|cap| cap.saturating_sub(self.raises_this_street));
+        
+        // Mirrors perform_action: an opening bet must be at least the big blind,
+        // and a raise must add at least a big blind on top of the highest bet
+        let min_raise_to = if highest_bet == 0 {
+            self.big_blind
+        } else {
+            highest_bet + self.min_raise_increment()
+        };
+        let all_in_to = player.current_bet + player.chips;
+        let max_raise_to = match self.pot_limit_max_raise_to() {
+            Some(pot_limit_to) => all_in_to.min(pot_limit_to),
+            None => all_in_to,
+        };
+        let can_raise = raises_left != Some(0) && max_raise_to >= min_raise_to;
+        
+        LegalActions {
+            can_check: to_call == 0,
+            to_call,
+            min_raise_to: if can_raise { Some(min_raise_to) } else { None },
+            max_raise_to: if can_raise { Some(max_raise_to) } else { None },
+            raises_left,
+        }
+    }
+    
+    // Everyone folded preflop without a bet, leaving the big blind (or the straddle) alone
+    fn is_walk(&self, winner_idx: usize) -> bool {
+        self.community_cards.is_empty()
+            && winner_idx == self.option_player_idx()
+            && self.action_history.iter().all(|a| matches!(a.action, GameAction::Fold))
+    }
+    
+    // Give the top contributor back whatever nobody else put in to match, before the pot is
+    // split or raked. Folded players' chips count as matched: they called before letting go.
+    fn return_uncalled_bet(&mut self) {
+        self.uncalled_return = None;
+        let Some((top_idx, &top)) = self.hand_contributions.iter().enumerate().max_by_key(|&(_, c)| c) else {
+            return;
+        };
+        let matched = self.hand_contributions.iter()
+            .enumerate()
+            .filter(|&(idx, _)| idx != top_idx)
+            .map(|(_, &c)| c)
+            .max()
+            .unwrap_or(0);
+        let refund = top.saturating_sub(matched).min(self.pot);
+        if refund == 0 {
+            return;
+        }
+        self.hand_contributions[top_idx] -= refund;
+        self.pot -= refund;
+        self.players[top_idx].chips += refund;
+        self.uncalled_return = Some((top_idx, refund));
+        self.events.push(GameEvent::BetReturned { seat: top_idx, amount: refund });
+    }
+    
     pub fn determine_winner(&mut self) -> (usize, u32, String) {
+        self.last_pot = self.pot;
+        
         // Get active (non-folded) players
         let active_players: Vec<usize> = self.players.iter()
             .enumerate()
             .filter(|(_, player)| !player.folded)
             .map(|(idx, _)| idx)
             .collect();
+        
+        // A card showing up twice means the deck was corrupted; evaluate_hand skips the
+        // repeats, but flag it so the result isn't trusted blindly
+        if active_players.len() > 1 {
+            for &idx in &active_players {
+                let duplicates = self.duplicate_cards(idx);
+                if !duplicates.is_empty() {
+                    let cards = duplicates.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+                    self.debug_log.push(format!("System warning: hand #{}: duplicate card(s) {} between {}'s hand and the board - ignoring the repeats",
+                                                self.hand_number, cards, self.players[idx].name));
+                }
+            }
+        }
+        
+        // With --debug, a second opinion on every hand from an independent evaluator
+        if self.eval_self_check && active_players.len() > 1 {
+            for warning in evalcheck::cross_check(self) {
+                self.debug_log.push(format!("System warning: {}", warning));
+            }
+        }
+        
+        // The part of the biggest bet nobody matched was never really in the pot
+        self.return_uncalled_bet();
             
-        // If only one player remains, they win
+        // If only one player remains, they win (less the house's cut)
         if active_players.len() == 1 {
             let winner_idx = active_players[0];
-            let winnings = self.pot;
+            self.last_rake = self.rake_for_pot();
+            let winnings = self.pot - self.last_rake;
             self.players[winner_idx].chips += winnings;
+            self.events.push(GameEvent::PotAwarded { seat: winner_idx, amount: winnings });
             
             // Define a simple hand type for display
-            let hand_type = if self.players[winner_idx].hand.is_empty() {
+            let hand_type = if self.is_walk(winner_idx) {
+                self.last_walk = Some(winnings);
+                "a walk".to_string()
+            } else if self.players[winner_idx].hand.is_empty() {
                 "by default (others folded)".to_string()
             } else if self.community_cards.is_empty() {
                 "with their hole cards".to_string()
@@ -871,163 +2013,92 @@ impl Game {
             };
             
             self.pot = 0;
+            self.hand_contributions = vec![0; self.players.len()];
+            self.side_pot_awards.clear();
+            self.pot_awards.clear();
             return (winner_idx, winnings, hand_type);
         }
         
-        // If more than one player, determine best hand
-        let mut best_rank_value = 0;
-        let mut best_actual_hand = None;
-        let mut winner_idx = active_players[0]; // Default to first active player
-        let mut winner_hand_type = "High Card".to_string();
+        // If more than one player, split the pot into layers by how much each remaining
+        // player committed this hand, so a short all-in can only win what they matched.
+        // Each layer goes to the best hand among the players who paid into it. A seat that put
+        // nothing in (dealt in with no chips) has no stake in any layer.
+        let mut levels: Vec<u32> = active_players.iter()
+            .map(|&idx| self.hand_contributions[idx])
+            .filter(|&level| level > 0)
+            .collect();
+        levels.sort();
+        levels.dedup();
+        
+        let mut layers: Vec<(u32, Vec<usize>)> = Vec::new(); // (Chips, eligible seats)
+        let mut previous_level = 0;
+        for level in levels {
+            // Chips each player put in between the previous level and this one
+            let layer: u32 = self.hand_contributions.iter()
+                .map(|&c| c.min(level).saturating_sub(previous_level))
+                .sum();
+            let eligible: Vec<usize> = active_players.iter()
+                .copied()
+                .filter(|&idx| self.hand_contributions[idx] >= level)
+                .collect();
+            layers.push((layer, eligible));
+            previous_level = level;
+        }
         
-        // Use poker hand evaluator to find winner
-        for &player_idx in &active_players {
-            // We combine player's hole cards with community cards
-            let player_cards = &self.players[player_idx].hand;
-            
-            // Try to convert our cards to poker-rs format
-            if !player_cards.is_empty() && !self.community_cards.is_empty() {
-                let mut all_cards = Vec::new();
-                
-                // Process player cards
-                for card in player_cards {
-                    // Convert our rank to poker-rs Value
-                    let poker_value = match card.rank {
-                        Rank::Two => PokerValue::Two,
-                        Rank::Three => PokerValue::Three,
-                        Rank::Four => PokerValue::Four,
-                        Rank::Five => PokerValue::Five,
-                        Rank::Six => PokerValue::Six,
-                        Rank::Seven => PokerValue::Seven,
-                        Rank::Eight => PokerValue::Eight,
-                        Rank::Nine => PokerValue::Nine,
-                        Rank::Ten => PokerValue::Ten,
-                        Rank::Jack => PokerValue::Jack,
-                        Rank::Queen => PokerValue::Queen,
-                        Rank::King => PokerValue::King,
-                        Rank::Ace => PokerValue::Ace,
-                    };
-                    
-                    // Convert our suit to poker-rs Suit
-                    let poker_suit = match card.suit {
-                        Suit::Hearts => PokerSuit::Heart,
-                        Suit::Diamonds => PokerSuit::Diamond,
-                        Suit::Clubs => PokerSuit::Club,
-                        Suit::Spades => PokerSuit::Spade,
-                    };
-                    
-                    all_cards.push(PokerCard { value: poker_value, suit: poker_suit });
-                }
-                
-                // Process community cards
-                for card in &self.community_cards {
-                    // Convert our rank to poker-rs Value
-                    let poker_value = match card.rank {
-                        Rank::Two => PokerValue::Two,
-                        Rank::Three => PokerValue::Three,
-                        Rank::Four => PokerValue::Four,
-                        Rank::Five => PokerValue::Five,
-                        Rank::Six => PokerValue::Six,
-                        Rank::Seven => PokerValue::Seven,
-                        Rank::Eight => PokerValue::Eight,
-                        Rank::Nine => PokerValue::Nine,
-                        Rank::Ten => PokerValue::Ten,
-                        Rank::Jack => PokerValue::Jack,
-                        Rank::Queen => PokerValue::Queen,
-                        Rank::King => PokerValue::King,
-                        Rank::Ace => PokerValue::Ace,
-                    };
-                    
-                    // Convert our suit to poker-rs Suit
-                    let poker_suit = match card.suit {
-                        Suit::Hearts => PokerSuit::Heart,
-                        Suit::Diamonds => PokerSuit::Diamond,
-                        Suit::Clubs => PokerSuit::Club,
-                        Suit::Spades => PokerSuit::Spade,
-                    };
-                    
-                    all_cards.push(PokerCard { value: poker_value, suit: poker_suit });
-                }
-                
-                // Create a hand with all cards (player's + community)
-                let hand = Hand::new_with_cards(all_cards);
-                
-                // Evaluate the hand to get best 5-card hand
-                let hand_rank = hand.rank();
-                
-                // Get numerical rank value for comparison
-                let rank_value = match hand_rank {
-                    PokerRank::HighCard(_) => 0,
-                    PokerRank::OnePair(_) => 1,
-                    PokerRank::TwoPair(_) => 2,
-                    PokerRank::ThreeOfAKind(_) => 3,
-                    PokerRank::Straight(_) => 4,
-                    PokerRank::Flush(_) => 5,
-                    PokerRank::FullHouse(_) => 6,
-                    PokerRank::FourOfAKind(_) => 7,
-                    PokerRank::StraightFlush(_) => 8,
-                };
-
-                // If this player has a better hand or this is the first player we're checking
-                if rank_value > best_rank_value || best_actual_hand.is_none() {
-                    // Update best rank and winner
-                    best_rank_value = rank_value;
-                    best_actual_hand = Some(hand_rank.clone());
-                    winner_idx = player_idx;
-                    
-                    // Update the hand type string based on the rank
-                    winner_hand_type = match hand_rank {
-                        PokerRank::HighCard(_) => "High Card".to_string(),
-                        PokerRank::OnePair(_) => "Pair".to_string(),
-                        PokerRank::TwoPair(_) => "Two Pair".to_string(),
-                        PokerRank::ThreeOfAKind(_) => "Three of a Kind".to_string(),
-                        PokerRank::Straight(_) => "Straight".to_string(),
-                        PokerRank::Flush(_) => "Flush".to_string(),
-                        PokerRank::FullHouse(_) => "Full House".to_string(),
-                        PokerRank::FourOfAKind(_) => "Four of a Kind".to_string(),
-                        PokerRank::StraightFlush(_) => "Straight Flush".to_string(),
-                    };
-                } 
-                // In case of a tie in hand rank category, we need to compare the actual hands
-                // rs_poker's Rankable trait handles this by implementing PartialOrd
-                else if rank_value == best_rank_value && best_actual_hand.is_some() {
-                    if hand_rank > *best_actual_hand.as_ref().unwrap() {
-                        best_actual_hand = Some(hand_rank.clone());
-                        winner_idx = player_idx;
-                        
-                        // Update the hand type string based on the rank
-                        winner_hand_type = match hand_rank {
-                            PokerRank::HighCard(_) => "High Card".to_string(),
-                            PokerRank::OnePair(_) => "Pair".to_string(),
-                            PokerRank::TwoPair(_) => "Two Pair".to_string(),
-                            PokerRank::ThreeOfAKind(_) => "Three of a Kind".to_string(),
-                            PokerRank::Straight(_) => "Straight".to_string(),
-                            PokerRank::Flush(_) => "Flush".to_string(),
-                            PokerRank::FullHouse(_) => "Full House".to_string(),
-                            PokerRank::FourOfAKind(_) => "Four of a Kind".to_string(),
-                            PokerRank::StraightFlush(_) => "Straight Flush".to_string(),
-                        };
-                    }
-                }
-            } else if player_cards.len() >= 2 {
-                // If we only have hole cards (no community cards), just check for a pair
-                if player_cards[0].rank == player_cards[1].rank {
-                    // Only update if the current best hand is worse than a pair
-                    if best_rank_value < 1 {
-                        best_rank_value = 1; // Pair
-                        winner_idx = player_idx;
-                        winner_hand_type = "Pair".to_string();
-                    }
-                } else {
-                    // High card - only update if we haven't found anything better yet
-                    if best_rank_value == 0 && best_actual_hand.is_none() {
-                        winner_idx = player_idx;
-                        winner_hand_type = "High Card".to_string();
-                    }
+        // Dead money from folded players above the highest remaining stake belongs to the top layer
+        let distributed: u32 = layers.iter().map(|(amount, _)| amount).sum();
+        if distributed < self.pot {
+            let dead_money = self.pot - distributed;
+            match layers.last_mut() {
+                Some(layer) => layer.0 += dead_money,
+                None => layers.push((dead_money, active_players.clone())),
+            }
+        } else if distributed > self.pot {
+            // Contribution tracking is out of sync with the pot (it shouldn't be) - fall back
+            // to one pot for everyone still in rather than creating chips
+            layers = vec![(self.pot, active_players.clone())];
+        }
+        
+        // The house takes its cut from the main pot first, then the side pots in order
+        let rake = self.rake_for_pot();
+        let mut rake_left = rake;
+        for layer in layers.iter_mut() {
+            let cut = layer.0.min(rake_left);
+            layer.0 -= cut;
+            rake_left -= cut;
+        }
+        self.last_rake = rake - rake_left;
+        
+        // Each layer is split evenly between its tied best hands; odd chips go to the tied seat
+        // closest to the button's left
+        let mut awards: Vec<(usize, u32)> = Vec::new();
+        let mut pots: Vec<PotAward> = Vec::new();
+        for (amount, eligible) in layers {
+            let (mut winners, hand) = self.best_hands_among(&eligible);
+            winners.sort_by_key(|&idx| self.seats_left_of_button(idx));
+            let share = amount / winners.len() as u32;
+            let odd_chips = amount % winners.len() as u32;
+            let shares: Vec<(usize, u32)> = winners.iter()
+                .enumerate()
+                .map(|(i, &idx)| (idx, share + if (i as u32) < odd_chips { 1 } else { 0 }))
+                .collect();
+            for &(idx, chips) in &shares {
+                match awards.iter_mut().find(|(seat, _)| *seat == idx) {
+                    Some(award) => award.1 += chips,
+                    None => awards.push((idx, chips)),
                 }
             }
+            if amount > 0 {
+                pots.push(PotAward { winners: shares, amount, hand, contenders: eligible });
+            }
         }
         
+        // The main pot's first winner is the one reported
+        let (winner_idx, winner_hand_type) = pots.first()
+            .map(|pot| (pot.winners[0].0, pot.hand.clone()))
+            .unwrap_or_else(|| (active_players[0], "High Card".to_string()));
+        self.pot_awards = pots;
+        
         // Create a descriptive string for the winning hand
         let card_description = if !self.community_cards.is_empty() && !self.players[winner_idx].hand.is_empty() {
             // Get the winner's hole cards
@@ -1042,7 +2113,7 @@ impl Game {
                     // For a pair, show what the pair is if possible
                     let pair_in_hole = self.players[winner_idx].hand[0].rank == self.players[winner_idx].hand[1].rank;
                     if pair_in_hole {
-                        format!("Pair of {}s", self.players[winner_idx].hand[0].rank.to_string())
+                        format!("Pair of {}s", self.players[winner_idx].hand[0].rank)
                     } else {
                         // The pair includes one card from the community cards
                         format!("Pair with {}", hole_cards)
@@ -1057,7 +2128,7 @@ impl Game {
                         self.players[winner_idx].hand[0].suit == self.players[winner_idx].hand[1].suit;
                     
                     if same_suit {
-                        format!("Flush ({}) with {}", self.players[winner_idx].hand[0].suit.to_string(), hole_cards)
+                        format!("Flush ({}) with {}", self.players[winner_idx].hand[0].suit, hole_cards)
                     } else {
                         format!("Flush with {}", hole_cards)
                     }
@@ -1069,103 +2140,798 @@ impl Game {
             }
         } else {
             // Fallback if we don't have cards to show
-            format!("{}", winner_hand_type)
+            winner_hand_type.clone()
         };
         
-        let winnings = self.pot;
-        self.players[winner_idx].chips += winnings;
+        // Pay out every layer; report the main pot winner and record what everyone else won
+        // (side pots and shares of a split)
+        for &(idx, amount) in &awards {
+            self.players[idx].chips += amount;
+            if amount > 0 {
+                self.events.push(GameEvent::PotAwarded { seat: idx, amount });
+            }
+        }
+        let winnings = awards.iter()
+            .find(|(idx, _)| *idx == winner_idx)
+            .map(|(_, amount)| *amount)
+            .unwrap_or(0);
+        self.side_pot_awards = awards.into_iter()
+            .filter(|(idx, _)| *idx != winner_idx)
+            .collect();
         self.pot = 0;
+        self.hand_contributions = vec![0; self.players.len()];
         
         (winner_idx, winnings, card_description)
     }
     
-    pub fn get_bot_action(&self, bot_player: &Player) -> Result<GameAction, String> {
-        // Generate bot actions based on difficulty
-        let action_str = self.generate_random_bot_action(bot_player);
+    // Convert one of our cards to rs_poker's representation
+    pub(crate) fn to_poker_card(card: &Card) -> PokerCard {
+        let value = match card.rank {
+            Rank::Two => PokerValue::Two,
+            Rank::Three => PokerValue::Three,
+            Rank::Four => PokerValue::Four,
+            Rank::Five => PokerValue::Five,
+            Rank::Six => PokerValue::Six,
+            Rank::Seven => PokerValue::Seven,
+            Rank::Eight => PokerValue::Eight,
+            Rank::Nine => PokerValue::Nine,
+            Rank::Ten => PokerValue::Ten,
+            Rank::Jack => PokerValue::Jack,
+            Rank::Queen => PokerValue::Queen,
+            Rank::King => PokerValue::King,
+            Rank::Ace => PokerValue::Ace,
+        };
         
-        // Parse the action string
-        if action_str.starts_with("fold") {
-            Ok(GameAction::Fold)
-        } else if action_str.starts_with("call") {
-            Ok(GameAction::Call)
-        } else if action_str.starts_with("check") {
-            Ok(GameAction::Check)
-        } else if action_str.starts_with("raise") {
-            // Extract the raise amount
-            let parts: Vec<&str> = action_str.split_whitespace().collect();
-            if parts.len() >= 2 {
-                if let Ok(amount) = parts[1].parse::<u32>() {
-                    Ok(GameAction::Raise(amount))
-                } else {
-                    // Default raise amount
-                    Ok(GameAction::Raise(self.min_bet))
-                }
-            } else {
-                // Default raise amount
-                Ok(GameAction::Raise(self.min_bet))
+        let suit = match card.suit {
+            Suit::Hearts => PokerSuit::Heart,
+            Suit::Diamonds => PokerSuit::Diamond,
+            Suit::Clubs => PokerSuit::Club,
+            Suit::Spades => PokerSuit::Spade,
+        };
+        
+        PokerCard { value, suit }
+    }
+    
+    // Evaluate a player's best hand from their hole cards plus the board.
+    // Returns None before there's a board, since hole cards alone can't be ranked.
+    pub fn evaluate_hand(&self, player_idx: usize) -> Option<PokerRank> {
+        let hole_cards = &self.players[player_idx].hand;
+        if hole_cards.is_empty() || self.community_cards.is_empty() {
+            return None;
+        }
+        
+        // Drop repeated cards rather than hand duplicates to the evaluator
+        let mut unique_cards: Vec<&Card> = Vec::with_capacity(7);
+        for card in hole_cards.iter().chain(self.community_cards.iter()) {
+            if !unique_cards.contains(&card) {
+                unique_cards.push(card);
             }
-        } else {
-            // Default to checking
-            Ok(GameAction::Check)
         }
+        
+        let all_cards: Vec<PokerCard> = unique_cards.into_iter()
+            .map(Game::to_poker_card)
+            .collect();
+        Some(self.rank_cards(all_cards))
     }
     
-    pub fn make_openai_api_call(&self, api_key: &str, request: &OpenAIRequest) -> Result<String, String> {
-        let client = &self.ai_client;
+    // rs_poker's verdict on five to seven cards, plus the Six-plus A-6-7-8-9 straight it
+    // doesn't know about when playing short deck
+    pub fn rank_cards(&self, cards: Vec<PokerCard>) -> PokerRank {
+        let rank = Hand::new_with_cards(cards.clone()).rank();
+        if !self.short_deck {
+            return rank;
+        }
         
-        // First attempt to send the request
-        let response_result = client.post("https://api.openai.com/v1/chat/completions")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Bearer {}", api_key))
-            .json(request)
-            .send();
-            
-        // Handle HTTP request errors
-        let response = match response_result {
-            Ok(resp) => resp,
-            Err(e) => {
-                return Err(format!("Network error: {}", e));
+        // The Ace plays low below the Six, so A-6-7-8-9 ranks where 5-6-7-8-9 would:
+        // ask the evaluator for that straight (suited if all five share a suit)
+        let low_ranks = [PokerValue::Ace, PokerValue::Six, PokerValue::Seven, PokerValue::Eight, PokerValue::Nine];
+        let stand_in = |value: PokerValue| if value == PokerValue::Ace { PokerValue::Five } else { value };
+        let suits = [PokerSuit::Spade, PokerSuit::Club, PokerSuit::Heart, PokerSuit::Diamond];
+        let suited = suits.iter().find(|&&suit| {
+            low_ranks.iter().all(|&value| cards.iter().any(|c| c.value == value && c.suit == suit))
+        });
+        let low_straight = match suited {
+            Some(&suit) => low_ranks.iter().map(|&value| PokerCard { value: stand_in(value), suit }).collect(),
+            None if low_ranks.iter().all(|&value| cards.iter().any(|c| c.value == value)) => {
+                // Mixed suits: any non-matching suit for the stand-in Five keeps it from reading as a flush
+                low_ranks.iter().enumerate()
+                    .map(|(i, &value)| PokerCard { value: stand_in(value), suit: suits[i % 2] })
+                    .collect()
             }
+            None => return rank,
         };
-        
-        // Try to parse the JSON response
-        let json_result = response.json::<OpenAIResponse>();
-        
-        match json_result {
-            Ok(parsed) => {
-                if let Some(choice) = parsed.choices.first() {
-                    Ok(choice.message.content.clone())
+        let low_rank = Hand::new_with_cards(low_straight).rank();
+        if self.compare_ranks(&low_rank, &rank) == cmp::Ordering::Greater { low_rank } else { rank }
+    }
+    
+    // Order two evaluated hands. Short deck has fewer cards of each suit, so a flush is
+    // rarer than a full house there and the two swap places.
+    pub fn compare_ranks(&self, a: &PokerRank, b: &PokerRank) -> cmp::Ordering {
+        if self.short_deck {
+            let category = |rank: &PokerRank| match rank {
+                PokerRank::Flush(_) => 6,
+                PokerRank::FullHouse(_) => 5,
+                PokerRank::HighCard(_) => 0,
+                PokerRank::OnePair(_) => 1,
+                PokerRank::TwoPair(_) => 2,
+                PokerRank::ThreeOfAKind(_) => 3,
+                PokerRank::Straight(_) => 4,
+                PokerRank::FourOfAKind(_) => 7,
+                PokerRank::StraightFlush(_) => 8,
+            };
+            let by_category = category(a).cmp(&category(b));
+            if by_category != cmp::Ordering::Equal {
+                return by_category;
+            }
+        }
+        a.cmp(b)
+    }
+    
+    // A player's best five with its rank, once there's a board to play with
+    pub fn best_hand(&self, player_idx: usize) -> Option<BestHand> {
+        let rank = self.evaluate_hand(player_idx)?;
+        let player = &self.players[player_idx];
+        Some(BestHand {
+            owner: if player.is_bot { player.name.clone() } else { "You".to_string() },
+            rank,
+            cards: self.best_five_cards(player_idx),
+        })
+    }
+    
+    // Every hand still live at showdown, best first, as numbered lines. Tied hands share a place,
+    // and a hand that only lost on a kicker says which card decided it.
+    // "1. Bot 2: Pair of Aces, K-Q-9 kickers" / "2. You: Pair of Aces, K-J-9 kickers - Bot 2's Q kicker beats your J"
+    pub fn showdown_ranking(&self) -> Vec<String> {
+        let mut hands: Vec<BestHand> = (0..self.players.len())
+            .filter(|&idx| !self.players[idx].folded)
+            .filter_map(|idx| self.best_hand(idx))
+            .collect();
+        if hands.len() < 2 {
+            return Vec::new();
+        }
+        hands.sort_by(|a, b| self.compare_ranks(&b.rank, &a.rank));
+        
+        let mut lines = Vec::new();
+        let mut place = 1;
+        for (i, hand) in hands.iter().enumerate() {
+            let mut line = String::new();
+            if i > 0 {
+                let above = &hands[i - 1];
+                if self.compare_ranks(&above.rank, &hand.rank) == cmp::Ordering::Equal {
+                    line = " - ties".to_string();
                 } else {
-                    Err("No choices returned from API".to_string())
+                    place = i + 1;
+                    if above.describe() == hand.describe() {
+                        if let Some((better, worse)) = deciding_kicker(above, hand) {
+                            line = format!(" - {} {} kicker beats {} {}", above.possessive(false), better,
+                                           hand.possessive(false), worse);
+                        }
+                    }
                 }
-            },
-            Err(_e) => {
-                // Return a friendly error message instead of the raw error
-                Err(format!("API response error (using fallback)"))
             }
+            lines.push(format!("{}. {}: {}{}", place, hand.owner, hand.describe_with_kickers(), line));
         }
+        lines
     }
     
-    pub fn generate_random_bot_action(&self, player: &Player) -> String {
-        let mut rng = rand::thread_rng();
-        
-        // Check if the player has enough chips to make meaningful bets
-        let has_chips = player.chips >= self.min_bet;
-        
-        // Reduce raising probability based on action count to prevent infinite loops
-        let raise_penalty = (self.last_action_count as f32 * 0.5).min(8.0) as u32;
-        
-        // If we're in later rounds or have many actions, bots should be more conservative
-        let is_late_round = self.round == Round::Turn || self.round == Round::River;
+    // The five cards (hole cards and board) that make up a player's best hand, for showing
+    // exactly what won. Fewer than five cards available means all of them play.
+    pub fn best_five_cards(&self, player_idx: usize) -> Vec<Card> {
+        let mut cards: Vec<Card> = Vec::with_capacity(7);
+        for card in self.players[player_idx].hand.iter().chain(self.community_cards.iter()) {
+            if !cards.contains(card) {
+                cards.push(card.clone());
+            }
+        }
+        if cards.len() <= 5 {
+            return cards;
+        }
         
-        match player.bot_difficulty {
-            BotDifficulty::Easy => {
-                // Easy bots mostly check/call, occasionally raise, and rarely fold
-                let mut choice: i32 = rng.gen_range(0..10);
-                
-                // Adjust choice based on round and action count
-                if is_late_round || self.last_action_count > 10 {
-                    choice = choice.saturating_add(2); // Make raising less likely
+        // At most 21 combinations for seven cards
+        let n = cards.len();
+        let mut best: Option<(PokerRank, [usize; 5])> = None;
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let picked = [a, b, c, d, e];
+                            let rank = self.rank_cards(picked.iter().map(|&i| Game::to_poker_card(&cards[i])).collect());
+                            if best.as_ref().is_none_or(|(best_rank, _)| self.compare_ranks(&rank, best_rank) == cmp::Ordering::Greater) {
+                                best = Some((rank, picked));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        best.map(|(_, picked)| picked.iter().map(|&i| cards[i].clone()).collect())
+            .unwrap_or_default()
+    }
+    
+    // Cards that appear more than once among a player's hole cards and the board
+    pub fn duplicate_cards(&self, player_idx: usize) -> Vec<Card> {
+        let mut seen: Vec<&Card> = Vec::with_capacity(7);
+        let mut duplicates = Vec::new();
+        for card in self.players[player_idx].hand.iter().chain(self.community_cards.iter()) {
+            if seen.contains(&card) {
+                if !duplicates.contains(card) {
+                    duplicates.push(card.clone());
+                }
+            } else {
+                seen.push(card);
+            }
+        }
+        duplicates
+    }
+    
+    // Display name for an evaluated hand category
+    pub fn hand_type_name(hand_rank: &PokerRank) -> &'static str {
+        match hand_rank {
+            PokerRank::HighCard(_) => "High Card",
+            PokerRank::OnePair(_) => "Pair",
+            PokerRank::TwoPair(_) => "Two Pair",
+            PokerRank::ThreeOfAKind(_) => "Three of a Kind",
+            PokerRank::Straight(_) => "Straight",
+            PokerRank::Flush(_) => "Flush",
+            PokerRank::FullHouse(_) => "Full House",
+            PokerRank::FourOfAKind(_) => "Four of a Kind",
+            PokerRank::StraightFlush(_) => "Straight Flush",
+        }
+    }
+    
+    // Pick the best hand among the given players, returning the winner and their hand type.
+    // Ties go to the earlier seat in the list.
+    // Every candidate holding the best hand (more than one on a tie), and its type
+    pub fn best_hands_among(&self, candidates: &[usize]) -> (Vec<usize>, String) {
+        let (best_idx, hand_type) = self.best_hand_among(candidates);
+        let Some(best_rank) = self.evaluate_hand(best_idx) else {
+            return (vec![best_idx], hand_type);
+        };
+        let tied = candidates.iter()
+            .copied()
+            .filter(|&idx| idx == best_idx || self.evaluate_hand(idx)
+                .is_some_and(|rank| self.compare_ranks(&rank, &best_rank) == cmp::Ordering::Equal))
+            .collect();
+        (tied, hand_type)
+    }
+    
+    // How many seats clockwise from the button a seat is (the small blind is 1, the button last)
+    fn seats_left_of_button(&self, idx: usize) -> usize {
+        let n = self.players.len();
+        (idx + n - self.dealer_idx - 1) % n
+    }
+    
+    pub fn best_hand_among(&self, candidates: &[usize]) -> (usize, String) {
+        let mut winner_idx = candidates[0];
+        let mut winner_hand_type = "High Card".to_string();
+        let mut best_rank: Option<PokerRank> = None;
+        let mut best_is_pocket_pair = false;
+        
+        for &player_idx in candidates {
+            if let Some(hand_rank) = self.evaluate_hand(player_idx) {
+                // Category first, then kickers (with short deck's flush/full house swap)
+                let is_better = match &best_rank {
+                    Some(best) => self.compare_ranks(&hand_rank, best) == cmp::Ordering::Greater,
+                    None => true,
+                };
+                if is_better {
+                    winner_hand_type = Game::hand_type_name(&hand_rank).to_string();
+                    best_rank = Some(hand_rank);
+                    winner_idx = player_idx;
+                }
+            } else if best_rank.is_none() && self.players[player_idx].hand.len() >= 2 {
+                // Only hole cards to go on: a pocket pair beats unpaired hands
+                let hole_cards = &self.players[player_idx].hand;
+                if hole_cards[0].rank == hole_cards[1].rank && !best_is_pocket_pair {
+                    best_is_pocket_pair = true;
+                    winner_idx = player_idx;
+                    winner_hand_type = "Pair".to_string();
+                }
+            }
+        }
+        
+        (winner_idx, winner_hand_type)
+    }
+    
+    // Monte Carlo estimate of a player's share of the pot against random hands for every
+    // other player still in, completing the board each iteration. Ties count fractionally.
+    pub fn estimate_equity<R: Rng>(&self, player_idx: usize, iterations: usize, rng: &mut R) -> f64 {
+        let opponents = self.players.iter()
+            .enumerate()
+            .filter(|(idx, p)| *idx != player_idx && !p.folded)
+            .count();
+        self.simulate_equity(player_idx, &[], opponents, &[], iterations, rng)
+    }
+    
+    // Same estimate with one opponent's hole cards drawn from a hand range instead of the whole
+    // deck (everyone else still in gets random hands). None when the opponent is out of the
+    // hand or nothing in the range fits around the cards already showing.
+    pub fn equity_vs_range<R: Rng>(&self, player_idx: usize, opponent_idx: usize, range: &HandRange,
+                                   iterations: usize, rng: &mut R) -> Option<f64> {
+        if opponent_idx == player_idx || self.players.get(opponent_idx).is_none_or(|p| p.folded) {
+            return None;
+        }
+        let mut dead = self.players[player_idx].hand.clone();
+        dead.extend(self.community_cards.iter().cloned());
+        let combos = range.live_combos(&dead);
+        if combos.is_empty() {
+            return None;
+        }
+        let others = self.players.iter()
+            .enumerate()
+            .filter(|(idx, p)| *idx != player_idx && *idx != opponent_idx && !p.folded)
+            .count();
+        Some(self.simulate_equity(player_idx, &[], others, &combos, iterations, rng))
+    }
+    
+    // Same estimate, but against the actual hole cards of everyone still in the hand
+    // (for all-in spots where the cards are effectively face up)
+    pub fn all_in_equity<R: Rng>(&self, player_idx: usize, iterations: usize, rng: &mut R) -> f64 {
+        let opponents: Vec<usize> = self.players.iter()
+            .enumerate()
+            .filter(|(idx, p)| *idx != player_idx && !p.folded && p.hand.len() >= 2)
+            .map(|(idx, _)| idx)
+            .collect();
+        self.simulate_equity(player_idx, &opponents, 0, &[], iterations, rng)
+    }
+    
+    // `ranged` is the combo list of an opponent playing a hand range (empty for none); one of
+    // them is drawn each iteration and its cards kept out of the rest of the deal
+    fn simulate_equity<R: Rng>(&self, player_idx: usize, known_opponents: &[usize], random_opponents: usize,
+                               ranged: &[[Card; 2]], iterations: usize, rng: &mut R) -> f64 {
+        let hole_cards = &self.players[player_idx].hand;
+        if hole_cards.len() < 2 || iterations == 0 {
+            return 0.0;
+        }
+        
+        // Everything not in a known hand or on the board could still come out
+        let is_known = |card: &Card| {
+            hole_cards.contains(card)
+                || self.community_cards.contains(card)
+                || known_opponents.iter().any(|&idx| self.players[idx].hand.contains(card))
+        };
+        let mut unseen: Vec<Card> = self.fresh_deck().into_iter()
+            .filter(|c| !is_known(c))
+            .collect();
+        let board_needed = 5usize.saturating_sub(self.community_cards.len());
+        let ranged_cards = if ranged.is_empty() { 0 } else { 2 };
+        if unseen.len() < board_needed + random_opponents * 2 + ranged_cards {
+            return 0.0;
+        }
+        
+        let mut share = 0.0;
+        for _ in 0..iterations {
+            unseen.shuffle(rng);
+            let drawn = ranged.choose(rng);
+            let mut next_card = unseen.iter().filter(|c| drawn.is_none_or(|combo| !combo.contains(c)));
+            
+            let mut board: Vec<PokerCard> = self.community_cards.iter().map(Game::to_poker_card).collect();
+            for _ in 0..board_needed {
+                board.push(Game::to_poker_card(next_card.next().unwrap()));
+            }
+            
+            let rank_with = |cards: &[&Card]| {
+                let mut all: Vec<PokerCard> = cards.iter().map(|c| Game::to_poker_card(c)).collect();
+                all.extend(board.iter().cloned());
+                self.rank_cards(all)
+            };
+            
+            let our_rank = rank_with(&[&hole_cards[0], &hole_cards[1]]);
+            let mut opponent_ranks = known_opponents.iter()
+                .map(|&idx| rank_with(&[&self.players[idx].hand[0], &self.players[idx].hand[1]]))
+                .collect::<Vec<_>>();
+            if let Some(combo) = drawn {
+                opponent_ranks.push(rank_with(&[&combo[0], &combo[1]]));
+            }
+            for _ in 0..random_opponents {
+                opponent_ranks.push(rank_with(&[next_card.next().unwrap(), next_card.next().unwrap()]));
+            }
+            
+            if opponent_ranks.iter().all(|rank| self.compare_ranks(rank, &our_rank) != cmp::Ordering::Greater) {
+                let tied = 1 + opponent_ranks.iter().filter(|rank| **rank == our_rank).count();
+                share += 1.0 / tied as f64;
+            }
+        }
+        
+        share / iterations as f64
+    }
+    
+    // Split the pot between the two all-in players by equity instead of running the board out.
+    // Only the matched part of the pot is shared; chips beyond what the shorter stack could
+    // cover go back to whoever put them in. Returns (player, chips awarded, equity) for both.
+    pub fn propose_chop<R: Rng>(&self, iterations: usize, rng: &mut R) -> Vec<(usize, u32, f64)> {
+        let in_hand: Vec<usize> = self.players.iter()
+            .enumerate()
+            .filter(|(_, p)| !p.folded)
+            .map(|(idx, _)| idx)
+            .collect();
+        if in_hand.len() != 2 {
+            return Vec::new();
+        }
+        let (first, second) = (in_hand[0], in_hand[1]);
+        
+        let matched = self.hand_contributions[first].min(self.hand_contributions[second]);
+        let first_excess = self.hand_contributions[first] - matched;
+        let second_excess = self.hand_contributions[second] - matched;
+        let contested = self.pot.saturating_sub(first_excess + second_excess + self.rake_for_pot());
+        
+        let first_equity = self.all_in_equity(first, iterations, rng);
+        let first_share = ((contested as f64 * first_equity).round() as u32).min(contested);
+        
+        vec![
+            (first, first_share + first_excess, first_equity),
+            (second, contested - first_share + second_excess, 1.0 - first_equity),
+        ]
+    }
+    
+    // The cards that would have completed the board, in the order they'd have been dealt.
+    // Only a peek - the deck isn't touched. Each street still burns a card first, as deal_board_to does.
+    pub fn rabbit_hunt_cards(&self) -> Vec<Card> {
+        let mut deck = self.deck.iter().rev();
+        let mut cards = Vec::new();
+        let mut board_len = self.community_cards.len();
+        while board_len < 5 {
+            let street_end = if board_len == 0 { 3 } else { board_len + 1 };
+            if deck.next().is_none() {
+                break;
+            }
+            while board_len < street_end {
+                match deck.next() {
+                    Some(card) => cards.push(card.clone()),
+                    None => return cards,
+                }
+                board_len += 1;
+            }
+        }
+        cards
+    }
+    
+    // House cut for the current pot: a percentage (rounded down), optionally capped,
+    // and nothing at all for hands that end before the flop under no-flop-no-drop
+    pub fn rake_for_pot(&self) -> u32 {
+        if self.rake_percent == 0 || (self.no_flop_no_drop && self.community_cards.is_empty()) {
+            return 0;
+        }
+        let rake = (self.pot as u64 * self.rake_percent as u64 / 100) as u32;
+        match self.rake_cap {
+            Some(cap) => rake.min(cap),
+            None => rake,
+        }
+    }
+    
+    // Pay out an agreed chop and close the hand without dealing the rest of the board
+    pub fn apply_chop(&mut self, shares: &[(usize, u32, f64)]) {
+        self.last_pot = self.pot;
+        for &(idx, amount, _) in shares {
+            self.players[idx].chips += amount;
+            self.events.push(GameEvent::PotAwarded { seat: idx, amount });
+        }
+        // Whatever the shares leave behind is the rake
+        let paid_out: u32 = shares.iter().map(|&(_, amount, _)| amount).sum();
+        self.last_rake = self.pot.saturating_sub(paid_out);
+        self.pot = 0;
+        self.hand_contributions = vec![0; self.players.len()];
+        self.chop_pending = false;
+        self.round = Round::Showdown;
+    }
+    
+    // No deal: run the board out and go to showdown as usual
+    pub fn decline_chop(&mut self) {
+        self.chop_pending = false;
+        self.run_out_board();
+        self.round = Round::Showdown;
+    }
+    
+    // Deal any missing community cards so all five are out (used when nobody can act anymore)
+    fn run_out_board(&mut self) {
+        self.deal_board_to(5);
+    }
+    
+    pub fn get_bot_action(&mut self, bot_player: &Player) -> Result<GameAction, String> {
+        // With an API key, let the model decide; fall back to the built-in bots if it fails
+        self.last_bot_reason = None;
+        #[cfg(feature = "llm")]
+        if let Some(api_key) = self.api_key.clone() {
+            match self.api_bot_action(&api_key) {
+                Ok(action) => return Ok(action),
+                Err(e) => {
+                    self.api_stats.failures += 1;
+                    self.debug_log.push(format!("{}: {} - using built-in play", bot_player.name, e));
+                }
+            }
+        }
+        
+        Ok(self.heuristic_bot_action(bot_player))
+    }
+    
+    // Start the API decision for the player to act on a worker thread, so the table keeps
+    // drawing while the model thinks. None without an API key (or the llm feature): the
+    // built-in bots decide on the spot.
+    pub fn start_api_bot_action(&self) -> Option<PendingBotAction> {
+        #[cfg(feature = "llm")]
+        if let Some(api_key) = self.api_key.clone() {
+            let (sender, receiver) = mpsc::channel();
+            let mut game = self.clone();
+            game.debug_log.clear();
+            game.last_bot_reason = None;
+            std::thread::spawn(move || {
+                let action = game.api_bot_action(&api_key);
+                // Nobody is listening if the app already gave up on this decision
+                let _ = sender.send(ApiDecision {
+                    action,
+                    api_stats: Some(game.api_stats),
+                    debug_log: game.debug_log,
+                    reason: game.last_bot_reason,
+                });
+            });
+            return Some(PendingBotAction::new(self.current_player_idx, self.hand_number, receiver));
+        }
+        None
+    }
+    
+    // Take over what a worker's decision brought back. A failed call falls back to the built-in
+    // bots, the same as get_bot_action.
+    pub fn finish_api_bot_action(&mut self, decision: ApiDecision, bot_player: &Player) -> GameAction {
+        if let Some(stats) = decision.api_stats {
+            self.api_stats = stats;
+        }
+        self.debug_log.extend(decision.debug_log);
+        self.last_bot_reason = decision.reason;
+        match decision.action {
+            Ok(action) => action,
+            Err(e) => {
+                self.api_stats.failures += 1;
+                self.debug_log.push(format!("{}: {} - using built-in play", bot_player.name, e));
+                self.heuristic_bot_action(bot_player)
+            }
+        }
+    }
+    
+    // The built-in bots' decision for the given player, based on their difficulty
+    pub fn heuristic_bot_action(&self, bot_player: &Player) -> GameAction {
+        // Generate bot actions based on difficulty
+        let action_str = self.generate_random_bot_action(bot_player);
+        
+        // Parse the action string
+        if action_str.starts_with("fold") {
+            GameAction::Fold
+        } else if action_str.starts_with("call") {
+            GameAction::Call
+        } else if action_str.starts_with("check") {
+            GameAction::Check
+        } else if action_str.starts_with("raise") {
+            // Extract the raise amount
+            let parts: Vec<&str> = action_str.split_whitespace().collect();
+            if parts.len() >= 2 {
+                if let Ok(amount) = parts[1].parse::<u32>() {
+                    GameAction::Raise(amount)
+                } else {
+                    // Default raise amount
+                    GameAction::Raise(self.big_blind)
+                }
+            } else {
+                // Default raise amount
+                GameAction::Raise(self.big_blind)
+            }
+        } else {
+            // Default to checking
+            GameAction::Check
+        }
+    }
+    
+    // Ask the model for the current player's action
+    #[cfg(feature = "llm")]
+    fn api_bot_action(&mut self, api_key: &str) -> Result<GameAction, String> {
+        let reply_format = if self.think_aloud {
+            "Reply on one line as ACTION | REASON: exactly one action (fold, check, call, or raise <total>), then a bar, then one short sentence saying why."
+        } else {
+            "Reply with exactly one action: fold, check, call, or raise <total>."
+        };
+        let request = OpenAIRequest {
+            model: "gpt-3.5-turbo".to_string(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: format!("You are playing No-Limit Texas Hold'em. {}", reply_format),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: self.bot_prompt(self.current_player_idx),
+                },
+            ],
+            temperature: 0.7,
+        };
+        
+        // Queue behind other requests past the concurrency limit; the wait isn't counted as latency
+        let limiter = Arc::clone(&self.api_limiter);
+        let _permit = match limiter.try_acquire() {
+            Some(permit) => permit,
+            None => {
+                self.debug_log.push(format!("All {} API slots busy - waiting for one", limiter.max()));
+                limiter.acquire()
+            },
+        };
+        self.api_stats.calls += 1;
+        let started = Instant::now();
+        let response = self.make_openai_api_call(api_key, &request);
+        self.api_stats.record_latency(started.elapsed().as_millis() as u64);
+        if let Some(line) = self.api_stats.latency_line() {
+            self.debug_log.push(line);
+        }
+        // Models add reasons even when not asked, so always keep them out of the action
+        let (action, reason) = Game::split_rationale(&response?);
+        if self.think_aloud {
+            self.last_bot_reason = reason;
+        }
+        Ok(self.parse_api_action(&action))
+    }
+    
+    // Describe the spot for the model, including the exact legal raise range
+    pub fn bot_prompt(&self, player_idx: usize) -> String {
+        let player = &self.players[player_idx];
+        let legal = self.legal_actions();
+        let cards = |cards: &[Card]| cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+        
+        let mut lines = vec![
+            format!("Round: {:?}", self.round),
+            format!("Your hole cards: {}", cards(&player.hand)),
+            format!("Board: {}", if self.community_cards.is_empty() { "none".to_string() } else { cards(&self.community_cards) }),
+            format!("Pot: {}. Your stack: {}. Your bet this round: {}.", self.pot, player.chips, player.current_bet),
+        ];
+        
+        if legal.can_check {
+            lines.push("There is no bet to you; you can check.".to_string());
+        } else {
+            lines.push(format!("To call: {}.", legal.to_call));
+        }
+        
+        match (legal.min_raise_to, legal.max_raise_to) {
+            (Some(min_to), Some(max_to)) => lines.push(format!(
+                "If you raise, the minimum total is {} and your maximum ({}) is {}.", min_to,
+                if max_to < player.current_bet + player.chips { "pot limit" } else { "all-in" }, max_to)),
+            _ => lines.push("You cannot raise right now.".to_string()),
+        }
+        
+        lines.push(if self.think_aloud {
+            "Reply as: <fold, check, call or raise <total>> | <one-sentence reason>.".to_string()
+        } else {
+            "Reply with one of: fold, check, call, raise <total>.".to_string()
+        });
+        lines.join("\n")
+    }
+    
+    // Split a reply like "RAISE 60 | strong draw" into the action and the reason. Copes with what
+    // models actually send: labels ("Action: call. Reason: ..."), quotes and markdown, and a dash,
+    // semicolon or new line in place of the bar. The reason is None when there isn't one.
+    // Pure string work; parse_api_action still decides what the action means.
+    pub fn split_rationale(reply: &str) -> (String, Option<String>) {
+        let unwrap = |text: &str| text.trim().trim_matches(|c: char| matches!(c, '"' | '\'' | '`' | '*' | '_')).trim().to_string();
+        let strip_label = |text: &str, labels: &[&str]| {
+            let text = text.trim();
+            labels.iter()
+                .find(|label| text.get(..label.len()).is_some_and(|start| start.eq_ignore_ascii_case(label)))
+                .map_or(text.to_string(), |label| text[label.len()..].trim().to_string())
+        };
+        
+        let clean = unwrap(reply);
+        let lower = clean.to_ascii_lowercase();
+        let split = ["|", "\n", " — ", " – ", " - ", ";"].iter()
+            .find_map(|sep| clean.split_once(sep))
+            .or_else(|| ["reason:", "rationale:", "because"].iter()
+                .find_map(|label| lower.find(label))
+                .filter(|&at| at > 0)
+                .map(|at| clean.split_at(at)));
+        let (action, reason) = split.unwrap_or((clean.as_str(), ""));
+        
+        let action = unwrap(&strip_label(&unwrap(action), &["action:", "decision:", "move:"]));
+        let action = action.trim_end_matches(['.', ',']).to_string();
+        let reason = unwrap(&strip_label(&unwrap(reason), &["reason:", "rationale:", "because", "why:"]));
+        let reason = if reason.is_empty() { None } else { Some(reason.chars().take(160).collect()) };
+        (action, reason)
+    }
+    
+    // Turn the model's reply into an action, moving raise sizes into the legal range
+    pub fn parse_api_action(&mut self, response: &str) -> GameAction {
+        // "raise $1,000" is a total of 1000
+        let text = response.trim().to_lowercase().replace([',', '$'], "");
+        let name = self.players[self.current_player_idx].name.clone();
+        
+        if text.starts_with("fold") {
+            return GameAction::Fold;
+        } else if text.starts_with("check") {
+            return GameAction::Check;
+        } else if text.starts_with("call") {
+            return GameAction::Call;
+        } else if !(text.starts_with("raise") || text.starts_with("bet")) {
+            self.debug_log.push(format!("{}: unrecognized reply '{}', checking/calling", name, response.trim()));
+            return GameAction::Call;
+        }
+        
+        self.api_stats.raises += 1;
+        let legal = self.legal_actions();
+        let (min_to, max_to) = match (legal.min_raise_to, legal.max_raise_to) {
+            (Some(min_to), Some(max_to)) => (min_to, max_to),
+            _ => {
+                self.api_stats.clamped += 1;
+                self.debug_log.push(format!("{}: raise not allowed here, calling instead", name));
+                return GameAction::Call;
+            }
+        };
+        
+        // The first number in the reply is the requested total
+        let requested = text.split(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())
+            .and_then(|part| part.parse::<u32>().ok());
+        let total = requested.unwrap_or(min_to).clamp(min_to, max_to);
+        if requested != Some(total) {
+            self.api_stats.clamped += 1;
+            let asked = requested.map(|r| r.to_string()).unwrap_or_else(|| "no amount".to_string());
+            self.debug_log.push(format!("{}: raise to {} clamped to {} (legal {}-{})", name, asked, total, min_to, max_to));
+        }
+        
+        // Raise amounts are the chips added now
+        GameAction::Raise(total - self.players[self.current_player_idx].current_bet)
+    }
+    
+    #[cfg(feature = "llm")]
+    pub fn make_openai_api_call(&self, api_key: &str, request: &OpenAIRequest) -> Result<String, String> {
+        let client = &self.ai_client;
+        
+        // First attempt to send the request
+        let response_result = client.post("https://api.openai.com/v1/chat/completions")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(request)
+            .send();
+            
+        // Handle HTTP request errors
+        let response = match response_result {
+            Ok(resp) => resp,
+            Err(e) => {
+                return Err(format!("Network error: {}", e));
+            }
+        };
+        
+        // Try to parse the JSON response
+        let json_result = response.json::<OpenAIResponse>();
+        
+        match json_result {
+            Ok(parsed) => {
+                if let Some(choice) = parsed.choices.first() {
+                    Ok(choice.message.content.clone())
+                } else {
+                    Err("No choices returned from API".to_string())
+                }
+            },
+            Err(_e) => {
+                // Return a friendly error message instead of the raw error
+                Err("API response error (using fallback)".to_string())
+            }
+        }
+    }
+    
+    pub fn generate_random_bot_action(&self, player: &Player) -> String {
+        let mut rng = self.table_rng.borrow_mut();
+        
+        // Check if the player has enough chips to make meaningful bets
+        let has_chips = player.chips >= self.big_blind;
+        
+        // Reduce raising probability based on action count to prevent infinite loops
+        let raise_penalty = (self.last_action_count as f32 * 0.5).min(8.0) as u32;
+        
+        // If we're in later rounds or have many actions, bots should be more conservative
+        let is_late_round = self.round == Round::Turn || self.round == Round::River;
+        
+        match player.bot_difficulty {
+            BotDifficulty::Easy => {
+                // Easy bots mostly check/call, occasionally raise, and rarely fold
+                let mut choice: i32 = rng.gen_range(0..10);
+                
+                // Adjust choice based on round and action count
+                if is_late_round || self.last_action_count > 10 {
+                    choice = choice.saturating_add(2); // Make raising less likely
                 }
                 
                 if choice < 5 {
@@ -1174,7 +2940,7 @@ impl Game {
                     "check".to_string()
                 } else if choice < 9 && has_chips && raise_penalty < 8 {
                     // Smaller raises to avoid escalation
-                    format!("raise {}", self.min_bet)
+                    format!("raise {}", self.big_blind)
                 } else {
                     "fold".to_string()
                 }
@@ -1194,7 +2960,7 @@ impl Game {
                     "check".to_string()
                 } else if choice < 9 && has_chips && raise_penalty < 7 {
                     // More modest raises
-                    format!("raise {}", rng.gen_range(1..3) * self.min_bet)
+                    format!("raise {}", rng.gen_range(1..3) * self.big_blind)
                 } else {
                     "fold".to_string()
                 }
@@ -1214,11 +2980,752 @@ impl Game {
                     "check".to_string()
                 } else if choice < 8 && has_chips && raise_penalty < 6 {
                     // Still aggressive but controlled raises
-                    format!("raise {}", rng.gen_range(1..3) * self.min_bet)
+                    format!("raise {}", rng.gen_range(1..3) * self.big_blind)
                 } else {
                     "fold".to_string()
                 }
             },
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    // Everyone checks or calls to the river, the way a table of calling stations would
+    fn check_down(game: &mut Game) {
+        for _ in 0..200 {
+            if game.round == Round::Showdown {
+                break;
+            }
+            let action = if game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+            game.perform_action(action);
+            if !game.next_player() {
+                break;
+            }
+        }
+    }
+
+    fn total_chips(game: &Game) -> u32 {
+        game.players.iter().map(|p| p.chips).sum()
+    }
+
+    // Check a 4-seat hand down to showdown with the button on seat 0, `river_bettor` (if any)
+    // betting the river and everyone else calling it
+    fn four_way_to_showdown(river_bettor: Option<usize>) -> Game {
+        let mut game = Game::headless(4, 1000, 5, 10);
+        game.dealer_idx = 3;
+        game.deal_cards();
+        assert_eq!(game.dealer_idx, 0);
+        let mut bet_made = false;
+        for _ in 0..200 {
+            if game.round == Round::Showdown {
+                break;
+            }
+            let legal = game.legal_actions();
+            let action = if game.round == Round::River && Some(game.current_player_idx) == river_bettor && !bet_made {
+                bet_made = true;
+                GameAction::Raise(game.big_blind)
+            } else if legal.can_check {
+                GameAction::Check
+            } else {
+                GameAction::Call
+            };
+            game.perform_action(action);
+            if !game.next_player() {
+                break;
+            }
+        }
+        assert_eq!(game.round, Round::Showdown);
+        game
+    }
+
+    #[test]
+    fn river_bettor_shows_first() {
+        let game = four_way_to_showdown(Some(2));
+        assert_eq!(game.showdown_order(), vec![2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn with_no_river_bet_the_first_seat_left_of_the_button_shows_first() {
+        let game = four_way_to_showdown(None);
+        assert_eq!(game.showdown_order(), vec![1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn folded_players_are_left_out_of_the_showing_order() {
+        let mut game = four_way_to_showdown(None);
+        game.players[1].folded = true;
+        assert_eq!(game.showdown_order(), vec![2, 3, 0]);
+    }
+
+    #[test]
+    fn busted_seat_is_dealt_out_and_wins_nothing() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.players[2].chips = 0;
+        game.stack_deck(&[
+            [card(Rank::King, Suit::Spades), card(Rank::Seven, Suit::Clubs)],
+            [card(Rank::Queen, Suit::Hearts), card(Rank::Six, Suit::Diamonds)],
+            [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+        ], &[
+            card(Rank::Two, Suit::Clubs), card(Rank::Eight, Suit::Diamonds), card(Rank::Nine, Suit::Hearts),
+            card(Rank::Three, Suit::Spades), card(Rank::Jack, Suit::Clubs),
+        ]);
+        game.deal_cards();
+        assert!(game.players[2].folded, "a seat with no chips is dealt out");
+
+        check_down(&mut game);
+        let (winner, _, _) = game.determine_winner();
+        assert_eq!(winner, 0);
+        assert_eq!(game.players[2].chips, 0);
+        assert_eq!(total_chips(&game), 2000);
+        assert!(game.pot_awards.iter().all(|pot| pot.winners.iter().all(|&(seat, _)| seat != 2)));
+    }
+
+    #[test]
+    fn button_and_blinds_pass_over_a_busted_seat() {
+        for dealer in 0..3 {
+            let mut game = Game::headless(3, 1000, 5, 10);
+            game.ante = 0;
+            game.players[2].chips = 0;
+            game.dealer_idx = dealer;
+            game.deal_cards();
+
+            assert_ne!(game.dealer_idx, 2, "the button moved onto a busted seat");
+            assert_ne!(game.small_blind_idx, 2);
+            assert_ne!(game.big_blind_idx, 2);
+            assert_ne!(game.small_blind_idx, game.big_blind_idx);
+            assert_eq!(game.pot, 15);
+            for event in &game.events {
+                if let GameEvent::BlindPosted { seat, amount, .. } = *event {
+                    assert_ne!(seat, 2);
+                    assert!(amount > 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn short_stack_goes_all_in_posting_the_big_blind() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.ante = 0;
+        game.dealer_idx = 2; // the button moves to seat 0
+        game.players[2].chips = 4;
+        game.deal_cards();
+
+        assert_eq!(game.big_blind_idx, 2);
+        assert!(game.events.iter().any(|event| matches!(event,
+            GameEvent::BlindPosted { seat: 2, kind: BlindKind::Big, amount: 4 })));
+        assert_eq!(game.players[2].chips, 0);
+        assert!(game.players[2].all_in);
+        assert_eq!(game.pot, 9);
+        assert_ne!(game.current_player_idx, 2, "an all-in blind has no decision to make");
+    }
+
+    #[test]
+    fn short_stack_goes_all_in_posting_the_small_blind() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.ante = 0;
+        game.dealer_idx = 2; // the button moves to seat 0
+        game.players[1].chips = 3;
+        game.deal_cards();
+
+        assert_eq!(game.small_blind_idx, 1);
+        assert!(game.events.iter().any(|event| matches!(event,
+            GameEvent::BlindPosted { seat: 1, kind: BlindKind::Small, amount: 3 })));
+        assert!(game.players[1].all_in);
+        assert_eq!(game.pot, 13);
+        assert_eq!(total_chips(&game) + game.pot, 2003);
+    }
+
+    #[test]
+    fn seat_that_put_nothing_in_has_no_stake_in_any_layer() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.players[2].chips = 0;
+        game.stack_deck(&[
+            [card(Rank::King, Suit::Spades), card(Rank::Seven, Suit::Clubs)],
+            [card(Rank::Queen, Suit::Hearts), card(Rank::Six, Suit::Diamonds)],
+            [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+        ], &[
+            card(Rank::Two, Suit::Clubs), card(Rank::Eight, Suit::Diamonds), card(Rank::Nine, Suit::Hearts),
+            card(Rank::Three, Suit::Spades), card(Rank::Jack, Suit::Clubs),
+        ]);
+        game.deal_cards();
+        check_down(&mut game);
+
+        // Even if the empty seat is still live at showdown, its aces can't win chips it never matched
+        game.players[2].folded = false;
+        let (winner, winnings, _) = game.determine_winner();
+        assert_eq!(winner, 0);
+        assert!(winnings > 0);
+        assert_eq!(game.players[2].chips, 0);
+        assert_eq!(total_chips(&game), 2000);
+    }
+
+    #[test]
+    fn tied_hands_split_the_pot_with_the_odd_chip_left_of_the_button() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.dealer_idx = 2; // Moves to seat 0 on the deal, so seat 1 is first to the button's left
+        game.stack_deck(&[
+            [card(Rank::Ten, Suit::Hearts), card(Rank::Three, Suit::Clubs)],
+            [card(Rank::Ten, Suit::Diamonds), card(Rank::Four, Suit::Clubs)],
+            [card(Rank::Seven, Suit::Clubs), card(Rank::Eight, Suit::Diamonds)],
+        ], &[
+            card(Rank::Ace, Suit::Spades), card(Rank::King, Suit::Spades), card(Rank::Queen, Suit::Diamonds),
+            card(Rank::Jack, Suit::Clubs), card(Rank::Two, Suit::Hearts),
+        ]);
+        game.deal_cards();
+        assert_eq!(game.dealer_idx, 0);
+        check_down(&mut game);
+        game.determine_winner();
+
+        // Three antes and three big blinds: an odd pot for the two broadway straights
+        assert_eq!(game.last_pot, 33);
+        assert_eq!(game.pot_awards.len(), 1);
+        assert_eq!(game.pot_awards[0].winners, vec![(1, 17), (0, 16)]);
+        assert_eq!(game.players[0].chips, 1000 - 11 + 16);
+        assert_eq!(game.players[1].chips, 1000 - 11 + 17);
+        assert_eq!(game.players[2].chips, 1000 - 11);
+    }
+
+    #[test]
+    fn rabbit_hunt_burns_before_each_street_like_the_deal() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        let board = [
+            card(Rank::Two, Suit::Clubs), card(Rank::Eight, Suit::Diamonds), card(Rank::Nine, Suit::Hearts),
+            card(Rank::Three, Suit::Spades), card(Rank::Jack, Suit::Clubs),
+        ];
+        game.stack_deck(&[
+            [card(Rank::King, Suit::Spades), card(Rank::Seven, Suit::Clubs)],
+            [card(Rank::Queen, Suit::Hearts), card(Rank::Six, Suit::Diamonds)],
+            [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)],
+        ], &board);
+        game.deal_cards();
+        assert_eq!(game.rabbit_hunt_cards(), board.to_vec());
+
+        let mut after_flop = game.clone();
+        after_flop.deal_board_to(3);
+        assert_eq!(after_flop.rabbit_hunt_cards(), board[3..].to_vec());
+
+        check_down(&mut game);
+        assert_eq!(game.community_cards, board.to_vec());
+    }
+
+    // The button, the deal and fifty bot decisions from one seed
+    fn seeded_run(seed: u64) -> (usize, Vec<Vec<Card>>, Vec<String>) {
+        let mut game = Game::headless(6, 1000, 5, 10);
+        game.reseed(seed);
+        let dealer = game.dealer_idx;
+        game.deal_cards();
+        let hands = game.players.iter().map(|p| p.hand.clone()).collect();
+        let actions = (0..50)
+            .map(|_| format!("{:?}", game.heuristic_bot_action(&game.players[game.current_player_idx])))
+            .collect();
+        (dealer, hands, actions)
+    }
+
+    #[test]
+    fn same_seed_replays_the_button_and_the_bots() {
+        assert_eq!(seeded_run(42), seeded_run(42));
+        assert_ne!(seeded_run(42).2, seeded_run(43).2);
+    }
+
+    // Six bots with the button moving to seat 0: SB 1, BB 2, UTG 3
+    fn straddle_table(chance: f64) -> Game {
+        let mut game = Game::headless(6, 1000, 5, 10);
+        game.allow_straddle = true;
+        game.bot_straddle_chance = chance;
+        game.dealer_idx = 5;
+        game
+    }
+
+    #[test]
+    fn straddle_is_posted_after_the_blinds_and_moves_the_first_action() {
+        let mut game = straddle_table(1.0);
+        game.deal_cards();
+        let blinds: Vec<(usize, u32)> = game.events.iter()
+            .filter_map(|event| match *event {
+                GameEvent::BlindPosted { seat, amount, .. } => Some((seat, amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(blinds, vec![(1, 5), (2, 10), (3, 20)]);
+        assert!(matches!(game.events.last(), Some(GameEvent::BlindPosted { kind: BlindKind::Straddle, .. })));
+        assert_eq!(game.straddle_idx, Some(3));
+        assert_eq!(game.current_player_idx, 4, "the seat after the straddler acts first");
+
+        // Everyone calls round to the big blind; the straddler, not the big blind, has the last option
+        for seat in [4, 5, 0, 1, 2] {
+            assert_eq!(game.current_player_idx, seat);
+            game.perform_action(GameAction::Call);
+            assert!(game.next_player());
+        }
+        assert_eq!(game.round, Round::PreFlop);
+        assert_eq!(game.current_player_idx, 3);
+        game.perform_action(GameAction::Check);
+        game.next_player();
+        assert_eq!(game.round, Round::Flop);
+    }
+
+    #[test]
+    fn bot_straddles_replay_from_the_seed() {
+        let straddles = |seed: u64| {
+            let mut game = straddle_table(0.5);
+            game.reseed(seed);
+            (0..30).map(|_| { game.deal_cards(); game.straddle_idx }).collect::<Vec<_>>()
+        };
+        let first = straddles(5);
+        assert_eq!(first, straddles(5));
+        assert!(first.iter().any(Option::is_some) && first.iter().any(Option::is_none));
+    }
+
+    // Blinds 25/50 in 25 chips, everyone on 990 so the all-in isn't a round number
+    fn chip_increment_table() -> Game {
+        let mut game = Game::headless(4, 990, 25, 50);
+        game.chip_increment = 25;
+        game.ante = 0;
+        game.deal_cards();
+        game
+    }
+
+    #[test]
+    fn rounded_raise_never_drops_under_the_minimum() {
+        let game = chip_increment_table();
+        assert_eq!(game.legal_actions().min_raise_to, Some(100));
+        assert_eq!(game.round_raise(60), 100);
+        assert_eq!(game.round_raise(112), 100);
+        assert_eq!(game.round_raise(113), 125);
+    }
+
+    #[test]
+    fn rounded_minimum_goes_up_to_the_next_chip() {
+        let mut game = chip_increment_table();
+        // Facing a short all-in to 110 (all-ins aren't rounded), the minimum raise is to 160,
+        // which isn't a whole number of chips
+        let shover = game.current_player_idx;
+        game.players[shover].chips = 110;
+        game.perform_action(GameAction::Raise(110));
+        assert!(game.next_player());
+        assert_eq!(game.legal_actions().min_raise_to, Some(160));
+        assert_eq!(game.round_raise(150), 175);
+    }
+
+    #[test]
+    fn rounded_raise_leaves_all_ins_alone_and_stops_at_the_stack() {
+        let game = chip_increment_table();
+        assert_eq!(game.round_raise(990), 990);
+        assert_eq!(game.round_raise(985), 975);
+        assert_eq!(game.round_raise(989), 990, "rounding up past the stack is just the all-in");
+    }
+
+    #[test]
+    fn turn_comes_right_after_the_third_flop_check() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.deal_cards();
+        while game.round == Round::PreFlop {
+            let action = if game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+            game.perform_action(action);
+            assert!(game.next_player());
+        }
+        assert_eq!(game.round, Round::Flop);
+        assert_eq!(game.community_cards.len(), 3);
+
+        let mut acted = Vec::new();
+        for check in 1..=3 {
+            assert_eq!(game.round, Round::Flop, "the flop ended before check {}", check);
+            assert!(!acted.contains(&game.current_player_idx), "seat {} acted twice", game.current_player_idx);
+            acted.push(game.current_player_idx);
+            game.perform_action(GameAction::Check);
+            assert!(game.next_player());
+        }
+        assert_eq!(game.round, Round::Turn);
+        assert_eq!(game.community_cards.len(), 4);
+        assert_eq!(game.current_player_idx, acted[0], "the turn opens with the flop's first seat again");
+    }
+
+    // Without the llm feature there is no HTTP client at all: even with a key set, no request
+    // is started and every bot decision comes from the built-in play
+    #[cfg(not(feature = "llm"))]
+    #[test]
+    fn no_llm_build_never_calls_the_api() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.api_key = Some("sk-test".to_string());
+        game.deal_cards();
+        assert!(game.start_api_bot_action().is_none());
+        let bot = game.players[game.current_player_idx].clone();
+        assert!(game.get_bot_action(&bot).is_ok());
+        assert_eq!((game.api_stats.calls, game.api_stats.failures), (0, 0));
+    }
+
+    #[test]
+    fn duplicate_card_warning_names_the_hand() {
+        let mut game = Game::headless(2, 1000, 5, 10);
+        game.deal_cards();
+        check_down(&mut game);
+        let repeated = game.community_cards[0].clone();
+        game.players[0].hand[0] = repeated.clone();
+        game.debug_log.clear();
+        game.determine_winner();
+        let expected = format!("System warning: hand #{}: duplicate card(s) {} between", game.hand_number, repeated);
+        assert!(game.debug_log.iter().any(|line| line.starts_with(&expected)), "{:?}", game.debug_log);
+    }
+
+    // Chips each seat put in or got back according to the events, to compare with the stacks
+    fn chips_moved_by_events(game: &Game) -> Vec<i64> {
+        let mut moved = vec![0i64; game.players.len()];
+        for event in &game.events {
+            match event {
+                GameEvent::AntesPosted { posted } => {
+                    for &(seat, amount) in posted {
+                        moved[seat] -= amount as i64;
+                    }
+                },
+                GameEvent::BlindPosted { seat, amount, .. } | GameEvent::ChipsIn { seat, amount } => moved[*seat] -= *amount as i64,
+                GameEvent::PotAwarded { seat, amount } | GameEvent::BetReturned { seat, amount } => moved[*seat] += *amount as i64,
+                _ => {}
+            }
+        }
+        moved
+    }
+
+    fn stack_changes(game: &Game, before: &[u32]) -> Vec<i64> {
+        game.players.iter().zip(before).map(|(p, &b)| p.chips as i64 - b as i64).collect()
+    }
+
+    #[test]
+    fn deal_events_match_the_chips_posted() {
+        let mut game = Game::headless(4, 1000, 5, 10);
+        game.ante = 2;
+        game.dealer_idx = 3;
+        let before: Vec<u32> = game.players.iter().map(|p| p.chips).collect();
+        game.deal_cards();
+
+        assert!(matches!(game.events[0], GameEvent::HandStarted { hand_no, dealer } if hand_no == game.hand_number && dealer == 0));
+        let posted: Vec<String> = game.events[1..].iter().map(|e| format!("{:?}", e)).collect();
+        assert_eq!(posted, vec![
+            "AntesPosted { posted: [(0, 2), (1, 2), (2, 2), (3, 2)] }".to_string(),
+            "BlindPosted { seat: 1, kind: Small, amount: 5 }".to_string(),
+            "BlindPosted { seat: 2, kind: Big, amount: 10 }".to_string(),
+        ]);
+        assert_eq!(chips_moved_by_events(&game), stack_changes(&game, &before));
+        assert_eq!(game.pot, 23);
+    }
+
+    #[test]
+    fn short_blind_event_reports_what_was_actually_posted() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.ante = 0;
+        game.dealer_idx = 2;
+        game.players[2].chips = 4;
+        let before: Vec<u32> = game.players.iter().map(|p| p.chips).collect();
+        game.deal_cards();
+        assert!(game.events.iter().any(|e| matches!(e, GameEvent::BlindPosted { seat: 2, kind: BlindKind::Big, amount: 4 })),
+                "{:?}", game.events);
+        assert_eq!(chips_moved_by_events(&game), stack_changes(&game, &before));
+    }
+
+    #[test]
+    fn whole_hand_events_account_for_every_chip() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        let before: Vec<u32> = game.players.iter().map(|p| p.chips).collect();
+        game.deal_cards();
+        let opener = game.current_player_idx;
+        game.perform_action(GameAction::Raise(30));
+        game.next_player();
+        check_down(&mut game);
+        game.determine_winner();
+        assert!(game.events.iter().any(|e| matches!(e, GameEvent::ChipsIn { seat, .. } if *seat == opener)));
+        assert_eq!(chips_moved_by_events(&game), stack_changes(&game, &before));
+    }
+
+    // A dealt hand whose betting is set straight to `put_in` (chips each seat has in the pot)
+    // and `left` (what each still holds); seats in `folded` have let go
+    fn betting_ended(put_in: &[u32], left: &[u32], folded: &[usize]) -> Game {
+        let mut game = Game::headless(put_in.len(), 1000, 5, 10);
+        game.deal_cards();
+        game.hand_contributions = put_in.to_vec();
+        game.pot = put_in.iter().sum();
+        for (idx, player) in game.players.iter_mut().enumerate() {
+            player.chips = left[idx];
+            player.folded = folded.contains(&idx);
+        }
+        game.round = Round::Showdown;
+        game.events.clear();
+        game
+    }
+
+    #[test]
+    fn uncalled_part_of_a_bet_goes_back_to_the_bettor() {
+        // Seat 0 bets 80, seat 1 calls all-in for 30, seat 2 folded its 10
+        let mut game = betting_ended(&[80, 30, 10], &[920, 0, 990], &[2]);
+        game.determine_winner();
+        assert_eq!(game.uncalled_return, Some((0, 50)));
+        assert!(game.events.iter().any(|e| matches!(e, GameEvent::BetReturned { seat: 0, amount: 50 })));
+        assert_eq!(game.pot_awards.iter().map(|p| p.amount).sum::<u32>(), 70, "only the matched chips are played for");
+        assert_eq!(total_chips(&game), 920 + 990 + 120);
+    }
+
+    #[test]
+    fn refund_is_measured_against_the_biggest_all_in() {
+        // Two all-ins for 60 and 120 under seat 0's 200: 80 comes back, and the rest makes a
+        // main pot of 190 (60 x 3 plus the folded 10) and a side pot of 120
+        let mut game = betting_ended(&[200, 120, 60, 10], &[800, 0, 0, 990], &[3]);
+        game.determine_winner();
+        assert_eq!(game.uncalled_return, Some((0, 80)));
+        let pots: Vec<u32> = game.pot_awards.iter().map(|p| p.amount).collect();
+        assert_eq!(pots, vec![190, 120]);
+        assert_eq!(total_chips(&game), 800 + 990 + 390);
+    }
+
+    #[test]
+    fn matched_bets_get_no_refund() {
+        let mut game = betting_ended(&[200, 120, 200], &[800, 0, 800], &[]);
+        game.determine_winner();
+        assert_eq!(game.uncalled_return, None);
+        assert!(!game.events.iter().any(|e| matches!(e, GameEvent::BetReturned { .. })));
+        assert_eq!(game.pot_awards.iter().map(|p| p.amount).sum::<u32>(), 520);
+    }
+
+    // A table raking `percent` of a `pot`-sized pot, with the flop out or not
+    fn raked_table(percent: u32, cap: Option<u32>, pot: u32, flop_dealt: bool) -> Game {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.rake_percent = percent;
+        game.rake_cap = cap;
+        game.pot = pot;
+        if flop_dealt {
+            game.community_cards = vec![
+                card(Rank::Two, Suit::Clubs), card(Rank::Eight, Suit::Diamonds), card(Rank::Nine, Suit::Hearts),
+            ];
+        }
+        game
+    }
+
+    #[test]
+    fn rake_is_rounded_down_to_a_whole_chip() {
+        assert_eq!(raked_table(5, None, 199, true).rake_for_pot(), 9, "5% of $199 is $9.95");
+        assert_eq!(raked_table(5, None, 200, true).rake_for_pot(), 10);
+        assert_eq!(raked_table(5, None, 19, true).rake_for_pot(), 0, "too small a pot to take a chip from");
+        assert_eq!(raked_table(0, None, 1000, true).rake_for_pot(), 0);
+    }
+
+    #[test]
+    fn rake_stops_at_the_cap() {
+        assert_eq!(raked_table(10, Some(30), 1000, true).rake_for_pot(), 30);
+        assert_eq!(raked_table(10, Some(30), 300, true).rake_for_pot(), 30, "exactly at the cap");
+        assert_eq!(raked_table(10, Some(30), 250, true).rake_for_pot(), 25, "under the cap");
+    }
+
+    #[test]
+    fn no_flop_no_drop_only_spares_preflop_pots() {
+        let mut preflop = raked_table(10, None, 100, false);
+        assert_eq!(preflop.rake_for_pot(), 10, "raked without the rule");
+        preflop.no_flop_no_drop = true;
+        assert_eq!(preflop.rake_for_pot(), 0);
+
+        let mut flop = raked_table(10, None, 100, true);
+        flop.no_flop_no_drop = true;
+        assert_eq!(flop.rake_for_pot(), 10);
+    }
+
+    #[test]
+    fn rake_comes_out_of_the_settled_pot() {
+        let mut game = betting_ended(&[100, 100, 100], &[900, 900, 900], &[]);
+        game.rake_percent = 5;
+        game.rake_cap = Some(12);
+        game.determine_winner();
+        assert_eq!(game.last_rake, 12);
+        assert_eq!(game.pot_awards.iter().map(|p| p.amount).sum::<u32>(), 288);
+        assert_eq!(total_chips(&game) + game.last_rake, 3000);
+    }
+
+    // The human's and Bot 1's best hands with these hole cards on a five-card board
+    fn showdown_hands(yours: [Card; 2], bots: [Card; 2], board: &[Card]) -> (BestHand, BestHand) {
+        let mut game = Game::new(1, 1, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        game.stack_deck(&[yours, bots], &[]);
+        game.deal_cards();
+        game.community_cards = board.to_vec();
+        (game.best_hand(0).unwrap(), game.best_hand(1).unwrap())
+    }
+
+    fn aces_and_nines_board() -> Vec<Card> {
+        vec![
+            card(Rank::Ace, Suit::Spades), card(Rank::Nine, Suit::Diamonds), card(Rank::Nine, Suit::Clubs),
+            card(Rank::Five, Suit::Hearts), card(Rank::Two, Suit::Clubs),
+        ]
+    }
+
+    #[test]
+    fn comparison_names_the_deciding_kicker() {
+        let (yours, bots) = showdown_hands(
+            [card(Rank::Ace, Suit::Hearts), card(Rank::Jack, Suit::Diamonds)],
+            [card(Rank::Ace, Suit::Diamonds), card(Rank::Queen, Suit::Clubs)],
+            &aces_and_nines_board(),
+        );
+        assert_eq!(describe_comparison(&bots, &yours), "Same Two Pair (Aces and Nines) - Bot 1's Q kicker beats your J");
+    }
+
+    #[test]
+    fn comparison_of_a_split_says_the_hands_tie() {
+        // Both play the board's five for the last card: the kickers in hand are too small
+        let (yours, bots) = showdown_hands(
+            [card(Rank::Ace, Suit::Hearts), card(Rank::Four, Suit::Diamonds)],
+            [card(Rank::Ace, Suit::Diamonds), card(Rank::Three, Suit::Clubs)],
+            &aces_and_nines_board(),
+        );
+        assert_eq!(describe_comparison(&bots, &yours), "Your Two Pair (Aces and Nines) ties Bot 1's Two Pair (Aces and Nines)");
+    }
+
+    #[test]
+    fn comparison_of_different_categories_names_both_hands() {
+        let board = [
+            card(Rank::King, Suit::Diamonds), card(Rank::Eight, Suit::Diamonds), card(Rank::Three, Suit::Diamonds),
+            card(Rank::Nine, Suit::Clubs), card(Rank::Nine, Suit::Hearts),
+        ];
+        let (yours, bots) = showdown_hands(
+            [card(Rank::Nine, Suit::Spades), card(Rank::Ace, Suit::Spades)],
+            [card(Rank::Ace, Suit::Diamonds), card(Rank::Two, Suit::Diamonds)],
+            &board,
+        );
+        let comparison = describe_comparison(&bots, &yours);
+        assert!(comparison.starts_with("Your Three of a Kind (Nines) loses to Bot 1's Flush (A-high "), "{}", comparison);
+        assert!(!comparison.contains("kicker"));
+    }
+
+    #[test]
+    fn acting_at_showdown_is_rejected_and_changes_nothing() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.deal_cards();
+        check_down(&mut game);
+        assert_eq!(game.round, Round::Showdown);
+
+        let stacks = |game: &Game| game.players.iter().map(|p| (p.chips, p.current_bet, p.folded, p.all_in)).collect::<Vec<_>>();
+        let before = (stacks(&game), game.pot, game.hand_contributions.clone(), game.action_history.len(), game.current_player_idx);
+        for action in [GameAction::Raise(100), GameAction::Call, GameAction::Fold] {
+            let (played, bet) = game.perform_action(action);
+            assert!(matches!(played, GameAction::Check));
+            assert_eq!(bet, None);
+        }
+        let after = (stacks(&game), game.pot, game.hand_contributions.clone(), game.action_history.len(), game.current_player_idx);
+        assert_eq!(after, before);
+        assert_eq!(game.round, Round::Showdown);
+        assert_eq!(game.debug_log.iter().filter(|line| line.contains("at showdown - ignored")).count(), 3);
+    }
+
+    // Cards no longer in the deck, a hand or on the board: the burns
+    fn burned_cards(game: &Game) -> usize {
+        let seen = game.deck.len() + game.community_cards.len() + game.players.iter().map(|p| p.hand.len()).sum::<usize>();
+        game.fresh_deck().len() - seen
+    }
+
+    #[test]
+    fn one_card_is_burned_before_each_street() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.deal_cards();
+        assert_eq!((game.deck.len(), burned_cards(&game)), (46, 0));
+
+        // (board, deck, burned) once each street is out
+        let mut streets = Vec::new();
+        for _ in 0..200 {
+            if game.round == Round::Showdown {
+                break;
+            }
+            let round = game.round;
+            let action = if game.legal_actions().can_check { GameAction::Check } else { GameAction::Call };
+            game.perform_action(action);
+            game.next_player();
+            if game.round != round && game.round != Round::Showdown {
+                streets.push((game.community_cards.len(), game.deck.len(), burned_cards(&game)));
+            }
+        }
+        assert_eq!(streets, vec![(3, 42, 1), (4, 40, 2), (5, 38, 3)]);
+    }
+
+    #[test]
+    fn running_out_the_board_burns_for_every_street() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.deal_cards();
+        game.run_out_board();
+        assert_eq!((game.community_cards.len(), game.deck.len(), burned_cards(&game)), (5, 38, 3));
+
+        // From the turn, only the river's burn is left
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.deal_cards();
+        game.deal_board_to(4);
+        assert_eq!((game.deck.len(), burned_cards(&game)), (40, 2));
+        game.round = Round::River;
+        game.fill_board("test");
+        assert_eq!((game.community_cards.len(), game.deck.len(), burned_cards(&game)), (5, 38, 3));
+        assert!(game.debug_log.is_empty(), "{:?}", game.debug_log);
+    }
+
+    #[test]
+    fn api_limiter_holds_at_most_max_requests_in_flight() {
+        let limiter = ApiLimiter::new(2);
+        let first = limiter.try_acquire().expect("a free slot");
+        let second = limiter.try_acquire().expect("a free slot");
+        assert_eq!(limiter.in_flight(), 2);
+        assert!(limiter.try_acquire().is_none(), "the third request waits");
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        let third = limiter.try_acquire().expect("the freed slot");
+        assert!(limiter.try_acquire().is_none());
+
+        drop((second, third));
+        assert_eq!(limiter.in_flight(), 0);
+        assert_eq!(ApiLimiter::new(0).max(), 1, "a limit of zero would never let anything through");
+    }
+
+    #[test]
+    fn waiting_request_goes_out_once_a_slot_frees() {
+        let limiter = Arc::new(ApiLimiter::new(1));
+        let held = limiter.try_acquire().unwrap();
+        let (queued, started) = mpsc::channel();
+        let (sent, done) = mpsc::channel();
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            std::thread::spawn(move || {
+                queued.send(()).unwrap();
+                let _permit = limiter.acquire();
+                sent.send(limiter.in_flight()).unwrap();
+            })
+        };
+        started.recv().unwrap();
+        assert!(done.try_recv().is_err(), "no slot until the held request finishes");
+        drop(held);
+        assert_eq!(done.recv().unwrap(), 1);
+        waiter.join().unwrap();
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[test]
+    fn no_next_player_when_nobody_can_act() {
+        let mut game = Game::headless(4, 1000, 5, 10);
+        game.deal_cards();
+
+        // Everyone has folded
+        for player in &mut game.players {
+            player.folded = true;
+        }
+        assert_eq!(game.find_next_active_player(0), None);
+
+        // Everyone still in is all in
+        for (idx, player) in game.players.iter_mut().enumerate() {
+            player.folded = idx == 3;
+            player.chips = 0;
+            player.all_in = idx != 3;
+        }
+        assert_eq!(game.find_next_active_player(0), None);
+        assert_eq!(game.find_next_active_player(3), None);
+
+        // One seat with chips left: it's next from anywhere, itself included
+        game.players[2].chips = 50;
+        game.players[2].all_in = false;
+        assert_eq!(game.find_next_active_player(0), Some(2));
+        assert_eq!(game.find_next_active_player(2), Some(2));
+    }
+}
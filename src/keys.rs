@@ -0,0 +1,232 @@
+// Key bindings for the table's single-key commands, rebindable from a JSON key map
+// (--keys PATH). Actions left out of the map keep their default key.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    Call,
+    Check,
+    Fold,
+    Raise,
+    AutoCall,
+    Deal,
+    Stop,
+    Quit,
+    Name,
+    NewSession,
+    SetChips,
+    ShowdownStyle,
+    RabbitHunt,
+    TableSetup,
+    Straddle,
+    Verbosity,
+    HandStart,
+    LastShowdown,
+    Settings,
+    LogGrow,
+    LogShrink,
+    WhatIf,
+    VsRange,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 23] = [
+        KeyAction::Call, KeyAction::Check, KeyAction::Fold, KeyAction::Raise, KeyAction::AutoCall,
+        KeyAction::Deal, KeyAction::Stop, KeyAction::Quit, KeyAction::Name, KeyAction::NewSession,
+        KeyAction::SetChips, KeyAction::ShowdownStyle, KeyAction::RabbitHunt, KeyAction::TableSetup,
+        KeyAction::Straddle, KeyAction::Verbosity, KeyAction::HandStart, KeyAction::LastShowdown,
+        KeyAction::Settings, KeyAction::LogGrow, KeyAction::LogShrink, KeyAction::WhatIf,
+        KeyAction::VsRange,
+    ];
+
+    // Name used in key map files
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Call => "call",
+            KeyAction::Check => "check",
+            KeyAction::Fold => "fold",
+            KeyAction::Raise => "raise",
+            KeyAction::AutoCall => "auto_call",
+            KeyAction::Deal => "deal",
+            KeyAction::Stop => "stop",
+            KeyAction::Quit => "quit",
+            KeyAction::Name => "name",
+            KeyAction::NewSession => "new_session",
+            KeyAction::SetChips => "set_chips",
+            KeyAction::ShowdownStyle => "showdown_style",
+            KeyAction::RabbitHunt => "rabbit_hunt",
+            KeyAction::TableSetup => "table_setup",
+            KeyAction::Straddle => "straddle",
+            KeyAction::Verbosity => "verbosity",
+            KeyAction::HandStart => "hand_start",
+            KeyAction::LastShowdown => "last_showdown",
+            KeyAction::Settings => "settings",
+            KeyAction::LogGrow => "log_grow",
+            KeyAction::LogShrink => "log_shrink",
+            KeyAction::WhatIf => "what_if",
+            KeyAction::VsRange => "vs_range",
+        }
+    }
+
+    pub fn default_key(&self) -> char {
+        match self {
+            KeyAction::Call => 'c',
+            KeyAction::Check => 'k',
+            KeyAction::Fold => 'f',
+            KeyAction::Raise => 'r',
+            KeyAction::AutoCall => 'a',
+            KeyAction::Deal => 'd',
+            KeyAction::Stop => 's',
+            KeyAction::Quit => 'q',
+            KeyAction::Name => 'n',
+            KeyAction::NewSession => 'N',
+            KeyAction::SetChips => 'C',
+            KeyAction::ShowdownStyle => 'F',
+            KeyAction::RabbitHunt => 'h',
+            KeyAction::TableSetup => 'g',
+            KeyAction::Straddle => 'x',
+            KeyAction::Verbosity => 'v',
+            KeyAction::HandStart => 'b',
+            KeyAction::LastShowdown => 'w',
+            KeyAction::Settings => 'S',
+            KeyAction::LogGrow => '+',
+            KeyAction::LogShrink => '-',
+            KeyAction::WhatIf => 'W',
+            KeyAction::VsRange => 'V',
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    keys: [char; KeyAction::ALL.len()], // Indexed like KeyAction::ALL
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings { keys: KeyAction::ALL.map(|action| action.default_key()) }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: KeyAction) -> char {
+        self.keys[action as usize]
+    }
+
+    pub fn action(&self, key: char) -> Option<KeyAction> {
+        KeyAction::ALL.iter().copied().find(|&action| self.key(action) == key)
+    }
+
+    // "[c]all" style label for an input hint. A rebound key that doesn't start the word
+    // gets its own bracket instead: "[w] call".
+    pub fn hint(&self, action: KeyAction, word: &str) -> String {
+        let key = self.key(action);
+        if word.starts_with(key) || key == action.default_key() {
+            let rest: String = word.chars().skip(1).collect();
+            format!("[{}]{}", key, rest)
+        } else {
+            format!("[{}] {}", key, word)
+        }
+    }
+
+    // Apply a key map ({"call": "w", "check": "s", ...}) over the defaults. Every problem is
+    // reported together; any problem rejects the whole map so no half-applied layout is left.
+    pub fn from_map(map: &HashMap<String, String>) -> Result<KeyBindings, String> {
+        let mut bindings = KeyBindings::default();
+        let mut problems = Vec::new();
+        for (name, key) in map {
+            let Some(action) = KeyAction::ALL.iter().copied().find(|a| a.name() == name.trim()) else {
+                problems.push(format!("unknown action '{}'", name));
+                continue;
+            };
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                // Digits type amounts, so they can't be commands
+                (Some(c), None) if !c.is_ascii_digit() && !c.is_whitespace() => bindings.keys[action as usize] = c,
+                _ => problems.push(format!("'{}' for {} is not a single non-digit key", key, name)),
+            }
+        }
+        for (i, &action) in KeyAction::ALL.iter().enumerate() {
+            if let Some(&other) = KeyAction::ALL[..i].iter().find(|&&other| bindings.key(other) == bindings.key(action)) {
+                problems.push(format!("'{}' is bound to both {} and {}", bindings.key(action), other.name(), action.name()));
+            }
+        }
+        if problems.is_empty() {
+            Ok(bindings)
+        } else {
+            Err(problems.join("; "))
+        }
+    }
+
+    // Read a key map file (a JSON object of action names to keys)
+    pub fn load(path: &Path) -> Result<KeyBindings, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let map: HashMap<String, String> = serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e))?;
+        KeyBindings::from_map(&map).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|&(name, key)| (name.to_string(), key.to_string())).collect()
+    }
+
+    // A key map file in the temp directory, unique to the test that writes it
+    fn key_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("p_kr_keys_{}_{}.json", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn remapped_keys_replace_the_defaults() {
+        let keys = KeyBindings::from_map(&map(&[("call", "j"), ("check", "l")])).unwrap();
+        assert_eq!(keys.key(KeyAction::Call), 'j');
+        assert_eq!(keys.action('l'), Some(KeyAction::Check));
+        assert_eq!(keys.key(KeyAction::Fold), KeyAction::Fold.default_key(), "left out, so unchanged");
+    }
+
+    #[test]
+    fn every_default_key_is_distinct() {
+        assert!(KeyBindings::from_map(&HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let err = KeyBindings::from_map(&map(&[("call", "f")])).unwrap_err();
+        assert_eq!(err, "'f' is bound to both call and fold");
+    }
+
+    #[test]
+    fn unknown_actions_and_bad_keys_are_all_reported() {
+        let err = KeyBindings::from_map(&map(&[("jump", "j"), ("raise", "7"), ("fold", "ff")])).unwrap_err();
+        assert!(err.contains("unknown action 'jump'"), "{}", err);
+        assert!(err.contains("'7' for raise is not a single non-digit key"), "{}", err);
+        assert!(err.contains("'ff' for fold is not a single non-digit key"), "{}", err);
+    }
+
+    #[test]
+    fn key_map_file_loads() {
+        let path = key_file("good", r#"{"call": "j"}"#);
+        let keys = KeyBindings::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(keys.key(KeyAction::Call), 'j');
+    }
+
+    #[test]
+    fn broken_or_missing_file_is_an_error() {
+        let path = key_file("broken", r#"{"call": "j""#);
+        let err = KeyBindings::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(err.starts_with(&path.display().to_string()), "{}", err);
+
+        let missing = std::env::temp_dir().join("p_kr_keys_does_not_exist.json");
+        assert!(KeyBindings::load(&missing).unwrap_err().starts_with("Could not read"));
+    }
+}
@@ -0,0 +1,18 @@
+// Poker engine without the terminal UI, so benches and simulations can drive it directly
+pub mod game;
+pub mod config;
+pub mod util;
+pub mod session;
+pub mod profile;
+pub mod strategy;
+pub mod simulator;
+pub mod flavor;
+pub mod achievements;
+pub mod drill;
+pub mod audit;
+pub mod keys;
+pub mod coach;
+pub mod version;
+pub mod whatif;
+pub mod evalcheck;
+pub mod range;
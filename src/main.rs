@@ -1,12 +1,12 @@
-mod game;
 mod app;
-mod util;
 mod ui;
 
+use p_kr::{audit, game, util, config, session, flavor, profile, achievements, drill, keys, coach, version, whatif, range};
+
 use std::io;
 use std::time::Duration;
 use crossterm::{
-    event::{self, Event},
+    event::{self, Event, KeyCode, KeyModifiers},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     execute,
 };
@@ -16,9 +16,52 @@ use tui::{
 };
 
 use app::App;
+use config::Config;
 
 fn main() -> Result<(), io::Error> {
-    let api_key = std::env::var("OPENAI_API_KEY").ok();
+    // Builds without the llm feature have no OpenAI bots, so the key is left unread
+    let api_key = if cfg!(feature = "llm") { std::env::var("OPENAI_API_KEY").ok() } else { None };
+    
+    // --version answers on its own, before the other flags are checked
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(output) = version::version_output(&args) {
+        println!("{}", output);
+        return Ok(());
+    }
+    
+    // `simulate --audit-deals N` runs headless and never opens the table
+    if let Some(request) = audit::simulate_command(&args) {
+        match request {
+            Ok((hands, seed)) => run_deal_audit(hands, seed.unwrap_or_else(rand::random::<u64>)),
+            Err(e) => {
+                eprintln!("Simulate error: {}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+    
+    // Parse configuration before touching the terminal so errors print normally
+    let config = match Config::from_args(args.into_iter()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    
+    game::set_ascii_suits(config.ascii_suits);
+    
+    // Load drill scenarios up front too, so a bad file is reported before the screen switches
+    let drill = match &config.drill {
+        Some(path) => match drill::load_scenarios(path) {
+            Ok(scenarios) => Some(drill::DrillSession::new(scenarios)),
+            Err(e) => {
+                eprintln!("Drill error: {}", e);
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
     
     // Setup terminal
     enable_raw_mode()?;
@@ -28,23 +71,84 @@ fn main() -> Result<(), io::Error> {
     let mut terminal = Terminal::new(backend)?;
     
     // Create app state
-    let mut app = App::new(api_key, "Player 1".to_string());
+    let mut app = App::new(api_key, "Player 1".to_string(), config);
+    
+    // An autosave only survives when the last session didn't exit cleanly
+    if let Some(drill) = drill {
+        app.start_drill(drill);
+    } else if let Some(snapshot) = session::read_autosave() {
+        app.offer_recovery(snapshot);
+    }
     
     // Main game loop
     loop {
+        // Advance the staged flop reveal
+        app.update_flop_reveal();
+        
         // Handle bot actions if needed
         process_bot_actions(&mut app);
         
-        // Draw the UI
+        // Narrate a deal that didn't go through the app (the engine starting the next hand itself),
+        // announce the human's turn, and turn bets and payouts into notes beside the pot. This runs
+        // before the automatic actions below so a turn prompt lands ahead of what's done for it.
+        app.narrate_game_events();
+        
+        // Muck weak hands facing a preflop raise (with --auto-fold), then act on
+        // the human's standing call instruction
+        app.apply_auto_fold();
+        app.apply_auto_call();
+        
+        // Pot odds and a call verdict for the human's decision (with --pot-odds)
+        app.update_pot_odds();
+        
+        // Equity against the range the human has put an opponent on (V)
+        app.update_range_equity();
+        
+        // An amount typed for a turn that has passed shouldn't carry over to the next decision
+        app.clear_stale_input();
+        
+        // Make sure a bot's turn can never stall the game
+        app.check_bot_watchdog();
+        
+        // ...and neither can a menu left open on the human's turn
+        app.check_modal_timeout();
+        
+        // Events raised since (by the automatic actions above), and pot notes that have had their time
+        app.narrate_game_events();
+        app.expire_transients();
+        
+        // Show any engine warnings raised outside a bot action (e.g. at showdown)
+        app.flush_debug_log();
+        
+        // A bot's reaction to the hand that just finished
+        app.flush_table_talk();
+        app.flush_achievements();
+        
+        // Knockouts, and the victory screen once every bot is out of chips
+        app.check_eliminations();
+        
+        // Offer to quit at the stop-loss or stop-win (with --stop-loss / --stop-win)
+        app.check_session_limits();
+        
+        // Save the session once a hand has been settled
+        app.autosave_if_settled();
+        
+        // Keep the log following new messages unless the player has scrolled back
+        app.follow_log();
+        
+        // Draw the UI (read-only: anything that changes the log happens above)
         terminal.draw(|f| {
-            ui::render_ui(f, &mut app);
+            ui::render_ui(f, &app);
         })?;
         
         // Handle events with a timeout
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
-                // Process keys
-                app.on_key(key.code);
+                // Process keys (Alt+key folds showing cards)
+                match key.code {
+                    KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) => app.on_alt_key(c),
+                    code => app.on_key(code),
+                }
                 if app.should_quit {
                     break;
                 }
@@ -56,12 +160,38 @@ fn main() -> Result<(), io::Error> {
     disable_raw_mode()?;
     execute!(io::stdout(), LeaveAlternateScreen)?;
     
+    // Clean exit: nothing to recover next time, but the table and reads carry over
+    // (a drill never offered the autosave, so leave it for the next normal session)
+    if app.drill.is_none() {
+        session::clear_autosave();
+    }
+    if let Err(e) = app.save_profile() {
+        eprintln!("Could not save profile: {}", e);
+    }
+    
+    for line in app.exit_summary() {
+        println!("{}", line);
+    }
+    
     Ok(())
 }
 
+// Play the audit hands, print the report and exit: 0 if every check passed, 1 otherwise
+fn run_deal_audit(hands: u64, seed: u64) -> ! {
+    let started = std::time::Instant::now();
+    let audit = audit::audit_deals(hands, seed);
+    println!("{} hands, {} seats, seed {} ({:.1}s)\n", audit.hands, audit::AUDIT_SEATS, seed, started.elapsed().as_secs_f64());
+    let (lines, passed) = audit.report();
+    for line in lines {
+        println!("{}", line);
+    }
+    println!("\nOverall: {}", if passed { "PASS" } else { "FAIL" });
+    std::process::exit(if passed { 0 } else { 1 });
+}
+
 // Process bot actions - extracted from the main loop to make it more modular
 fn process_bot_actions(app: &mut App) {
-    if app.game_active && app.game.players[app.game.current_player_idx].is_bot {
+    if app.game_active && !app.game.chop_pending && app.game.players[app.game.current_player_idx].is_bot {
         if app.bot_thinking {
             if std::time::Instant::now() >= app.bot_think_until {
                 app.bot_thinking = false;
@@ -69,25 +199,17 @@ fn process_bot_actions(app: &mut App) {
                 std::thread::sleep(Duration::from_millis(50));
             }
         } else {
-            let bot_player = &app.game.players[app.game.current_player_idx].clone();
-            app.game.last_action_count += 1;
+            let bot_player = app.game.players[app.game.current_player_idx].clone();
             
-            if app.game.round != game::Round::PreFlop && app.game.community_cards.is_empty() {
-                app.messages.push(format!("Dealing cards for {:?} round", app.game.round));
-                app.game.deal_community_cards();
-            }
+            // next_round deals every street, so a betting street is never without a board
+            debug_assert!(!matches!(app.game.round, game::Round::Flop | game::Round::Turn | game::Round::River)
+                          || !app.game.community_cards.is_empty(),
+                          "{} to act on the {:?} with no board dealt", bot_player.name, app.game.round);
             
-            // Get bot action
-            match app.game.get_bot_action(bot_player) {
-                Ok(bot_action) => {
-                    // Process the bot's action
-                    app.process_bot_action(bot_action, bot_player.clone());
-                },
-                Err(e) => {
-                    app.messages.push(format!("Bot error: {}", e));
-                    // End the game on error to prevent loops
-                    app.game_active = false;
-                }
+            // An API decision comes back on a later pass; keep drawing until it does
+            if let Some(bot_action) = app.poll_bot_decision(&bot_player) {
+                app.game.last_action_count += 1;
+                app.process_bot_action(bot_action, bot_player);
             }
         }
     }
@@ -0,0 +1,232 @@
+// Long-lived player profile: the bots you've been playing and what you've learned about them.
+// Unlike the autosave this survives clean exits, so the same characters and reads carry over.
+
+use std::collections::HashMap;
+#[cfg(feature = "history")]
+use std::fs;
+use std::path::PathBuf;
+
+use crate::achievements::EarnedAchievement;
+use crate::game::{BotDifficulty, Game, Player, MAX_SEATS};
+#[cfg(feature = "history")]
+use crate::session;
+
+// How one opponent's hands have ended, for studying the bots
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct OpponentRecord {
+    pub hands: u32,         // Hands dealt to them
+    pub folded: u32,        // Hands they gave up before the pot was settled
+    pub showdowns: u32,     // Hands they took to a contested showdown
+    pub showdown_wins: u32, // Showdowns where they won the main pot or a side pot
+}
+
+impl OpponentRecord {
+    // Share of dealt hands they folded
+    pub fn muck_rate(&self) -> f64 {
+        if self.hands > 0 { self.folded as f64 * 100.0 / self.hands as f64 } else { 0.0 }
+    }
+    
+    // Share of their showdowns they won something at
+    pub fn showdown_win_rate(&self) -> f64 {
+        if self.showdowns > 0 { self.showdown_wins as f64 * 100.0 / self.showdowns as f64 } else { 0.0 }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct RosterBot {
+    pub name: String, // Seat name, e.g. "Bot 4"
+    pub difficulty: BotDifficulty,
+    #[cfg_attr(feature = "history", serde(default))]
+    pub id: u32,      // With the difficulty, the key for the bot's record (0 in older profiles)
+}
+
+// Fields are all defaulted so profiles written by older versions still load
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct Profile {
+    pub roster: Vec<RosterBot>,                       // Bots at the table when the profile was last saved, in seat order
+    pub opponents: HashMap<String, OpponentRecord>,   // Accumulated records, keyed by Player::record_key
+    pub achievements: Vec<EarnedAchievement>,         // Badges the player has earned, oldest first
+    pub next_bot_id: u32,                             // Ids below this have been given out (and may have records)
+}
+
+impl Profile {
+    // The table's current bots together with the records kept on them, and the player's badges
+    pub fn capture(game: &Game, opponents: &HashMap<String, OpponentRecord>, achievements: &[EarnedAchievement]) -> Self {
+        Profile {
+            roster: game.players.iter()
+                .filter(|p| p.is_bot)
+                .map(|p| RosterBot { name: p.name.clone(), difficulty: p.bot_difficulty.clone(), id: p.bot_id })
+                .collect(),
+            opponents: opponents.clone(),
+            achievements: achievements.to_vec(),
+            next_bot_id: game.next_bot_id,
+        }
+    }
+    
+    // Profiles from before bot ids kept records under the bot's name. Give those bots ids and
+    // move their records to the new keys; records of bots no longer seated can't be matched
+    // to anyone and are dropped.
+    #[cfg(feature = "history")]
+    fn upgrade(&mut self) {
+        if self.roster.iter().all(|bot| bot.id != 0) {
+            return;
+        }
+        let mut next_id = self.roster.iter().map(|bot| bot.id).max().unwrap_or(0).max(self.next_bot_id.saturating_sub(1)) + 1;
+        let mut opponents = HashMap::new();
+        for bot in &mut self.roster {
+            let old_key = bot.name.clone();
+            if bot.id == 0 {
+                bot.id = next_id;
+                next_id += 1;
+            }
+            let key = crate::game::bot_record_key(&bot.difficulty, bot.id);
+            if let Some(record) = self.opponents.remove(&old_key).or_else(|| self.opponents.remove(&key)) {
+                opponents.insert(key, record);
+            }
+        }
+        self.opponents = opponents;
+        self.next_bot_id = next_id;
+    }
+    
+    // Seat the saved roster after the human on a fresh game, everyone on `chips`
+    pub fn seat_roster(&self, game: &mut Game, chips: u32) -> Result<(), String> {
+        if self.roster.is_empty() || self.roster.len() + 1 > MAX_SEATS {
+            return Err(format!("Profile has {} bots, expected 1 to {}", self.roster.len(), MAX_SEATS - 1));
+        }
+        let Some(human) = game.players.iter().find(|p| !p.is_bot).cloned() else {
+            return Err("No human seat to keep".to_string());
+        };
+        
+        game.players = std::iter::once(human)
+            .chain(self.roster.iter().map(|bot| Player {
+                name: bot.name.clone(),
+                hand: Vec::new(),
+                chips,
+                current_bet: 0,
+                folded: false,
+                all_in: false,
+                is_bot: true,
+                bot_difficulty: bot.difficulty.clone(),
+                bot_id: bot.id,
+            }))
+            .collect();
+        game.settle_bot_ids(self.next_bot_id);
+        game.dealer_idx %= game.players.len();
+        game.reset_seat_tracking();
+        Ok(())
+    }
+}
+
+// ~/.local/share/p_kr/profile (under $XDG_DATA_HOME when that's set), shared with the autosave
+pub fn profile_dir() -> PathBuf {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    data_home.join("p_kr").join("profile")
+}
+
+pub fn profile_path() -> PathBuf {
+    profile_dir().join("profile.json")
+}
+
+// Where older versions kept the profile; read when there's nothing at the new location yet
+#[cfg(feature = "history")]
+fn legacy_profile_path() -> PathBuf {
+    session::legacy_dir().join("profile.json")
+}
+
+#[cfg(feature = "history")]
+pub fn read_profile() -> Option<Profile> {
+    let json = fs::read_to_string(profile_path())
+        .or_else(|_| fs::read_to_string(legacy_profile_path()))
+        .ok()?;
+    parse_profile(&json)
+}
+
+// Without the history feature there's no profile file: each run starts a fresh table
+#[cfg(not(feature = "history"))]
+pub fn read_profile() -> Option<Profile> {
+    None
+}
+
+#[cfg(feature = "history")]
+fn parse_profile(json: &str) -> Option<Profile> {
+    let mut profile: Profile = serde_json::from_str(json).ok()?;
+    profile.upgrade();
+    Some(profile)
+}
+
+#[cfg(feature = "history")]
+pub fn write_profile(profile: &Profile) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(profile).map_err(|e| format!("Could not serialize profile: {}", e))?;
+    session::write_atomically(&profile_path(), &json)
+}
+
+#[cfg(not(feature = "history"))]
+pub fn write_profile(_profile: &Profile) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_lives_under_the_data_directory() {
+        assert!(profile_path().ends_with("p_kr/profile/profile.json"));
+    }
+
+    #[test]
+    fn new_bot_in_a_freed_seat_name_gets_its_own_record() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        let old_bot = game.players[1].clone();
+        game.remove_bot(1).unwrap();
+        let idx = game.add_bot(1000, old_bot.bot_difficulty.clone()).unwrap();
+        let new_bot = &game.players[idx];
+        assert_eq!(new_bot.name, old_bot.name);
+        assert_ne!(new_bot.record_key(), old_bot.record_key());
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn ids_and_records_carry_over_through_the_profile() {
+        let mut game = Game::headless(3, 1000, 5, 10);
+        game.add_bot(1000, BotDifficulty::Hard).unwrap();
+        let mut opponents = HashMap::new();
+        opponents.insert(game.players[3].record_key(), OpponentRecord { hands: 7, ..Default::default() });
+        let json = serde_json::to_string(&Profile::capture(&game, &opponents, &[])).unwrap();
+
+        let profile = parse_profile(&json).unwrap();
+        let mut next_session = Game::new(1, 8, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        profile.seat_roster(&mut next_session, 1000).unwrap();
+        let seated = &next_session.players[4];
+        assert_eq!(seated.record_key(), game.players[3].record_key());
+        assert_eq!(profile.opponents[&seated.record_key()].hands, 7);
+
+        // The next bot to sit down gets an id nobody has had
+        let idx = next_session.add_bot(1000, BotDifficulty::Medium).unwrap();
+        let new_id = next_session.players[idx].bot_id;
+        assert!(game.players.iter().all(|p| p.bot_id != new_id));
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn name_keyed_records_move_to_the_bots_new_keys() {
+        let json = r#"{
+            "roster": [{"name": "Bot 1", "difficulty": "Easy"}, {"name": "Bot 2", "difficulty": "Hard"}],
+            "opponents": {"Bot 2": {"hands": 12, "folded": 3, "showdowns": 4, "showdown_wins": 1},
+                          "Bot 5": {"hands": 2}}
+        }"#;
+        let profile = parse_profile(json).unwrap();
+        let ids: Vec<u32> = profile.roster.iter().map(|bot| bot.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(profile.opponents.len(), 1);
+        assert_eq!(profile.opponents[&crate::game::bot_record_key(&BotDifficulty::Hard, 2)].hands, 12);
+        assert_eq!(profile.next_bot_id, 3);
+    }
+}
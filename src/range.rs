@@ -0,0 +1,89 @@
+// Hand ranges for the equity readout against one opponent: "top 10%" of starting hands,
+// ordered by the Chen formula, as the list of two-card combos it covers. The Monte Carlo
+// draws the opponent's hole cards from that list instead of from the whole deck.
+
+use crate::game::Card;
+
+// Chen formula points, doubled so the half points stay whole numbers
+fn chen_points(a: &Card, b: &Card) -> i32 {
+    let (high, low) = (a.rank.value().max(b.rank.value()), a.rank.value().min(b.rank.value()));
+    let card_points = |value: u8| match value {
+        14 => 20,
+        13 => 16,
+        12 => 14,
+        11 => 12,
+        other => other as i32,
+    };
+    if high == low {
+        return (card_points(high) * 2).max(10);
+    }
+    let mut points = card_points(high);
+    if a.suit == b.suit {
+        points += 4;
+    }
+    let gap = high - low - 1;
+    points -= match gap {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => 8,
+        _ => 10,
+    };
+    // Connected or one-gapped cards below a queen can make more straights
+    if gap <= 1 && high < 12 {
+        points += 2;
+    }
+    points
+}
+
+// Strength key for a combo: Chen points, then the higher card, the lower card and suitedness,
+// so every combo of one starting hand (all the A-J offsuits, say) shares a key
+fn strength(combo: &[Card; 2]) -> (i32, u8, u8, bool) {
+    let (a, b) = (&combo[0], &combo[1]);
+    (chen_points(a, b), a.rank.value().max(b.rank.value()), a.rank.value().min(b.rank.value()), a.suit == b.suit)
+}
+
+#[derive(Clone, Debug)]
+pub struct HandRange {
+    pub percent: u8,
+    combos: Vec<[Card; 2]>, // Every combo in the range, strongest first
+}
+
+impl HandRange {
+    // The strongest `percent` of the starting hands that can be dealt from `deck`, counted in
+    // combos. The cut is rounded up to the end of a starting hand, so a range never holds only
+    // some of a hand's suit combinations.
+    pub fn top(percent: u8, deck: &[Card]) -> HandRange {
+        let percent = percent.clamp(1, 100);
+        let mut all: Vec<[Card; 2]> = Vec::new();
+        for (i, a) in deck.iter().enumerate() {
+            for b in &deck[i + 1..] {
+                all.push([a.clone(), b.clone()]);
+            }
+        }
+        all.sort_by_key(|combo| std::cmp::Reverse(strength(combo)));
+
+        let mut take = (all.len() * percent as usize).div_ceil(100);
+        if let Some(last) = take.checked_sub(1).and_then(|idx| all.get(idx)).map(strength) {
+            while all.get(take).is_some_and(|combo| strength(combo) == last) {
+                take += 1;
+            }
+        }
+        all.truncate(take);
+        HandRange { percent, combos: all }
+    }
+
+    // "top 10%"
+    pub fn label(&self) -> String {
+        format!("top {}%", self.percent)
+    }
+
+    // The range's combos that don't use any of the `dead` cards (the human's hole cards and
+    // the board): what the opponent can actually be holding
+    pub fn live_combos(&self, dead: &[Card]) -> Vec<[Card; 2]> {
+        self.combos.iter()
+            .filter(|combo| !combo.iter().any(|card| dead.contains(card)))
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,447 @@
+// Between-hands session snapshots, used for autosave and crash recovery
+
+#[cfg(feature = "history")]
+use std::fs;
+#[cfg(feature = "history")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::game::{BotDifficulty, Game, Player, MAX_SEATS};
+use crate::profile;
+use crate::util;
+
+// Where a change to the human's stack came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub enum LedgerKind {
+    Ante,
+    Blind,    // Small blind, big blind or straddle
+    Bet,      // Call, bet or raise
+    Refund,   // Uncalled part of a bet handed back
+    PotShare, // Pot, side pot or chop share won
+    Rebuy,    // Buying back in after busting; between hands, so no hand's profit
+}
+
+// One chip movement for the human, signed: negative when chips left their stack
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct LedgerEntry {
+    pub hand: u32,
+    pub kind: LedgerKind,
+    pub amount: i64,
+}
+
+// Session pace, for tuning think times
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionStats {
+    #[cfg_attr(feature = "history", serde(skip, default = "Instant::now"))]
+    pub started_at: Instant, // Saved as session_secs in a snapshot
+    pub bot_think_ms: u64,   // Time spent waiting on bot decisions
+    pub human_think_ms: u64, // Time spent waiting on the human
+    pub rake_paid: u32,      // Rake taken from pots this session
+    pub stop_loss_alerted: bool, // The stop-loss alert has fired (once per session)
+    pub stop_win_alerted: bool,  // The stop-win alert has fired (once per session)
+    pub biggest_pot: u32,    // Largest pot settled this session
+    pub ledger: Vec<LedgerEntry>, // Every chip the human put in or took back, by hand; hand profit is the sum
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        SessionStats::new()
+    }
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        SessionStats {
+            started_at: Instant::now(),
+            bot_think_ms: 0,
+            human_think_ms: 0,
+            rake_paid: 0,
+            stop_loss_alerted: false,
+            stop_win_alerted: false,
+            biggest_pot: 0,
+            ledger: Vec::new(),
+        }
+    }
+    
+    pub fn record(&mut self, hand: u32, kind: LedgerKind, amount: i64) {
+        if amount != 0 {
+            self.ledger.push(LedgerEntry { hand, kind, amount });
+        }
+    }
+    
+    // The human's net for one hand, from the ledger
+    pub fn hand_net(&self, hand: u32) -> i32 {
+        self.ledger.iter().filter(|e| e.hand == hand && e.kind != LedgerKind::Rebuy).map(|e| e.amount).sum::<i64>() as i32
+    }
+    
+    // "blind -$10, bets -$40, pot +$120" for one hand, in the order the kinds first came up
+    pub fn hand_breakdown(&self, hand: u32) -> String {
+        let mut totals: Vec<(LedgerKind, i64)> = Vec::new();
+        for entry in self.ledger.iter().filter(|e| e.hand == hand && e.kind != LedgerKind::Rebuy) {
+            match totals.iter_mut().find(|(kind, _)| *kind == entry.kind) {
+                Some((_, total)) => *total += entry.amount,
+                None => totals.push((entry.kind, entry.amount)),
+            }
+        }
+        totals.iter()
+            .map(|&(kind, total)| {
+                let label = match kind {
+                    LedgerKind::Ante => "ante",
+                    LedgerKind::Blind => "blind",
+                    LedgerKind::Bet => "bets",
+                    LedgerKind::Refund => "returned",
+                    LedgerKind::PotShare => "pot",
+                    LedgerKind::Rebuy => "rebuy",
+                };
+                format!("{} {}${}", label, if total < 0 { "-" } else { "+" }, total.abs())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    
+    pub fn hands_per_hour(&self, hands: usize) -> f64 {
+        let hours = self.started_at.elapsed().as_secs_f64() / 3600.0;
+        if hours > 0.0 { hands as f64 / hours } else { 0.0 }
+    }
+    
+    // "Session 23m: 14 hands (37/hr), waiting on bots 61% / you 39%"
+    pub fn summary(&self, hands: usize) -> String {
+        let minutes = self.started_at.elapsed().as_secs() / 60;
+        let waited = self.bot_think_ms + self.human_think_ms;
+        let bot_share = (self.bot_think_ms * 100).checked_div(waited).unwrap_or(0);
+        let mut summary = format!("Session {}m: {} hands ({:.0}/hr)", minutes, hands, self.hands_per_hour(hands));
+        if waited > 0 {
+            summary.push_str(&format!(", waiting on bots {}% / you {}%", bot_share, 100 - bot_share));
+        }
+        if self.rake_paid > 0 {
+            summary.push_str(&format!(", rake {}", util::format_chips(self.rake_paid)));
+        }
+        summary
+    }
+}
+
+
+// The table's rules beyond the blinds and the betting structure: antes, straddles, the rake,
+// chip denominations and bot rebuys
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableRules {
+    pub ante: u32,
+    pub button_ante: bool,
+    pub allow_straddle: bool,
+    pub bot_straddle_chance: f64,
+    pub rake_percent: u32,
+    pub rake_cap: Option<u32>,
+    pub no_flop_no_drop: bool,
+    pub chip_increment: u32,
+    pub short_deck: bool,
+    pub bot_auto_rebuy: bool,
+    pub max_bot_rebuys: u32,
+}
+
+impl TableRules {
+    pub fn capture(game: &Game) -> Self {
+        TableRules {
+            ante: game.ante,
+            button_ante: game.button_ante,
+            allow_straddle: game.allow_straddle,
+            bot_straddle_chance: game.bot_straddle_chance,
+            rake_percent: game.rake_percent,
+            rake_cap: game.rake_cap,
+            no_flop_no_drop: game.no_flop_no_drop,
+            chip_increment: game.chip_increment,
+            short_deck: game.short_deck,
+            bot_auto_rebuy: game.bot_auto_rebuy,
+            max_bot_rebuys: game.max_bot_rebuys,
+        }
+    }
+
+    pub fn apply_to(&self, game: &mut Game) {
+        game.ante = self.ante;
+        game.button_ante = self.button_ante;
+        game.allow_straddle = self.allow_straddle;
+        game.bot_straddle_chance = self.bot_straddle_chance;
+        game.rake_percent = self.rake_percent;
+        game.rake_cap = self.rake_cap;
+        game.no_flop_no_drop = self.no_flop_no_drop;
+        game.chip_increment = self.chip_increment;
+        game.short_deck = self.short_deck;
+        game.bot_auto_rebuy = self.bot_auto_rebuy;
+        game.max_bot_rebuys = self.max_bot_rebuys;
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedPlayer {
+    pub name: String,
+    pub chips: u32,
+    pub is_bot: bool,
+    #[cfg_attr(feature = "history", serde(default))]
+    pub bot_difficulty: Option<BotDifficulty>, // Older saves don't have it
+    #[cfg_attr(feature = "history", serde(default))]
+    pub bot_id: u32, // 0 in older saves; a fresh id is given on restore
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "history", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionSnapshot {
+    pub players: Vec<SavedPlayer>,
+    pub dealer_idx: usize,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub max_raises_per_street: Option<u32>,
+    #[cfg_attr(feature = "history", serde(default))]
+    pub pot_limit: bool,
+    pub player_starting_chips: u32, // Baseline the human's profit is measured from
+    pub game_stats: Vec<i32>,       // Human profit per settled hand
+    pub hand_results: Vec<bool>,    // Whether the human won each settled hand
+    #[cfg_attr(feature = "history", serde(default))]
+    pub hand_number: u32,           // Hands dealt before the save, so numbering carries on
+    #[cfg_attr(feature = "history", serde(default))]
+    pub table_changes: Vec<String>, // Bots added, removed or retuned between hands, in order
+    #[cfg_attr(feature = "history", serde(default))]
+    pub next_bot_id: u32,           // So a bot seated after the restore doesn't reuse an id
+    #[cfg_attr(feature = "history", serde(default))]
+    pub rules: Option<TableRules>,  // None in older saves: the table keeps this run's rules
+    #[cfg_attr(feature = "history", serde(default))]
+    pub stats: Option<SessionStats>, // Pace, rake paid, limit alerts and the chip ledger
+    #[cfg_attr(feature = "history", serde(default))]
+    pub session_secs: u64,          // How long the session had been going
+}
+
+impl SessionSnapshot {
+    // Capture the table between hands (stacks, button, blinds and rules) along with the
+    // session stats and the history of table changes
+    pub fn capture(game: &Game, player_starting_chips: u32, game_stats: &[i32], hand_results: &[bool], table_changes: &[String],
+                   stats: &SessionStats) -> Self {
+        SessionSnapshot {
+            players: game.players.iter()
+                .map(|p| SavedPlayer {
+                    name: p.name.clone(),
+                    chips: p.chips,
+                    is_bot: p.is_bot,
+                    bot_difficulty: if p.is_bot { Some(p.bot_difficulty.clone()) } else { None },
+                    bot_id: p.bot_id,
+                })
+                .collect(),
+            dealer_idx: game.dealer_idx,
+            small_blind: game.small_blind,
+            big_blind: game.big_blind,
+            max_raises_per_street: game.max_raises_per_street,
+            pot_limit: game.pot_limit,
+            player_starting_chips,
+            game_stats: game_stats.to_vec(),
+            hand_results: hand_results.to_vec(),
+            hand_number: game.hand_number,
+            table_changes: table_changes.to_vec(),
+            next_bot_id: game.next_bot_id,
+            rules: Some(TableRules::capture(game)),
+            stats: Some(stats.clone()),
+            session_secs: stats.started_at.elapsed().as_secs(),
+        }
+    }
+
+    // The saved session stats, with the clock picking up where it left off
+    pub fn restored_stats(&self) -> Option<SessionStats> {
+        let mut stats = self.stats.clone()?;
+        stats.started_at = Instant::now().checked_sub(Duration::from_secs(self.session_secs)).unwrap_or_else(Instant::now);
+        Some(stats)
+    }
+
+    // Put the saved seats, stacks and blinds back on a freshly created game.
+    // The table may have had bots added or removed, so the seats are rebuilt from the save.
+    pub fn restore_into(&self, game: &mut Game) -> Result<(), String> {
+        if self.players.len() < 2 || self.players.len() > MAX_SEATS {
+            return Err(format!("Saved session has {} players, expected 2 to {}", self.players.len(), MAX_SEATS));
+        }
+        if self.players.iter().all(|p| p.is_bot) {
+            return Err("Saved session has no human seat".to_string());
+        }
+
+        let default_difficulty = game.players.iter()
+            .find(|p| p.is_bot)
+            .map(|p| p.bot_difficulty.clone())
+            .unwrap_or(BotDifficulty::Medium);
+        game.players = self.players.iter()
+            .map(|saved| Player {
+                name: saved.name.clone(),
+                hand: Vec::new(),
+                chips: saved.chips,
+                current_bet: 0,
+                folded: false,
+                all_in: false,
+                is_bot: saved.is_bot,
+                bot_difficulty: saved.bot_difficulty.clone().unwrap_or_else(|| default_difficulty.clone()),
+                bot_id: if saved.is_bot { saved.bot_id } else { 0 },
+            })
+            .collect();
+        game.settle_bot_ids(self.next_bot_id);
+
+        game.dealer_idx = self.dealer_idx % game.players.len();
+        game.reset_seat_tracking();
+        game.small_blind = self.small_blind;
+        game.big_blind = self.big_blind;
+        game.max_raises_per_street = self.max_raises_per_street;
+        game.pot_limit = self.pot_limit;
+        if let Some(rules) = &self.rules {
+            rules.apply_to(game);
+        }
+        game.hand_number = self.hand_number;
+        Ok(())
+    }
+}
+
+// Next to the profile: ~/.local/share/p_kr/profile/autosave.json (under $XDG_DATA_HOME when that's set)
+pub fn autosave_path() -> PathBuf {
+    profile::profile_dir().join("autosave.json")
+}
+
+// Where older versions kept their files: ~/.p_kr (or the working directory without a home)
+#[cfg(feature = "history")]
+pub(crate) fn legacy_dir() -> PathBuf {
+    let base = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    base.join(".p_kr")
+}
+
+#[cfg(feature = "history")]
+fn legacy_autosave_path() -> PathBuf {
+    legacy_dir().join("autosave.json")
+}
+
+// Write the snapshot atomically: a crash mid-save leaves the previous autosave intact
+#[cfg(feature = "history")]
+pub fn write_autosave(snapshot: &SessionSnapshot) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(snapshot).map_err(|e| format!("Could not serialize session: {}", e))?;
+    write_atomically(&autosave_path(), &json)
+}
+
+// Write through a temp file and rename, creating the directory if needed
+#[cfg(feature = "history")]
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    }
+
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, contents).map_err(|e| format!("Could not write {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Could not replace {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+// Load the autosave left behind by a session that didn't exit cleanly, if any
+#[cfg(feature = "history")]
+pub fn read_autosave() -> Option<SessionSnapshot> {
+    let json = fs::read_to_string(autosave_path())
+        .or_else(|_| fs::read_to_string(legacy_autosave_path()))
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+// Clean exits remove the autosave so the next launch starts fresh
+#[cfg(feature = "history")]
+pub fn clear_autosave() {
+    let _ = fs::remove_file(autosave_path());
+    let _ = fs::remove_file(legacy_autosave_path());
+}
+
+// Without the history feature nothing goes to disk or comes back from it; the session lives
+// in memory only
+#[cfg(not(feature = "history"))]
+pub fn write_autosave(_snapshot: &SessionSnapshot) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(not(feature = "history"))]
+pub fn read_autosave() -> Option<SessionSnapshot> {
+    None
+}
+
+#[cfg(not(feature = "history"))]
+pub fn clear_autosave() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn table_changes_survive_a_save_and_restore() {
+        let game = Game::headless(3, 1000, 5, 10);
+        let changes = vec!["Before hand 3: Bot 9 sits down with $1000 (Medium)".to_string()];
+        let snapshot = SessionSnapshot::capture(&game, 1000, &[20, -10], &[true, false], &changes, &SessionStats::new());
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: SessionSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.table_changes, changes);
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn saves_from_before_table_changes_still_load() {
+        let game = Game::headless(3, 1000, 5, 10);
+        let snapshot = SessionSnapshot::capture(&game, 1000, &[], &[], &[], &SessionStats::new());
+        let mut json: serde_json::Value = serde_json::to_value(&snapshot).unwrap();
+        for key in ["table_changes", "rules", "stats", "session_secs"] {
+            json.as_object_mut().unwrap().remove(key);
+        }
+        let restored: SessionSnapshot = serde_json::from_value(json).unwrap();
+        assert!(restored.table_changes.is_empty());
+        assert!(restored.rules.is_none());
+        assert!(restored.restored_stats().is_none());
+    }
+
+    #[cfg(feature = "history")]
+    #[test]
+    fn rules_stats_and_ledger_survive_a_save_and_load() {
+        let mut game = Game::new(1, 2, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        game.ante = 2;
+        game.button_ante = true;
+        game.allow_straddle = true;
+        game.bot_straddle_chance = 0.25;
+        game.rake_percent = 5;
+        game.rake_cap = Some(3);
+        game.no_flop_no_drop = true;
+        game.chip_increment = 5;
+        game.bot_auto_rebuy = true;
+        game.max_bot_rebuys = 2;
+        game.hand_number = 12;
+
+        let mut stats = SessionStats::new();
+        stats.started_at = Instant::now() - Duration::from_secs(600);
+        stats.bot_think_ms = 4000;
+        stats.human_think_ms = 9000;
+        stats.rake_paid = 7;
+        stats.stop_loss_alerted = true;
+        stats.biggest_pot = 340;
+        stats.record(12, LedgerKind::Blind, -10);
+        stats.record(12, LedgerKind::PotShare, 45);
+
+        let snapshot = SessionSnapshot::capture(&game, 1000, &[35], &[true], &[], &stats);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: SessionSnapshot = serde_json::from_str(&json).unwrap();
+
+        let mut fresh = Game::new(1, 2, BotDifficulty::Medium, 1000, 5, 10, None, "Tester".to_string());
+        loaded.restore_into(&mut fresh).unwrap();
+        assert_eq!(TableRules::capture(&fresh), TableRules::capture(&game));
+        assert_eq!(fresh.hand_number, 12);
+
+        let restored = loaded.restored_stats().unwrap();
+        assert_eq!((restored.bot_think_ms, restored.human_think_ms), (4000, 9000));
+        assert_eq!(restored.rake_paid, 7);
+        assert!(restored.stop_loss_alerted && !restored.stop_win_alerted);
+        assert_eq!(restored.biggest_pot, 340);
+        assert_eq!(restored.hand_net(12), 35);
+        assert_eq!(restored.ledger.len(), 2);
+        assert!(restored.started_at.elapsed() >= Duration::from_secs(600), "the session clock carries on");
+    }
+
+    #[test]
+    fn autosave_shares_the_profile_directory() {
+        assert_eq!(autosave_path().parent(), profile::profile_path().parent());
+        assert!(autosave_path().ends_with("p_kr/profile/autosave.json"));
+    }
+}
@@ -0,0 +1,115 @@
+// Pluggable decision-making for seats played by code (simulations, tournaments)
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::game::{BotDifficulty, Game, GameAction};
+
+/// How a seat played by code decides. A strategy only sees the table through `&Game`.
+///
+/// ```
+/// use p_kr::game::{Game, GameAction};
+/// use p_kr::simulator::Simulator;
+/// use p_kr::strategy::{CallingStation, Strategy};
+///
+/// // Folds everything it can
+/// struct Nit;
+///
+/// impl Strategy for Nit {
+///     fn name(&self) -> String {
+///         "Nit".to_string()
+///     }
+///
+///     fn decide(&mut self, game: &Game, _player_idx: usize) -> GameAction {
+///         if game.legal_actions().can_check { GameAction::Check } else { GameAction::Fold }
+///     }
+/// }
+///
+/// let mut simulator = Simulator::new(vec![Box::new(Nit), Box::new(CallingStation)], 1000, 5, 10, 1);
+/// simulator.run(20);
+/// assert_eq!(simulator.hands_played, 20);
+/// assert_eq!(simulator.net.iter().sum::<i64>(), 0);
+/// ```
+pub trait Strategy {
+    // Short label for results tables
+    fn name(&self) -> String;
+
+    // Choose an action for `player_idx`, who is the player to act in `game`
+    fn decide(&mut self, game: &Game, player_idx: usize) -> GameAction;
+}
+
+// The same heuristics the built-in table bots use, at a fixed difficulty
+pub struct HeuristicBot {
+    pub difficulty: BotDifficulty,
+}
+
+impl Strategy for HeuristicBot {
+    fn name(&self) -> String {
+        format!("Heuristic ({:?})", self.difficulty)
+    }
+
+    fn decide(&mut self, game: &Game, player_idx: usize) -> GameAction {
+        let mut player = game.players[player_idx].clone();
+        player.bot_difficulty = self.difficulty.clone();
+        game.heuristic_bot_action(&player)
+    }
+}
+
+// Plays by Monte Carlo equity: raises strong hands, calls when the pot odds justify it
+pub struct EquityBot {
+    pub iterations: usize,
+    rng: StdRng,
+}
+
+impl EquityBot {
+    pub fn new(iterations: usize, seed: u64) -> Self {
+        EquityBot { iterations, rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Strategy for EquityBot {
+    fn name(&self) -> String {
+        format!("Equity ({} sims)", self.iterations)
+    }
+
+    fn decide(&mut self, game: &Game, player_idx: usize) -> GameAction {
+        let legal = game.legal_actions();
+        let equity = game.estimate_equity(player_idx, self.iterations, &mut self.rng);
+
+        // Value-raise the minimum with a clear edge over the field
+        if equity > 0.6 {
+            if let Some(min_to) = legal.min_raise_to {
+                return GameAction::Raise(min_to - game.players[player_idx].current_bet);
+            }
+        }
+
+        if legal.can_check {
+            return GameAction::Check;
+        }
+
+        // Call when our share of the final pot covers the price
+        let pot_after_call = (game.pot + legal.to_call) as f64;
+        if equity * pot_after_call >= legal.to_call as f64 {
+            GameAction::Call
+        } else {
+            GameAction::Fold
+        }
+    }
+}
+
+// Never folds, never raises
+pub struct CallingStation;
+
+impl Strategy for CallingStation {
+    fn name(&self) -> String {
+        "Calling station".to_string()
+    }
+
+    fn decide(&mut self, game: &Game, _player_idx: usize) -> GameAction {
+        if game.legal_actions().can_check {
+            GameAction::Check
+        } else {
+            GameAction::Call
+        }
+    }
+}
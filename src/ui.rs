@@ -5,34 +5,98 @@ use tui::{
     layout::{Constraint, Direction, Layout},
     style::{Style, Modifier, Color},
     text::{Span, Line},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::app::App;
+use crate::app::{App, Setting, TRANSIENT_LIFETIME};
+use crate::game::{Card, Game};
 // Removed unused import Round
+use crate::coach;
+use crate::keys::KeyAction;
+use crate::util;
 use crate::util::get_player_position;
 
+// One color per bot, mid-tone 256-color entries so all nine read on dark and light terminals.
+// Red and green are left out so they keep meaning short stack / chip leader / wins.
+const SEAT_PALETTE: [Color; 9] = [
+    Color::Indexed(33),  // Blue
+    Color::Indexed(166), // Orange
+    Color::Indexed(127), // Magenta
+    Color::Indexed(37),  // Teal
+    Color::Indexed(136), // Gold
+    Color::Indexed(98),  // Purple
+    Color::Indexed(130), // Brown
+    Color::Indexed(67),  // Slate
+    Color::Indexed(169), // Pink
+];
+
+// Color for the player in a seat. Bots are keyed by their number rather than the seat index,
+// so the color follows "Bot 4" when seats are added or removed. The human keeps the default color.
+fn seat_color(game: &Game, idx: usize) -> Color {
+    let player = &game.players[idx];
+    if !player.is_bot {
+        return Color::Reset;
+    }
+    let number = player.name.strip_prefix("Bot ")
+        .and_then(|n| n.parse::<usize>().ok())
+        .unwrap_or(idx);
+    SEAT_PALETTE[number.saturating_sub(1) % SEAT_PALETTE.len()]
+}
+
+// Split a log line so a leading bot name ("Bot 3 raises ...") takes that bot's seat color
+fn seat_tagged_spans(game: &Game, text: String, style: Style) -> Vec<Span<'static>> {
+    let speaker = game.players.iter().enumerate().find(|(_, p)| {
+        p.is_bot && text.strip_prefix(p.name.as_str())
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+    });
+    match speaker {
+        Some((idx, player)) => {
+            let rest = text[player.name.len()..].to_string();
+            vec![
+                Span::styled(player.name.clone(), style.fg(seat_color(game, idx)).add_modifier(Modifier::BOLD)),
+                Span::styled(rest, style),
+            ]
+        }
+        None => vec![Span::styled(text, style)],
+    }
+}
+
 // Render the application UI
-pub fn render_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+pub fn render_ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+    // One pinned status line across the top, everything else below it
+    let outer_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1),   // Status line
+            Constraint::Min(0),      // Main game area and sidebar
+        ].as_ref())
+        .split(f.size());
+    
     // Create horizontal split first for main area and sidebar
     let horizontal_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .margin(1)
         .constraints([
             Constraint::Percentage(75),   // Main game area (75% of width)
             Constraint::Percentage(25),   // Right sidebar (25% of width)
         ].as_ref())
-        .split(f.size());
+        .split(outer_chunks[1]);
         
-    // Split the main area vertically
+    // Split the main area vertically. The rows left after the fixed panels are shared between
+    // game info and the log by app.log_share ('+'/'-'), keeping 6 for info and 10 for the log.
+    let free_rows = horizontal_chunks[0].height.saturating_sub(4 + 3 + 3);
+    let log_rows = ((free_rows as u32 * app.log_share as u32 / 100) as u16)
+        .min(free_rows.saturating_sub(6))
+        .max(10);
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(4),   // Reduced game info (status info only)
-            Constraint::Length(3),   // Community cards
+            Constraint::Min(6),      // Reduced game info (status info + leaderboard)
+            Constraint::Length(4),   // Community cards and this hand's betting by street
             Constraint::Length(3),   // Player hand
-            Constraint::Min(10),     // Messages (expanded)
+            Constraint::Length(log_rows), // Messages (expanded)
             Constraint::Length(3),   // Input
         ].as_ref())
         .split(horizontal_chunks[0]);
@@ -41,11 +105,14 @@ pub fn render_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let sidebar_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(10),  // Game status area (now on top)
+            Constraint::Length(14),  // Game status area (now on top)
             Constraint::Min(12),     // Stack/pot info with visualizations (now below)            
         ].as_ref())
         .split(horizontal_chunks[1]);
     
+    // The essentials, always in view
+    render_status_line(f, app, outer_chunks[0]);
+    
     // Game info widget (top area with core game status)
     render_game_info(f, app, main_chunks[0]);
     
@@ -61,135 +128,232 @@ pub fn render_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     // Player's hand widget
     render_player_hand(f, app, main_chunks[2]);
     
-    // Messages widget (with scrolling)
-    render_messages(f, app, main_chunks[3]);
+    // Messages widget (with scrolling), or the settings screen in its place while it's open
+    if app.input_mode == crate::app::InputMode::Settings {
+        render_settings(f, app, main_chunks[3]);
+    } else {
+        render_messages(f, app, main_chunks[3]);
+    }
     
     // Input widget
     render_input(f, app, main_chunks[4]);
+    
+    // Winning the whole table takes over the screen
+    if app.input_mode == crate::app::InputMode::TableWon {
+        render_victory(f, app);
+    }
+}
+
+// Full-screen summary once the human has every chip at the table
+fn render_victory<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let area = f.size();
+    let max_width = (area.width as usize).saturating_sub(6);
+    let mut lines = vec![
+        Line::from(Span::styled("You won the table!", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+    ];
+    lines.extend(app.victory_summary().into_iter().map(|line| Line::from(util::truncate_to_width(&line, max_width))));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("n: new session | r: bots rebuy and play on | q: quit",
+                                       Style::default().fg(Color::Yellow))));
+    
+    let victory = Paragraph::new(lines)
+        .block(Block::default().title("Victory").borders(Borders::ALL));
+    f.render_widget(Clear, area);
+    f.render_widget(victory, area);
 }
 
 // Render the game info section - now simplified with player status only
-fn render_game_info<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
+// "Your turn · To call $40 · Flop · Pot $120 · You $880 · [A♥] [K♦] [9♣]"
+// Most urgent first, so a narrow terminal cuts the board rather than whose turn it is
+fn render_status_line<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
+    let human_idx = app.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+    let human = &app.game.players[human_idx];
+    let mut parts = Vec::new();
+    if app.game.hand_number > 0 {
+        parts.push(format!("Hand #{}", app.game.hand_number));
+    }
+    if app.game_active {
+        let current = &app.game.players[app.game.current_player_idx];
+        if current.is_bot {
+            parts.push(format!("{} to act", current.name));
+        } else {
+            parts.push("Your turn".to_string());
+            let to_call = app.game.legal_actions().to_call;
+            if to_call > 0 {
+                parts.push(format!("To call {}", util::format_chips(to_call)));
+            }
+        }
+        parts.push(format!("{:?}", app.game.round));
+        parts.push(format!("Pot {}", util::format_chips(app.game.pot)));
+    } else {
+        parts.push("Between hands".to_string());
+    }
+    parts.push(format!("You {}", util::format_chips(human.chips)));
+    let visible = app.visible_community_cards().min(app.game.community_cards.len());
+    if app.game_active && visible > 0 {
+        parts.push(app.game.community_cards[..visible].iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" "));
+    }
+    
+    let line = util::truncate_to_width(&parts.join(" · "), area.width as usize);
+    let status = Paragraph::new(Line::from(Span::styled(line, Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))));
+    f.render_widget(status, area);
+}
+
+fn render_game_info<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
     // Show whose turn it is - keep brief for small screens 
     let current_player = &app.game.players[app.game.current_player_idx];
     let current_player_name = &current_player.name;
     let turn_info = if !app.game_active {
-        "Press 'd' to deal, 'q' to quit"
+        format!("Press '{}' to deal, '{}' to quit", app.keys.key(KeyAction::Deal), app.keys.key(KeyAction::Quit))
     } else if !current_player.is_bot {
-        "Your turn."
+        // Warn before a call, or the raise being typed, that would leave less than a big blind behind
+        let idx = app.game.current_player_idx;
+        let typed_raise = app.input.trim().parse::<u32>().ok();
+        let warning = match typed_raise {
+            Some(amount) => util::commitment_warning(&app.game, idx, amount).map(|w| format!("raising ${} {}", amount, w)),
+            None => util::commitment_warning(&app.game, idx, app.game.legal_actions().to_call).map(|w| format!("calling {}", w)),
+        };
+        let turn = match warning {
+            Some(warning) => format!("Your turn - {}.", warning),
+            None => "Your turn.".to_string(),
+        };
+        // Pot odds and the call verdict (with --pot-odds)
+        let turn = match &app.pot_odds {
+            Some(advice) => format!("{} Pot odds {} - {}.", turn, advice.ratio, advice.verdict()),
+            None => turn,
+        };
+        // Equity against the opponent's range (V)
+        match &app.range_equity {
+            Some(readout) => format!("{} Vs {} ({}): {:.0}% equity.", turn, readout.opponent, readout.range, readout.equity * 100.0),
+            None => turn,
+        }
     } else {
-        &format!("Waiting for {}", current_player_name)
+        format!("Waiting for {}", current_player_name)
     };
     
-    // Build player turn indicators - shorter format with clear bot numbering
-    let mut player_status = String::new();
+    // Build player turn indicators - shorter format with clear bot numbering.
+    // The chip leader and the short stack are colored so the table dynamic shows at a glance.
+    let mut player_status: Vec<(String, Style)> = Vec::new();
+    let (chip_leaders, short_stacks) = util::stack_extremes(&app.game);
     let max_players_to_show = if f.size().width < 80 { 5 } else { app.game.players.len() };
     
     // Find the human player index
     let human_idx = app.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
     
-    // Track bot number separately from player index
-    let mut bot_num = 1;
-    
     for (idx, player) in app.game.players.iter().enumerate().take(max_players_to_show) {
         // Determine player status indicator
         let status = if idx == app.game.current_player_idx {
             "➤"   // Current turn
         } else if player.folded {
             "✘"   // Folded
+        } else if player.all_in {
+            "A"   // All-in
         } else {
             "·"   // Waiting
         };
         
-        // Create player display name
+        // Create player display name ("You", or B1, B2, ... from the bot's name so the
+        // numbers stay put when seats are added or removed)
         let display_name = if idx == human_idx {
             "You".to_string()
         } else {
-            // Use consistent bot numbering (B1, B2, etc.)
-            let name = format!("B{}", bot_num);
-            bot_num += 1;
-            name
+            util::short_player_name(&app.game, idx)
         };
         
-        player_status.push_str(&format!("{}{} ", display_name, status));
+        // Seat position, e.g. "B3(CO)". The name takes the seat color; the rest shows stack standing.
+        let position = util::short_player_position(&app.game, idx);
+        // Cards the human turned over while folding
+        let shown = if idx == human_idx && player.folded {
+            app.shown_on_fold.iter().map(|c| c.to_string()).collect::<String>()
+        } else {
+            String::new()
+        };
+        let entry = if position.is_empty() {
+            format!("{}{} ", status, shown)
+        } else {
+            format!("({}){}{} ", position, status, shown)
+        };
+        let style = if player.all_in && !player.folded {
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+        } else if chip_leaders.contains(&idx) {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else if short_stacks.contains(&idx) {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        player_status.push((display_name, Style::default().fg(seat_color(&app.game, idx)).add_modifier(Modifier::BOLD)));
+        player_status.push((entry, style));
     }
     
     // Indicate if more players aren't shown
     if app.game.players.len() > max_players_to_show {
-        player_status.push_str(&format!("(+{})", app.game.players.len() - max_players_to_show));
+        player_status.push((format!("(+{})", app.game.players.len() - max_players_to_show), Style::default()));
     }
     
     // Game info
     let human_position = get_player_position(&app.game, human_idx);
     
-    let active_players = app.game.players.iter().filter(|p| !p.folded).count();
-    
-    // Get round result display
-    let result_display = if let Some((winner_name, profit)) = &app.round_results {
-        let profit_str = if *profit >= 0 {
-            format!(" +${}", profit)
-        } else {
-            format!(" -${}", profit.abs())
-        };
-        format!("Last hand: {} won.{}", winner_name, profit_str)
-    } else {
-        "".to_string()
-    };
-    
-    // Game status/controls display - simplified for top area
-    let game_status = if app.game_active {
-        "Game in progress [s: stop game]"
+    // Chip leaderboard - live between hands, frozen at the last hand's end while one is in play
+    let standings = if app.game_active {
+        app.leaderboard.clone()
     } else {
-        "Game not active [d: deal new hand, q: quit]"
+        app.game.standings()
     };
+    let leaders = util::leaderboard_line(&app.game, &standings);
     
     // Calculate available width to ensure no overflow
     let total_width = area.width as usize - 4; // Account for borders
     let truncate_large = total_width < 70; // If screen is narrow, use shorter format
     
+    // Player status, one span per seat, cut off with ".." when it would overflow
+    let mut players_line = vec![Span::raw("Players: ")];
+    let status_width = total_width.saturating_sub(10);
+    let mut used = 0;
+    for (entry, style) in player_status {
+        let width = entry.width();
+        if used + width > status_width {
+            players_line.push(Span::styled(util::truncate_to_width(&entry, status_width.saturating_sub(used)), style));
+            break;
+        }
+        used += width;
+        players_line.push(Span::styled(entry, style));
+    }
+    
     let game_info = Paragraph::new(vec![
         // Player status (with truncation to prevent overflow)
-        Line::from(vec![
-            Span::raw("Players: "),
-            Span::styled(
-                // Ensure player status fits within available width
-                if player_status.len() + 10 > total_width {
-                    // Safe truncation with bounds checking
-                    let safe_len = total_width.saturating_sub(13);
-                    if safe_len > 0 && safe_len < player_status.len() {
-                        format!("{}..", &player_status[0..safe_len])
-                    } else {
-                        player_status.chars().take(total_width.saturating_sub(13)).collect::<String>()
-                    }
-                } else {
-                    player_status
-                }, 
-                Style::default().fg(Color::White))
-        ]),
+        Line::from(players_line),
         // Round and position info
         Line::from(vec![
             Span::raw("Round: "),
             Span::styled(format!("{:?}", app.game.round), Style::default().fg(Color::Green)),
+            Span::raw(" | First: "),
+            Span::styled(util::short_player_name(&app.game, app.game.first_to_act_idx), Style::default().fg(Color::Cyan)),
             Span::raw(" | Position: "),
             Span::styled(
                 // Truncate position name if too long
-                if human_position.len() > 15 && truncate_large {
-                    format!("{}..", &human_position[0..12])
+                if truncate_large {
+                    util::truncate_to_width(&human_position, 14)
                 } else {
                     human_position
                 }, 
                 Style::default().fg(Color::Cyan)
             ),
         ]),
+        // Chip leaders, cut by display width so wide names can't push past the border
+        Line::from(vec![
+            Span::raw("Leaders: "),
+            Span::styled(util::truncate_to_width(&leaders, total_width.saturating_sub(9)), Style::default().fg(Color::Yellow))
+        ]),
         // Game action info (simplified)
         Line::from(vec![
             Span::styled("► ", Style::default().fg(Color::Green)),
             Span::styled(
-                if turn_info.len() + 2 > total_width {
-                    format!("{}..", &turn_info[0..total_width.saturating_sub(5)])
-                } else {
-                    turn_info.to_string()
-                }, 
+                util::truncate_to_width(&turn_info, total_width.saturating_sub(3)), 
                 Style::default().fg(Color::Cyan))
         ])
     ])
@@ -197,17 +361,25 @@ fn render_game_info<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layo
     f.render_widget(game_info, area);
 }
 
-// Render the community cards
+// Pot color by size in big blinds
+fn pot_heat(pot_bb: u32) -> Color {
+    if pot_bb > 60 {
+        Color::Red
+    } else if pot_bb > 20 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
 // Render the chip/pot/bet visualization sidebar
 fn render_chip_info<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
-    // Style the pot amount with color based on size
-    let pot_style = if app.game.pot > 100 {
-        Style::default().fg(Color::Yellow)
-    } else if app.game.pot > 50 {
-        Style::default().fg(Color::Green)
-    } else {
-        Style::default()
-    };
+    // Heat thresholds are measured in big blinds so they mean the same thing at any stakes
+    let bb = app.game.big_blind.max(1);
+    let pot_bb = app.game.pot / bb;
+    
+    // Pot heat: the amount and its dots turn yellow past 20bb and red past 60bb
+    let pot_color = pot_heat(pot_bb);
     
     // Get the human player's chip count
     let player_chips = app.game.players.iter()
@@ -221,38 +393,55 @@ fn render_chip_info<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::
         .max()
         .unwrap_or(0);
     
+    // On the human's turn, how deep the decision really is
+    let human_idx = app.game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+    let effective_line = if app.game_active && app.game.current_player_idx == human_idx {
+        util::effective_stack_line(&app.game, human_idx)
+            .map(|line| util::truncate_to_width(&line, (area.width as usize).saturating_sub(2)))
+            .unwrap_or_default()
+    } else {
+        String::new()
+    };
+    
     // Create a visually interesting display with larger visualizations
     let chip_info = Paragraph::new(vec![
         
-        // Pot section with larger visualization
+        // Pot section with larger visualization; the latest chip movement rides on the label
+        // line so it never pushes the rest down
+        Line::from(match app.transients.last() {
+            Some((text, shown_at)) => {
+                let style = if shown_at.elapsed() < TRANSIENT_LIFETIME / 2 {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                let note = util::truncate_to_width(text, (area.width as usize).saturating_sub(7));
+                vec![Span::raw("POT  "), Span::styled(note, style)]
+            },
+            None => vec![Span::raw("POT")],
+        }),
         Line::from(vec![
-            Span::raw("POT")
-        ]),
-        Line::from(vec![
-            Span::styled(format!("${}", app.game.pot), 
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            Span::styled(util::format_chips(app.game.pot), 
+                Style::default().fg(pot_color).add_modifier(Modifier::BOLD))
         ]),
         Line::from(vec![
             Span::styled(
                 {
-                    let pot = app.game.pot;
-                    if pot < 20 {
+                    if pot_bb < 2 {
                         "○"
-                    } else if pot < 50 {
+                    } else if pot_bb < 5 {
                         "○○"
-                    } else if pot < 100 {
+                    } else if pot_bb < 10 {
                         "●●"
-                    } else if pot < 200 {
+                    } else if pot_bb < 20 {
                         "●●●"
-                    } else if pot < 400 {
+                    } else if pot_bb < 40 {
                         "●●●●"
                     } else {
                         "●●●●●"
                     }
                 },
-                Style::default().fg(if app.game.pot > 200 { Color::Red } 
-                    else if app.game.pot > 100 { Color::Yellow } 
-                    else { Color::Green })
+                Style::default().fg(pot_color)
             )
         ]),
         // Empty line for spacing
@@ -262,39 +451,40 @@ fn render_chip_info<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::
             Span::raw("YOUR CHIPS")
         ]),
         Line::from(vec![
-            Span::styled(format!("${}", player_chips), 
+            Span::styled(util::format_chips(player_chips), 
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         ]),
         Line::from(vec![
             Span::styled(
                 {
-                    if player_chips < 30 {
+                    let chips_bb = player_chips / bb;
+                    if chips_bb < 3 {
                         "□"
-                    } else if player_chips < 70 {
+                    } else if chips_bb < 7 {
                         "□□"
-                    } else if player_chips < 120 {
+                    } else if chips_bb < 12 {
                         "■■"
-                    } else if player_chips < 200 {
+                    } else if chips_bb < 20 {
                         "■■■"
-                    } else if player_chips < 300 {
+                    } else if chips_bb < 30 {
                         "■■■■"
                     } else {
                         "■■■■■"
                     }
                 },
-                Style::default().fg(if player_chips < 50 { Color::Red } 
-                    else if player_chips < 100 { Color::Yellow } 
+                Style::default().fg(if player_chips < 5 * bb { Color::Red } 
+                    else if player_chips < 10 * bb { Color::Yellow } 
                     else { Color::Blue })
             )
         ]),
-        // Empty line for spacing
-        Line::from(vec![Span::raw("")]),
+        // Effective stack on your turn (otherwise an empty line for spacing)
+        Line::from(vec![Span::styled(effective_line, Style::default().fg(Color::Cyan))]),
         // Current bet section
         Line::from(vec![
             Span::raw("CURRENT BET")
         ]),
         Line::from(vec![
-            Span::styled(format!("${}", current_bet), 
+            Span::styled(util::format_chips(current_bet), 
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
         ]),
         Line::from(vec![
@@ -302,20 +492,20 @@ fn render_chip_info<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::
                 {
                     if current_bet == 0 {
                         "-"
-                    } else if current_bet < 10 {
+                    } else if current_bet < bb {
                         "▪"
-                    } else if current_bet < 30 {
+                    } else if current_bet < 3 * bb {
                         "▫▫"
-                    } else if current_bet < 60 {
+                    } else if current_bet < 6 * bb {
                         "▫▫▫"
-                    } else if current_bet < 100 {
+                    } else if current_bet < 10 * bb {
                         "▫▫▫▫"
                     } else {
                         "▫▫▫▫▫"
                     }
                 },
-                Style::default().fg(if current_bet > 70 { Color::Red }
-                    else if current_bet > 30 { Color::Yellow }
+                Style::default().fg(if current_bet > 7 * bb { Color::Red }
+                    else if current_bet > 3 * bb { Color::Yellow }
                     else { Color::Green })
             )
         ])
@@ -331,12 +521,8 @@ fn render_game_status<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
     
     // Last game result
     let result_display = if let Some((winner_name, profit)) = &app.round_results {
-        let profit_str = if *profit >= 0 {
-            format!(" +${}", profit)
-        } else {
-            format!(" -${}", profit.abs())
-        };
-        format!("{} won{}", winner_name, profit_str)
+        let profit_str = format!(" {}", util::format_profit(*profit));
+        util::truncate_to_width(&format!("{} won{}", winner_name, profit_str), (area.width as usize).saturating_sub(2))
     } else {
         "No results yet".to_string()
     };
@@ -344,24 +530,35 @@ fn render_game_status<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
     // Stats
     let stats_display = if !app.game_stats.is_empty() {
         let total_profit = app.game_stats.iter().sum::<i32>();
-        format!("Rounds: {}, Total: ${}{}", 
+        format!("Rounds: {}, Total: {}{}", 
             app.game_stats.len(),
             if total_profit >= 0 { "" } else { "-" }, 
-            total_profit.abs())
+            util::format_chips(total_profit.unsigned_abs()))
     } else {
         "No rounds played".to_string()
     };
     
+    // Current win/loss streak
+    let streak_display = app.streak_display();
+    let streak_color = match app.current_streak() {
+        Some((true, _)) => Color::Green,
+        Some((false, _)) => Color::Red,
+        None => Color::Gray,
+    };
+    
     // Game controls
+    let key = |action| app.keys.key(action);
     let controls = if app.game_active {
-        "s: stop | q: quit"
+        format!("{}: stop | {}: quit", key(KeyAction::Stop), key(KeyAction::Quit))
     } else {
-        "d: deal | n: set name | q: quit"
+        format!("{}: deal | {}: name | {}: new session | {}: quit",
+                key(KeyAction::Deal), key(KeyAction::Name), key(KeyAction::NewSession), key(KeyAction::Quit))
     };
     
     let status_widget = Paragraph::new(vec![
         Line::from(vec![
-            Span::styled("ACTIVE", Style::default().fg(Color::White))
+            Span::styled(if app.game.hand_number > 0 { format!("ACTIVE · Hand #{}", app.game.hand_number) } else { "ACTIVE".to_string() },
+                Style::default().fg(Color::White))
         ]),
         Line::from(vec![
             Span::styled(format!("{} ({} bots)", 
@@ -382,6 +579,19 @@ fn render_game_status<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
         Line::from(vec![
             Span::styled(stats_display, Style::default().fg(Color::Yellow))
         ]),
+        Line::from(vec![
+            Span::styled(streak_display, Style::default().fg(streak_color))
+        ]),
+        Line::from(vec![
+            Span::styled(app.avg_decision_display().unwrap_or_default(), Style::default().fg(Color::Gray))
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:.0} hands/hr", app.session_stats.hands_per_hour(app.game_stats.len())),
+                Style::default().fg(Color::Gray))
+        ]),
+        Line::from(vec![
+            Span::styled(format!("Rabbit hunts left: {}", app.rabbit_hunts_left), Style::default().fg(Color::Gray))
+        ]),
         Line::from(vec![Span::raw("")]),
         Line::from(vec![
             Span::styled("CONTROLS", Style::default().fg(Color::White))
@@ -396,27 +606,78 @@ fn render_game_status<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
 
 fn render_community_cards<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
     // Community cards - ensure they don't overflow
-    let community_text = if app.game.community_cards.is_empty() {
-        "No community cards yet".to_string()
+    // Only show the cards revealed so far (the flop may be mid-reveal)
+    let visible_cards = app.visible_community_cards();
+    let community_text = if visible_cards == 0 {
+        Line::from("No community cards yet")
     } else {
-        let cards_text = app.game.community_cards.iter()
+        let cards = &app.game.community_cards[..visible_cards.min(app.game.community_cards.len())];
+        let cards_text = cards.iter()
             .map(|c| c.to_string())
             .collect::<Vec<_>>()
             .join(" ");
         
-        // Truncate if necessary to prevent overflow
-        if cards_text.len() > area.width as usize - 4 {
-            format!("{}..", &cards_text[0..(area.width as usize - 7)])
+        // After a showdown, light up the board cards that made the winning hand
+        let max_width = (area.width as usize).saturating_sub(4);
+        if !app.winning_cards.is_empty() && cards_text.width() <= max_width {
+            Line::from(highlighted_card_spans(cards.iter().map(|c| c.to_string()), &app.winning_cards))
         } else {
-            cards_text
+            // On the human's turn, point out what the board makes possible (as far as it fits)
+            // (with --coach, led by the human's outs on the flop and turn)
+            let human_turn = app.game_active && !app.game.players[app.game.current_player_idx].is_bot;
+            let mut warnings = Vec::new();
+            if human_turn && app.config.coach && !app.game.short_deck {
+                warnings.extend(coach::count_outs(&app.game.players[app.game.current_player_idx].hand, cards).describe());
+            }
+            if human_turn {
+                warnings.extend(util::board_texture(cards));
+            }
+            let room = max_width.saturating_sub(cards_text.width() + 2);
+            if warnings.is_empty() || room < 8 {
+                // Truncate if necessary to prevent overflow
+                Line::from(util::truncate_to_width(&cards_text, max_width))
+            } else {
+                Line::from(vec![
+                    Span::raw(format!("{}  ", cards_text)),
+                    Span::styled(util::truncate_to_width(&warnings.join(" · "), room), Style::default().fg(Color::Yellow)),
+                ])
+            }
         }
     };
     
-    let community = Paragraph::new(community_text)
+    // Betting so far under the board; the latest streets win when it doesn't all fit
+    let max_width = (area.width as usize).saturating_sub(4);
+    let mut streets = util::street_summaries(&app.game);
+    let mut history = streets.join(" | ");
+    while history.width() > max_width && streets.len() > 1 {
+        streets.remove(0);
+        history = format!("… {}", streets.join(" | "));
+    }
+    let history_line = Line::from(Span::styled(util::truncate_to_width(&history, max_width), Style::default().fg(Color::DarkGray)));
+    
+    let community = Paragraph::new(vec![community_text, history_line])
         .block(Block::default().title("Community Cards").borders(Borders::ALL));
     f.render_widget(community, area);
 }
 
+// Cards as spans with the winning five emphasized and the rest dimmed
+fn highlighted_card_spans<I: Iterator<Item = String>>(cards: I, winning: &[Card]) -> Vec<Span<'static>> {
+    let winning_text: Vec<String> = winning.iter().map(|c| c.to_string()).collect();
+    let mut spans = Vec::new();
+    for (i, card) in cards.enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        let style = if winning_text.contains(&card) {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(card, style));
+    }
+    spans
+}
+
 // Render the player's hand
 fn render_player_hand<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
     // Player's hand - prevent overflow
@@ -431,11 +692,7 @@ fn render_player_hand<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
         .unwrap_or_else(|| "No cards".to_string());
     
     // Truncate if necessary to prevent overflow
-    let hand_text = if hand_text.len() > area.width as usize - 4 {
-        format!("{}..", &hand_text[0..(area.width as usize - 7)])
-    } else {
-        hand_text
-    };
+    let hand_text = util::truncate_to_width(&hand_text, (area.width as usize).saturating_sub(4));
     
     let hand_block = Block::default()
         .title("Your Hand")
@@ -448,7 +705,7 @@ fn render_player_hand<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout
 }
 
 // Render the message log with scrolling
-fn render_messages<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
+fn render_messages<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
     // Messages - improve formatting and handle small screens
     // Calculate max message width with safety margin to prevent overflow
     let max_msg_width = if area.width > 10 { area.width as usize - 8 } else { 2 };
@@ -456,58 +713,48 @@ fn render_messages<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layou
     // Keep more history and allow scrolling
     // Display all messages without limit for scrolling
     let messages: Vec<ListItem> = app.messages.iter()
-        .map(|m| {
+        .enumerate()
+        .map(|(i, m)| {
             // More aggressive truncation for messages
-            let display_msg = if m.len() > max_msg_width {
-                // Ensure we don't go out of bounds with very small windows
-                let end_pos = if max_msg_width > 5 { max_msg_width - 3 } else { 2 };
-                if end_pos < m.len() {
-                    format!("{}..", &m[0..end_pos])
-                } else {
-                    m.clone()
+            // (width-aware, so card glyphs and wide names never split mid-character)
+            let display_msg = util::truncate_to_width(m, max_msg_width.max(4));
+            
+            // The winner's reveal line gets the same used/unused card highlighting as the board
+            if app.winning_reveal_msg == Some(i) && m.width() <= max_msg_width.max(4) {
+                if let Some((who, cards)) = m.split_once(": ") {
+                    let mut spans = vec![Span::styled(format!("{}: ", who), Style::default().fg(Color::Green))];
+                    spans.extend(highlighted_card_spans(cards.split(' ').map(|c| c.to_string()), &app.winning_cards));
+                    return ListItem::new(vec![Line::from(spans)]);
                 }
-            } else {
-                m.clone()
-            };
+            }
             
             // Use appropriate styling for different message types
-            if m.contains("wins") || m.contains("won") {
-                ListItem::new(vec![Line::from(vec![
-                    Span::styled(display_msg, Style::default().fg(Color::Green))
-                ])])
+            let style = if m.starts_with("Achievement unlocked") {
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+            } else if m.starts_with("You've hit your stop-") {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else if m.contains("wins") || m.contains("won") {
+                Style::default().fg(Color::Green)
             } else if m.contains("lost") || m.contains("error") || m.contains("fold") {
-                ListItem::new(vec![Line::from(vec![
-                    Span::styled(display_msg, Style::default().fg(Color::Red))
-                ])])
+                Style::default().fg(Color::Red)
             } else if m.contains("Your") || m.contains("You") {
-                ListItem::new(vec![Line::from(vec![
-                    Span::styled(display_msg, Style::default().fg(Color::Cyan))
-                ])])
+                Style::default().fg(Color::Cyan)
             } else {
-                ListItem::new(vec![Line::from(vec![Span::raw(display_msg)])])
-            }
+                Style::default()
+            };
+            ListItem::new(vec![Line::from(seat_tagged_spans(&app.game, display_msg, style))])
         })
         .collect();
     
     // Create scrollable list using StatefulList
     let mut messages_state = ListState::default();
     
-    // Auto-scroll to bottom if not manually scrolled up,
-    // otherwise keep user's scroll position
+    // The scroll position is kept at the bottom by App::follow_log unless the user scrolled up
     let messages_len = messages.len();
     
     if messages_len > 0 {
-        // If user hasn't scrolled up manually or we're adding new messages
-        if app.message_scroll_pos == 0 || app.message_scroll_pos >= messages_len.saturating_sub(2) {
-            // Auto-scroll to bottom
-            app.message_scroll_pos = messages_len.saturating_sub(1);
-        }
-        
         // This ensures the selected item is always visible
         messages_state.select(Some(app.message_scroll_pos.min(messages_len.saturating_sub(1))));
-    } else {
-        // Empty message list
-        app.message_scroll_pos = 0;
     }
     
     // Create a scrollable style with visual indication
@@ -531,42 +778,89 @@ fn render_messages<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layou
     f.render_stateful_widget(messages_widget, area, &mut messages_state);
 }
 
+// The settings screen: every runtime option with its current value, the selected one highlighted
+fn render_settings<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
+    let label_width = Setting::ALL.iter().map(|s| s.label().width()).max().unwrap_or(0);
+    let max_width = (area.width as usize).saturating_sub(4);
+    let items: Vec<ListItem> = Setting::ALL.iter()
+        .map(|&setting| {
+            let line = format!("{:<width$}  {}", setting.label(), app.setting_value(setting), width = label_width);
+            ListItem::new(util::truncate_to_width(&line, max_width))
+        })
+        .collect();
+    
+    let mut state = ListState::default();
+    state.select(Some(app.settings_cursor));
+    let settings = List::new(items)
+        .block(Block::default().title("Settings").borders(Borders::ALL))
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::REVERSED));
+    f.render_stateful_widget(settings, area, &mut state);
+}
+
 // Render the input field
 fn render_input<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
     // Input with enhanced info about available commands including scroll hints
     let input_title = if app.input_mode == crate::app::InputMode::PlayerName {
-        "Input [Enter name, press 'n' to confirm]".to_string()
+        format!("Input [Enter name, press '{}' to confirm]", app.keys.key(KeyAction::Name))
+    } else if app.input_mode == crate::app::InputMode::RecoverSession {
+        "Input [Recover previous session? y/n]".to_string()
+    } else if app.input_mode == crate::app::InputMode::DealMaking {
+        "Input [Chop the pot by equity? y/n]".to_string()
+    } else if app.input_mode == crate::app::InputMode::TableSetup {
+        "Input [Table: a add | N r remove | N e/m/h difficulty | g done]".to_string()
+    } else if app.input_mode == crate::app::InputMode::PostBlind {
+        "Input [Post your blind? y = post, n = sit out]".to_string()
+    } else if app.input_mode == crate::app::InputMode::Settings {
+        format!("Input [Settings: ↑/↓ select | Enter change | Esc or {} close]", app.keys.key(KeyAction::Settings))
+    } else if app.input_mode == crate::app::InputMode::TableWon {
+        "Input [Table won - n = new session, r = bots rebuy, q = quit]".to_string()
+    } else if app.input_mode == crate::app::InputMode::WhatIf && app.what_if_decision.is_none() {
+        "Input [What if: decision number, Enter | Esc cancel]".to_string()
+    } else if app.input_mode == crate::app::InputMode::WhatIf {
+        "Input [What if instead: f fold | k check | c call | amount + r raise | Esc cancel]".to_string()
+    } else if app.input_mode == crate::app::InputMode::Busted {
+        format!("Input [Out of chips - b = buy in for ${}, q = quit]", app.game.rebuy_stack)
+    } else if app.input_mode == crate::app::InputMode::SessionLimit {
+        "Input [Session limit hit - quit now? y = quit and save, n = continue]".to_string()
+    } else if app.input_mode == crate::app::InputMode::ConfirmNewSession {
+        "Input [New session - reset all chips and stats? y/n]".to_string()
     } else if app.game_active && !app.bot_thinking && !app.game.players[app.game.current_player_idx].is_bot {
         // Show appropriate options based on the current betting situation and player's chips
-        let highest_bet = app.game.players.iter().map(|p| p.current_bet).max().unwrap_or(0);
-        let player = &app.game.players[app.game.current_player_idx];
-        let player_current_bet = player.current_bet;
-        let player_chips = player.chips;
+        let legal = app.game.legal_actions();
+        let player_current_bet = app.game.players[app.game.current_player_idx].current_bet;
         
         // Determine available actions
         let mut available_actions = Vec::new();
         
         // Check/Call option
-        if highest_bet > player_current_bet {
-            if player_chips > 0 {
-                available_actions.push("[c]all");
-            }
-        } else {
-            available_actions.push("[k]heck");
+        if legal.can_check {
+            available_actions.push(app.keys.hint(KeyAction::Check, "check"));
+        } else if legal.to_call > 0 {
+            available_actions.push(format!("{} ${}", app.keys.hint(KeyAction::Call, "call"), legal.to_call));
         }
         
         // Fold option - always available unless checking is free
-        if highest_bet > player_current_bet || player_current_bet > 0 {
-            available_actions.push("[f]old");
+        if !legal.can_check || player_current_bet > 0 {
+            available_actions.push(app.keys.hint(KeyAction::Fold, "fold"));
         }
         
-        // Raise option - only if player has enough chips for min raise
-        let min_raise_amount = highest_bet * 2;
-        if player_chips > (highest_bet - player_current_bet) {
-            // Only show raise if player has chips left after calling
-            if player_chips > (highest_bet - player_current_bet) + app.game.min_bet {
-                available_actions.push("[r]aise");
+        // Raise option - only if the player can cover a minimum raise and the street isn't capped.
+        // The typed raise amount is the chips added now, so show the range in those terms.
+        if let (Some(min_to), Some(max_to)) = (legal.min_raise_to, legal.max_raise_to) {
+            let min_add = min_to.saturating_sub(player_current_bet);
+            let max_add = max_to.saturating_sub(player_current_bet);
+            let raise = app.keys.hint(KeyAction::Raise, "raise");
+            let raise_text = if app.game.pot_limit {
+                format!("{} ${}+ Max raise: ${}", raise, min_add, max_add)
+            } else {
+                format!("{} ${}-${}", raise, min_add, max_add)
+            };
+            match legal.raises_left {
+                Some(left) => available_actions.push(format!("{} ({} left)", raise_text, left)),
+                None => available_actions.push(raise_text),
             }
+        } else if legal.raises_left == Some(0) {
+            available_actions.push("(raises capped)".to_string());
         }
         
         if available_actions.is_empty() {
@@ -574,28 +868,161 @@ fn render_input<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect
         } else {
             format!("Input [{}]", available_actions.join(" "))
         }
-    } else if app.bot_thinking {
-        "Input [WAITING...]".to_string()
+    } else if app.bot_thinking || app.pending_bot_action.is_some() {
+        match app.auto_call {
+            Some((u32::MAX, _)) => "Input [WAITING... auto-call: any]".to_string(),
+            Some((limit, _)) => format!("Input [WAITING... auto-call up to ${}]", limit),
+            None => "Input [WAITING...]".to_string(),
+        }
+    } else if !app.input.is_empty() {
+        format!("Input [{}: use as next session's starting chips]", app.keys.key(KeyAction::SetChips))
     } else {
-        "Input [d:deal q:quit]".to_string()
+        format!("Input [{}:deal {}:settings {}:quit]", app.keys.key(KeyAction::Deal),
+                app.keys.key(KeyAction::Settings), app.keys.key(KeyAction::Quit))
     };
     
-    // Truncate input if it gets too long
-    let display_input = if app.input.len() > area.width as usize - 6 {
-        format!("{}..", &app.input[0..(area.width as usize - 9)])
-    } else {
-        app.input.clone()
-    };
+    // Truncate input if it gets too long (by display width - names can hold wide characters)
+    let display_input = util::truncate_to_width(&app.input, (area.width as usize).saturating_sub(7));
     
     // Also truncate the title if needed
-    let truncated_title = if input_title.len() > area.width as usize - 6 {
-        format!("{}..", &input_title[0..(area.width as usize - 9)])
-    } else {
-        input_title.to_string()
-    };
+    let truncated_title = util::truncate_to_width(&input_title, (area.width as usize).saturating_sub(7));
     
-    let input = Paragraph::new(display_input)
+    // What the typed amount comes to, as it's typed
+    let mut spans = vec![Span::raw(display_input.clone())];
+    if let Some(preview) = amount_preview(app) {
+        spans.push(Span::styled(format!("  {}", preview), Style::default().fg(Color::DarkGray)));
+    }
+    
+    let input = Paragraph::new(Line::from(spans))
         .style(Style::default())
         .block(Block::default().title(truncated_title).borders(Borders::ALL));
     f.render_widget(input, area);
+    
+    // Editing cursor for the amount (digits go in where it is)
+    let human_turn = app.game_active && !app.game.players[app.game.current_player_idx].is_bot;
+    if app.input_mode == crate::app::InputMode::Normal && !app.input.is_empty() && (human_turn || !app.game_active) {
+        let cursor = app.input_cursor.min(display_input.len()) as u16;
+        f.set_cursor((area.x + 1 + cursor).min(area.right().saturating_sub(2)), area.y + 1);
+    }
+}
+
+// "= raise $40 (to $60)", or why the typed amount won't go through as it is
+fn amount_preview(app: &App) -> Option<String> {
+    let human_turn = app.game_active && !app.bot_thinking && !app.game.players[app.game.current_player_idx].is_bot;
+    if app.input_mode != crate::app::InputMode::Normal || !human_turn {
+        return None;
+    }
+    let amount = util::parse_amount(&app.input)?;
+    let legal = app.game.legal_actions();
+    let current_bet = app.game.players[app.game.current_player_idx].current_bet;
+    Some(match (legal.min_raise_to, legal.max_raise_to) {
+        (Some(min_to), Some(max_to)) => {
+            let (min_add, max_add) = (min_to.saturating_sub(current_bet), max_to.saturating_sub(current_bet));
+            if amount < min_add {
+                format!("= ${} - below the ${} minimum raise", amount, min_add)
+            } else if amount > max_add {
+                format!("= ${} - more than the ${} most you can raise", amount, max_add)
+            } else {
+                format!("= raise ${} (to ${})", amount, current_bet + amount)
+            }
+        },
+        _ => format!("= ${} - no raise possible now", amount),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::{backend::TestBackend, Terminal};
+    use crate::config::Config;
+
+    // The screen as text rows, and the buffer for checking styles
+    fn draw(app: &App, width: u16, height: u16) -> (Vec<String>, tui::buffer::Buffer) {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| render_ui(f, app)).unwrap();
+        let buffer = terminal.backend().buffer().clone();
+        let rows = (0..height)
+            .map(|y| (0..width).map(|x| buffer.get(x, y).symbol.clone()).collect::<String>())
+            .collect();
+        (rows, buffer)
+    }
+
+    fn deep_stack_app() -> App {
+        let config = Config { starting_chips: 1_000_000, fresh_table: true, ..Config::default() };
+        let mut app = App::new(None, "Tester".to_string(), config);
+        app.game.deal_cards();
+        app.game_active = true;
+        app
+    }
+
+    // Every column where a panel border runs down the screen, taken from a top border row
+    fn border_columns(row: &str) -> Vec<usize> {
+        row.chars().enumerate()
+            .filter(|(_, c)| matches!(c, '┌' | '┐'))
+            .map(|(x, _)| x)
+            .collect()
+    }
+
+    #[test]
+    fn million_chip_stacks_fit_at_80_columns() {
+        let app = deep_stack_app();
+        let (rows, _) = draw(&app, 80, 30);
+
+        // Amounts are abbreviated and shown whole, in the header and the sidebar
+        let header = rows.iter().position(|row| row.contains("Hand #1")).expect("status header");
+        assert!(rows[header].contains("Pot $24"), "header: {}", rows[header]);
+        assert!(rows[header].contains("You $999k"), "header: {}", rows[header]);
+        assert!(rows.iter().any(|row| row.contains("│$999k")), "sidebar stack missing");
+        assert!(rows.iter().all(|row| !row.contains("999990")), "an unabbreviated stack slipped through");
+
+        // No text runs over a panel edge: every border column stays a border glyph all the way down
+        // to the last panel row
+        let columns = border_columns(&rows[header + 1]);
+        assert_eq!(columns.len(), 4, "expected two panels side by side: {}", rows[header + 1]);
+        let bottom = rows.iter().rposition(|row| row.contains('┘')).unwrap();
+        for row in &rows[header + 1..=bottom] {
+            let cells: Vec<char> = row.chars().collect();
+            for &x in &columns {
+                assert!("│┌┐└┘├┤".contains(cells[x]), "column {} overwritten in {:?}", x, row);
+            }
+        }
+    }
+
+    // The pot amount's color, found under the sidebar's POT label
+    fn pot_color_for(pot_bb: u32) -> Color {
+        let mut app = deep_stack_app();
+        app.game.pot = pot_bb * app.game.big_blind;
+        let (rows, buffer) = draw(&app, 80, 30);
+        let label_row = rows.iter().position(|row| row.contains("│POT")).expect("POT label");
+        let x = rows[label_row].chars().position(|c| c == 'P').unwrap();
+        buffer.get(x as u16, label_row as u16 + 1).fg
+    }
+
+    #[test]
+    fn pot_heat_follows_big_blinds() {
+        assert_eq!(pot_color_for(5), Color::Green);
+        assert_eq!(pot_color_for(20), Color::Green);
+        assert_eq!(pot_color_for(21), Color::Yellow);
+        assert_eq!(pot_color_for(60), Color::Yellow);
+        assert_eq!(pot_color_for(61), Color::Red);
+    }
+
+    #[test]
+    fn wide_names_stay_inside_the_game_info_panel() {
+        let mut app = deep_stack_app();
+        for (n, player) in app.game.players.iter_mut().filter(|p| p.is_bot).enumerate() {
+            player.name = format!("熊猫熊猫熊猫熊猫{}", n);
+        }
+        let (rows, _) = draw(&app, 60, 30);
+        let leaders = rows.iter().position(|row| row.contains("Leaders:")).expect("leaders line");
+        let players = rows.iter().position(|row| row.contains("Players:")).expect("players line");
+        let columns = border_columns(&rows[players - 1]);
+        for row in [players, leaders] {
+            let cells: Vec<char> = rows[row].chars().collect();
+            for &x in &columns {
+                assert!("│┌┐└┘├┤".contains(cells[x]), "column {} overwritten in {:?}", x, rows[row]);
+            }
+        }
+        assert!(rows[leaders].contains(".."), "leaders should be cut: {:?}", rows[leaders]);
+    }
 }
@@ -1,4 +1,6 @@
-use crate::game::Game;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::game::{Card, Game, GameAction, Round, Suit};
 
 // Helper function to get player position name
 pub fn get_player_position(game: &Game, player_idx: usize) -> String {
@@ -8,6 +10,8 @@ pub fn get_player_position(game: &Game, player_idx: usize) -> String {
         return "Small Blind (SB)".to_string();
     } else if player_idx == game.big_blind_idx {
         return "Big Blind (BB)".to_string();
+    } else if game.straddle_idx == Some(player_idx) {
+        return "Straddle (STR)".to_string();
     } else if game.players.len() <= 3 {
         return "".to_string(); // No special positions in very small games besides the blinds
     } 
@@ -47,4 +51,349 @@ pub fn get_player_position(game: &Game, player_idx: usize) -> String {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// Abbreviated position for compact displays: "Cut-off (CO)" -> "CO", "UTG+1" stays as is
+pub fn short_player_position(game: &Game, player_idx: usize) -> String {
+    let position = get_player_position(game, player_idx);
+    match (position.find('('), position.rfind(')')) {
+        (Some(open), Some(close)) if open < close => position[open + 1..close].to_string(),
+        _ => position,
+    }
+}
+
+// Chip amount for fixed-width displays: exact below 10k, abbreviated above
+// (e.g. "$9500", "$12.5k", "$350k", "$1.2M"). Rounds down so a stack is never overstated.
+pub fn format_chips(amount: u32) -> String {
+    if amount >= 100_000_000 {
+        format!("${}M", amount / 1_000_000)
+    } else if amount >= 1_000_000 {
+        format_tenths(amount / 100_000, "M")
+    } else if amount >= 100_000 {
+        format!("${}k", amount / 1_000)
+    } else if amount >= 10_000 {
+        format_tenths(amount / 100, "k")
+    } else {
+        format!("${}", amount)
+    }
+}
+
+// "$12.5k" from 125 tenths, dropping a trailing ".0"
+fn format_tenths(tenths: u32, unit: &str) -> String {
+    if tenths.is_multiple_of(10) {
+        format!("${}{}", tenths / 10, unit)
+    } else {
+        format!("${}.{}{}", tenths / 10, tenths % 10, unit)
+    }
+}
+
+// Signed profit/loss for displays, e.g. "+$40" or "-$1.2M"
+pub fn format_profit(profit: i32) -> String {
+    let sign = if profit >= 0 { "+" } else { "-" };
+    format!("{}{}", sign, format_chips(profit.unsigned_abs()))
+}
+
+// Clean up a typed name: drop control characters, trim, and cut to `max_width` terminal cells
+// (wide characters like CJK and emoji count as two). Empty names are rejected.
+pub fn sanitize_name(raw: &str, max_width: usize) -> Result<String, String> {
+    let cleaned: String = raw.chars().filter(|c| !c.is_control()).collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() {
+        return Err("Name cannot be empty. Please enter a name.".to_string());
+    }
+    Ok(take_width(cleaned, max_width).trim_end().to_string())
+}
+
+// Fit text into `width` terminal cells, ending with ".." when something had to be cut
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    format!("{}..", take_width(text, width.saturating_sub(2)))
+}
+
+// Longest prefix of `text` that fits in `width` cells, never splitting a character
+fn take_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (byte_idx, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &text[..byte_idx];
+        }
+    }
+    text
+}
+
+// Compact player label used in the header ("You", "B3", ...)
+pub fn short_player_name(game: &Game, player_idx: usize) -> String {
+    let player = &game.players[player_idx];
+    if !player.is_bot {
+        "You".to_string()
+    } else {
+        player.name.replace("Bot ", "B")
+    }
+}
+
+// 1 -> "1st", 2 -> "2nd", 11 -> "11th", 23 -> "23rd"
+pub fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+// Seats holding the biggest and the smallest stack (ties included), for highlighting.
+// Busted seats don't count as the short stack, and an even table has neither.
+pub fn stack_extremes(game: &Game) -> (Vec<usize>, Vec<usize>) {
+    let live: Vec<(usize, u32)> = game.players.iter()
+        .enumerate()
+        .filter(|(_, p)| p.chips > 0)
+        .map(|(idx, p)| (idx, p.chips))
+        .collect();
+    let (Some(most), Some(least)) = (live.iter().map(|&(_, c)| c).max(), live.iter().map(|&(_, c)| c).min()) else {
+        return (Vec::new(), Vec::new());
+    };
+    if most == least {
+        return (Vec::new(), Vec::new());
+    }
+    
+    let seats_with = |chips: u32| live.iter().filter(|&&(_, c)| c == chips).map(|&(idx, _)| idx).collect();
+    (seats_with(most), seats_with(least))
+}
+
+// Effective stack for a player still in the hand: against the one opponent heads-up,
+// or against the shortest opponent stack multiway. e.g. "Effective: $320 (vs B3)"
+pub fn effective_stack_line(game: &Game, player_idx: usize) -> Option<String> {
+    if game.players[player_idx].folded {
+        return None;
+    }
+    let opponents: Vec<usize> = game.players.iter()
+        .enumerate()
+        .filter(|&(idx, p)| idx != player_idx && !p.folded)
+        .map(|(idx, _)| idx)
+        .collect();
+    let shortest = opponents.iter().copied().min_by_key(|&idx| game.effective_stack(player_idx, idx))?;
+    let effective = format_chips(game.effective_stack(player_idx, shortest));
+    if opponents.len() == 1 {
+        Some(format!("Effective: {} (vs {})", effective, short_player_name(game, shortest)))
+    } else {
+        Some(format!("Effective: {} (shortest of {})", effective, opponents.len()))
+    }
+}
+
+// A typed chip amount. Surrounding spaces and leading zeros don't matter; anything that isn't
+// a whole number fitting in a u32 is None rather than wrapping or erroring later.
+pub fn parse_amount(text: &str) -> Option<u32> {
+    let text = text.trim();
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let digits = text.trim_start_matches('0');
+    if digits.is_empty() { Some(0) } else { digits.parse().ok() }
+}
+
+// Pot odds as a ratio of the pot to the call, e.g. "3:1" or "2.5:1"
+pub fn pot_odds_ratio(pot: u32, to_call: u32) -> String {
+    let ratio = pot as f64 / to_call.max(1) as f64;
+    if (ratio - ratio.round()).abs() < 0.05 {
+        format!("{:.0}:1", ratio)
+    } else {
+        format!("{:.1}:1", ratio)
+    }
+}
+
+// Share of the final pot a call has to win to break even: the call over the pot after calling
+pub fn break_even_equity(pot: u32, to_call: u32) -> f64 {
+    if to_call == 0 {
+        return 0.0;
+    }
+    to_call as f64 / (pot + to_call) as f64
+}
+
+// Warning when putting `chips_in` more into the pot would leave the player with chips behind
+// but less than a big blind: they're committed, and all-in on the next street anyway
+pub fn commitment_warning(game: &Game, player_idx: usize, chips_in: u32) -> Option<&'static str> {
+    let behind = game.players[player_idx].chips.checked_sub(chips_in)?;
+    if chips_in > 0 && behind > 0 && behind < game.big_blind {
+        Some("this commits your stack (all-in next street anyway)")
+    } else {
+        None
+    }
+}
+
+// Dangers a newer player might overlook on the board so far, e.g.
+// "Board shows 3 hearts - flush possible". Nothing before the flop.
+pub fn board_texture(community: &[Card]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if community.len() < 3 {
+        return warnings;
+    }
+    
+    for suit in [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
+        let count = community.iter().filter(|c| c.suit == suit).count();
+        if count >= 3 {
+            warnings.push(format!("Board shows {} {} - flush possible", count, suit.to_string().to_lowercase()));
+        }
+    }
+    
+    // Three different ranks inside any five-rank window can be filled in by two hole cards
+    // (the Ace counts low as well for the wheel)
+    let mut values: Vec<u8> = community.iter().map(|c| c.rank.value()).collect();
+    if values.contains(&14) {
+        values.push(1);
+    }
+    values.sort();
+    values.dedup();
+    let connected = (1..=10u8).any(|low| values.iter().filter(|&&v| v >= low && v < low + 5).count() >= 3);
+    if connected {
+        warnings.push("Board is coordinated - straights possible".to_string());
+    }
+    
+    let paired = community.iter().enumerate()
+        .any(|(i, card)| community[..i].iter().any(|other| other.rank == card.rank));
+    if paired {
+        warnings.push("Board is paired - full houses possible".to_string());
+    }
+    warnings
+}
+
+// One-line chip leaderboard: top three stacks plus the human's rank if they're not among them,
+// e.g. "B4 $310 · You $255 (2nd) · B1 $190"
+pub fn leaderboard_line(game: &Game, standings: &[(usize, u32)]) -> String {
+    let human_idx = game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+    
+    let mut entries: Vec<String> = standings.iter()
+        .enumerate()
+        .take(3)
+        .map(|(rank, &(idx, chips))| {
+            if idx == human_idx {
+                format!("You {} ({})", format_chips(chips), ordinal(rank + 1))
+            } else {
+                format!("{} {}", short_player_name(game, idx), format_chips(chips))
+            }
+        })
+        .collect();
+    
+    // Always show where the human stands
+    if let Some(rank) = standings.iter().position(|&(idx, _)| idx == human_idx) {
+        if rank >= 3 {
+            entries.push(format!("You {} ({})", format_chips(standings[rank].1), ordinal(rank + 1)));
+        }
+    }
+    
+    // Once players start busting, show how many are left
+    let remaining = standings.iter().filter(|&&(_, chips)| chips > 0).count();
+    if remaining < standings.len() {
+        entries.push(format!("{} left", remaining));
+    }
+    
+    entries.join(" · ")
+}
+
+// This hand's betting, one summary per street so far, e.g.
+// "Preflop: BB $10, UTG raise $30, 2 calls, 4 folds" and "Flop: check, BB bet $40, call".
+// Runs of calls or folds are counted rather than listed.
+pub fn street_summaries(game: &Game) -> Vec<String> {
+    let mut summaries = Vec::new();
+    for (round, name) in [(Round::PreFlop, "Preflop"), (Round::Flop, "Flop"), (Round::Turn, "Turn"), (Round::River, "River")] {
+        let actions: Vec<_> = game.action_history.iter().filter(|a| a.round == round).collect();
+        if actions.is_empty() {
+            continue;
+        }
+        
+        let mut parts: Vec<String> = Vec::new();
+        if round == Round::PreFlop {
+            parts.push(format!("BB {}", format_chips(game.big_blind)));
+        }
+        let mut facing_bet = round == Round::PreFlop;
+        let mut i = 0;
+        while i < actions.len() {
+            let seat = {
+                let position = short_player_position(game, actions[i].player_idx);
+                if position.is_empty() { short_player_name(game, actions[i].player_idx) } else { position }
+            };
+            match actions[i].action {
+                GameAction::Raise(_) => {
+                    let verb = if facing_bet { "raise" } else { "bet" };
+                    let total = actions[i].bet_total.map(format_chips).unwrap_or_default();
+                    parts.push(format!("{} {} {}", seat, verb, total).trim_end().to_string());
+                    facing_bet = true;
+                    i += 1;
+                }
+                GameAction::Check => {
+                    parts.push("check".to_string());
+                    i += 1;
+                }
+                GameAction::Call | GameAction::Fold => {
+                    let is_call = matches!(actions[i].action, GameAction::Call);
+                    let run = actions[i..].iter()
+                        .take_while(|a| if is_call { matches!(a.action, GameAction::Call) } else { matches!(a.action, GameAction::Fold) })
+                        .count();
+                    let word = if is_call { "call" } else { "fold" };
+                    parts.push(if run == 1 { word.to_string() } else { format!("{} {}s", run, word) });
+                    i += run;
+                }
+            }
+        }
+        summaries.push(format!("{}: {}", name, parts.join(", ")));
+    }
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_name_drops_control_characters() {
+        assert_eq!(sanitize_name("Al\x1b[31mice\n", 20).unwrap(), "Al[31mice");
+        assert_eq!(sanitize_name("\tBob\u{7f} ", 20).unwrap(), "Bob");
+        assert!(sanitize_name("\x07\r\n", 20).is_err(), "nothing printable left");
+        assert!(sanitize_name("   ", 20).is_err());
+    }
+
+    #[test]
+    fn sanitize_name_cuts_at_the_width_without_splitting_wide_characters() {
+        assert_eq!(sanitize_name("Eightchr", 8).unwrap(), "Eightchr", "exactly at the limit");
+        assert_eq!(sanitize_name("Ninechars", 8).unwrap(), "Ninechar");
+        // Each of these takes two cells: three fit in seven, the fourth would need an eighth
+        assert_eq!(sanitize_name("日本語名前", 7).unwrap(), "日本語");
+        assert_eq!(sanitize_name("日本語名前", 8).unwrap(), "日本語名");
+        // A cut that leaves a space at the end doesn't keep it
+        assert_eq!(sanitize_name("Ann Lee", 4).unwrap(), "Ann");
+    }
+
+    #[test]
+    fn truncate_to_width_marks_a_cut_and_keeps_inside_the_width() {
+        assert_eq!(truncate_to_width("Bot 12", 6), "Bot 12", "fits exactly");
+        assert_eq!(truncate_to_width("Bot 123", 6), "Bot ..");
+        assert_eq!(truncate_to_width("日本語名前", 10), "日本語名前");
+        let cut = truncate_to_width("日本語名前", 9);
+        assert_eq!(cut, "日本語..");
+        assert!(cut.width() <= 9);
+        // An odd width leaves a cell short rather than splitting a character
+        assert_eq!(truncate_to_width("日本語名前", 6), "日本..");
+        assert_eq!(truncate_to_width("日本語名前", 5), "日..");
+        assert_eq!(truncate_to_width("abc", 2), "..");
+    }
+
+    #[test]
+    fn format_chips_switches_units_at_each_boundary() {
+        assert_eq!(format_chips(0), "$0");
+        assert_eq!(format_chips(9_999), "$9999");
+        assert_eq!(format_chips(10_000), "$10k");
+        assert_eq!(format_chips(12_345), "$12.3k");
+        assert_eq!(format_chips(99_999), "$99.9k");
+        assert_eq!(format_chips(100_000), "$100k");
+        assert_eq!(format_chips(999_999), "$999k");
+        assert_eq!(format_chips(1_000_000), "$1M");
+        assert_eq!(format_chips(1_250_000), "$1.2M");
+        assert_eq!(format_chips(99_999_999), "$99.9M");
+        assert_eq!(format_chips(100_000_000), "$100M");
+        assert_eq!(format_chips(u32::MAX), "$4294M");
+    }
+}
@@ -0,0 +1,109 @@
+// What this binary is, for bug reports: `p_kr --version [--verbose] [--format json]`.
+// The commit, features and rs_poker version come from build.rs.
+
+use crate::{profile, session};
+
+#[derive(Clone, Debug)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_hash: &'static str,  // Short commit, "-dirty" with local changes, or "unknown"
+    pub features: Vec<&'static str>, // Enabled cargo features
+    pub rs_poker: &'static str,  // Hand evaluator version from Cargo.lock
+    pub autosave_path: String,
+    pub profile_path: String,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            git_hash: env!("GIT_HASH"),
+            features: env!("P_KR_FEATURES").split(',').filter(|f| !f.is_empty()).collect(),
+            rs_poker: env!("RS_POKER_VERSION"),
+            autosave_path: session::autosave_path().display().to_string(),
+            profile_path: profile::profile_path().display().to_string(),
+        }
+    }
+
+    // "p_kr 0.1.0 (3f2a9c1)"
+    pub fn short(&self) -> String {
+        format!("p_kr {} ({})", self.version, self.git_hash)
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            self.short(),
+            format!("Features: {}", if self.features.is_empty() { "none".to_string() } else { self.features.join(", ") }),
+            format!("Hand evaluator: rs_poker {}", self.rs_poker),
+            format!("Autosave: {}", self.autosave_path),
+            format!("Profile: {}", self.profile_path),
+        ]
+    }
+
+    pub fn to_json(&self) -> String {
+        let value = serde_json::json!({
+            "version": self.version,
+            "git_hash": self.git_hash,
+            "features": self.features,
+            "rs_poker": self.rs_poker,
+            "autosave_path": self.autosave_path,
+            "profile_path": self.profile_path,
+        });
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+}
+
+// The --version output, if it was asked for. It's answered before any other flag is parsed,
+// so it works whatever else is on the command line.
+pub fn version_output(args: &[String]) -> Option<String> {
+    if !args.iter().any(|arg| arg == "--version" || arg == "-V") {
+        return None;
+    }
+    let info = BuildInfo::current();
+    let json = args.windows(2).any(|pair| pair[0] == "--format" && pair[1] == "json");
+    Some(if json {
+        info.to_json()
+    } else if args.iter().any(|arg| arg == "--verbose") {
+        info.lines().join("\n")
+    } else {
+        info.short()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(args: &[&str]) -> Option<String> {
+        version_output(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn json_output_parses_with_every_key() {
+        let json = output(&["--version", "--format", "json"]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let object = value.as_object().unwrap();
+        let mut keys: Vec<&str> = object.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["autosave_path", "features", "git_hash", "profile_path", "rs_poker", "version"]);
+        assert_eq!(value["version"], env!("CARGO_PKG_VERSION"));
+        assert!(value["features"].is_array());
+        assert!(!value["git_hash"].as_str().unwrap().is_empty());
+    }
+
+    #[test]
+    fn plain_and_verbose_output() {
+        let short = output(&["--version"]).unwrap();
+        assert!(short.starts_with(&format!("p_kr {} (", env!("CARGO_PKG_VERSION"))), "{}", short);
+
+        let verbose = output(&["-V", "--verbose"]).unwrap();
+        for label in ["Features: ", "Hand evaluator: rs_poker ", "Autosave: ", "Profile: "] {
+            assert!(verbose.contains(label), "{:?} missing from:\n{}", label, verbose);
+        }
+    }
+
+    #[test]
+    fn other_flags_get_no_version_output() {
+        assert!(output(&["--verbose", "--format", "json"]).is_none());
+    }
+}
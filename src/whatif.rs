@@ -0,0 +1,147 @@
+// What-if replays of the last hand: the same deal and deck order, with one of the human's
+// decisions changed. The replay runs on a copy of the table, so nothing here touches the
+// real game, its stacks or the session's stats.
+
+use crate::game::{ActionRecord, Game, GameAction, Round};
+
+// Safety stop for a replay that never reaches the end of the hand
+const MAX_REPLAY_ACTIONS: usize = 200;
+
+// How the replayed hand came out
+pub struct WhatIfOutcome {
+    pub lines: Vec<String>, // The hypothetical play from the changed decision on, board cards included
+    pub winner: String,     // "You" or the bot's name
+    pub hand_type: String,
+    pub human_net: i64,     // The human's stack change over the whole hypothetical hand
+    pub guessed: bool,      // Recorded play stopped fitting and the built-in bots filled in the rest
+}
+
+fn street(round: Round) -> &'static str {
+    match round {
+        Round::PreFlop => "preflop",
+        Round::Flop => "flop",
+        Round::Turn => "turn",
+        Round::River => "river",
+        Round::Showdown => "showdown",
+    }
+}
+
+fn verb(action: &GameAction, bet_total: Option<u32>) -> String {
+    match (action, bet_total) {
+        (GameAction::Fold, _) => "fold".to_string(),
+        (GameAction::Check, _) => "check".to_string(),
+        (GameAction::Call, Some(total)) if total > 0 => format!("call (${} in)", total),
+        (GameAction::Call, _) => "call".to_string(),
+        (GameAction::Raise(_), Some(total)) => format!("raise to ${}", total),
+        (GameAction::Raise(amount), None) => format!("raise ${}", amount),
+    }
+}
+
+// The human's decisions in a hand's history, as (index into the history, "Flop: you call ($40 in)")
+pub fn human_decisions(start: &Game, history: &[ActionRecord]) -> Vec<(usize, String)> {
+    history.iter()
+        .enumerate()
+        .filter(|(_, record)| start.players.get(record.player_idx).is_some_and(|p| !p.is_bot))
+        .map(|(idx, record)| {
+            let mut label = street(record.round).to_string();
+            label[..1].make_ascii_uppercase();
+            (idx, format!("{}: you {}", label, verb(&record.action, record.bet_total)))
+        })
+        .collect()
+}
+
+// Whether a recorded action still makes sense at this point of the replay
+fn still_fits(game: &Game, action: &GameAction) -> bool {
+    let legal = game.legal_actions();
+    match action {
+        GameAction::Fold => true,
+        GameAction::Check => legal.can_check,
+        GameAction::Call => !legal.can_check,
+        GameAction::Raise(_) => legal.min_raise_to.is_some(),
+    }
+}
+
+// Play the hand again from `start` (the table as it was dealt), following `history` up to the
+// human's decision at `decision` and playing `alternative` there instead. After that the
+// recorded actions are kept while the same player is due to act on the same street and the
+// action is still legal; from the first one that doesn't fit, the built-in bots play every
+// seat, the human's included. A heads-up all-in is run out rather than chopped.
+pub fn replay(start: &Game, history: &[ActionRecord], decision: usize, alternative: GameAction) -> Result<WhatIfOutcome, String> {
+    if history.get(decision).is_none_or(|record| start.players[record.player_idx].is_bot) {
+        return Err("that isn't one of your decisions".to_string());
+    }
+    let mut game = start.clone();
+    game.api_key = None;
+    game.think_aloud = false;
+    let human_idx = game.players.iter().position(|p| !p.is_bot).unwrap_or(0);
+    let name = |game: &Game, seat: usize| if seat == human_idx { "You".to_string() } else { game.players[seat].name.clone() };
+    let board = |game: &Game| game.community_cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ");
+
+    let mut lines = Vec::new();
+    let mut cursor = 0;
+    let mut guessed = false;
+    let mut board_shown = 0; // Board cards already in the lines (or known at the decision)
+    for _ in 0..MAX_REPLAY_ACTIONS {
+        if game.round == Round::Showdown {
+            break;
+        }
+        let seat = game.current_player_idx;
+        let round = game.round;
+        let mut is_guess = false;
+        let action = if cursor < decision {
+            // Up to the changed decision, everything happens as it did
+            let record = &history[cursor];
+            if record.player_idx != seat || record.round != round {
+                return Err("the replay lost track of the hand before your decision".to_string());
+            }
+            cursor += 1;
+            record.action.clone()
+        } else if cursor == decision {
+            if !still_fits(&game, &alternative) {
+                return Err(format!("you couldn't {} there", verb(&alternative, None)));
+            }
+            cursor += 1;
+            board_shown = game.community_cards.len();
+            alternative.clone()
+        } else {
+            match history.get(cursor) {
+                Some(record) if !guessed && record.player_idx == seat && record.round == round && still_fits(&game, &record.action) => {
+                    cursor += 1;
+                    record.action.clone()
+                },
+                _ => {
+                    guessed = true;
+                    is_guess = true;
+                    let player = game.players[seat].clone();
+                    game.heuristic_bot_action(&player)
+                }
+            }
+        };
+
+        let narrate = cursor > decision;
+        let (played, bet_total) = game.perform_action(action);
+        if narrate {
+            lines.push(format!("{} {}{}", name(&game, seat), verb(&played, bet_total), if is_guess { " (guessed)" } else { "" }));
+        }
+        let hand_continues = game.next_player();
+        if game.chop_pending {
+            game.decline_chop();
+        }
+        if narrate && game.community_cards.len() > board_shown {
+            board_shown = game.community_cards.len();
+            lines.push(format!("Board: {}", board(&game)));
+        }
+        if !hand_continues {
+            break;
+        }
+    }
+
+    let (winner_idx, _, hand_type) = game.determine_winner();
+    Ok(WhatIfOutcome {
+        lines,
+        winner: name(&game, winner_idx),
+        hand_type,
+        human_net: game.players[human_idx].chips as i64 - game.hand_start_chips[human_idx] as i64,
+        guessed,
+    })
+}
@@ -0,0 +1,41 @@
+// A whole hand through the public API: a seeded table, a scripted deck, every street played.
+
+use p_kr::game::{Card, Rank, Round, Suit};
+use p_kr::simulator::Simulator;
+use p_kr::strategy::{CallingStation, Strategy};
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card { rank, suit }
+}
+
+#[test]
+fn seeded_hand_plays_all_four_streets_to_the_scripted_winner() {
+    // Calling stations check every street after the preflop calls, so all four are played
+    let strategies: Vec<Box<dyn Strategy>> = vec![Box::new(CallingStation), Box::new(CallingStation), Box::new(CallingStation)];
+    let mut simulator = Simulator::new(strategies, 1000, 5, 10, 7);
+    simulator.game.stack_deck(&[
+        [card(Rank::Ace, Suit::Spades), card(Rank::King, Suit::Spades)],
+        [card(Rank::Queen, Suit::Hearts), card(Rank::Queen, Suit::Diamonds)],
+        [card(Rank::Seven, Suit::Clubs), card(Rank::Two, Suit::Diamonds)],
+    ], &[
+        card(Rank::Ace, Suit::Hearts), card(Rank::King, Suit::Diamonds), card(Rank::Nine, Suit::Clubs),
+        card(Rank::Four, Suit::Spades), card(Rank::Two, Suit::Hearts),
+    ]);
+
+    let winner = simulator.play_hand();
+    let game = &simulator.game;
+
+    assert_eq!(game.round, Round::Showdown);
+    assert_eq!(game.community_cards.len(), 5);
+    for street in [Round::PreFlop, Round::Flop, Round::Turn, Round::River] {
+        assert!(game.action_history.iter().any(|record| record.round == street), "nobody acted on the {:?}", street);
+    }
+
+    // Aces and kings beat queens and the deuces
+    assert_eq!(winner, 0);
+    // A $1 ante and a $10 call from each of the three seats
+    assert_eq!(game.last_pot, 33);
+    assert_eq!(game.pot, 0);
+    let chips: Vec<u32> = game.players.iter().map(|p| p.chips).collect();
+    assert_eq!(chips, vec![1022, 989, 989]);
+}